@@ -5,7 +5,7 @@ use std::fs::copy;
 use std::fs::remove_file;
 use std::path::Path;
 
-extern crate little_exif;
+extern crate revolt_little_exif as little_exif;
 use little_exif::metadata::Metadata;
 use little_exif::exif_tag::ExifTag;
 use little_exif::exif_tag::ExifTagGroup;
@@ -0,0 +1,43 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// Feeds arbitrary bytes into the decoders, which must never panic but return
+// an error for malformed data instead.
+// The first byte selects what the remaining bytes are interpreted as: 
+// Raw EXIF data (i.e. starting with the "Exif\0\0" header), which gets 
+// wrapped into a minimal JPEG to reach the general decoding directly, or the
+// contents of a JPEG, PNG or WebP file.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use revolt_little_exif::metadata::Metadata;
+use revolt_little_exif::filetype::FileExtension;
+
+fuzz_target!(|data: &[u8]| {
+	let Some((selector, data)) = data.split_first()
+	else
+	{
+		return;
+	};
+
+	match selector % 4
+	{
+		0 => {
+			// APP1 segment: Marker, length (including itself), EXIF data
+			if data.len() > 0xffff - 2
+			{
+				return;
+			}
+			let mut file_buffer = vec![0xff, 0xd8, 0xff, 0xe1];
+			file_buffer.extend(((data.len() + 2) as u16).to_be_bytes());
+			file_buffer.extend(data);
+			file_buffer.extend([0xff, 0xd9]);
+			let _ = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG);
+		},
+		1 => { let _ = Metadata::from_header_bytes(data, FileExtension::JPEG); },
+		2 => { let _ = Metadata::from_header_bytes(data, FileExtension::PNG { as_zTXt_chunk: true }); },
+		_ => { let _ = Metadata::from_header_bytes(data, FileExtension::WEBP); },
+	}
+});
@@ -0,0 +1,103 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+// Generates random but valid metadata, encodes it and decodes the result 
+// again, which has to give the same tags (and thumbnail) as before.
+
+#![no_main]
+
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+use libfuzzer_sys::arbitrary::Unstructured;
+use libfuzzer_sys::arbitrary::Result;
+
+use revolt_little_exif::metadata::Metadata;
+use revolt_little_exif::endian::Endian;
+use revolt_little_exif::exif_tag::ExifTag;
+use revolt_little_exif::exif_tag::ExifTagGroup;
+use revolt_little_exif::exif_tag_format::ExifTagFormat;
+use revolt_little_exif::filetype::FileExtension;
+
+/// The hex values of all known tags that can be written by little_exif
+fn
+writable_tags()
+-> &'static Vec<u16>
+{
+	static TAGS: OnceLock<Vec<u16>> = OnceLock::new();
+	return TAGS.get_or_init(|| (0..=0xffff)
+		.filter(|hex| ExifTag::from_u16(*hex)
+			.map(|tag| tag.is_writable() && tag.get_group() != ExifTagGroup::NO_GROUP)
+			.unwrap_or(false)
+		)
+		.collect()
+	);
+}
+
+/// Generates a known tag with a valid number of components. The values are
+/// restricted to those that survive a round trip unchanged, i.e. no NUL
+/// or non-ASCII characters in strings and no NaN for floating point values
+fn
+arbitrary_tag
+(
+	u: &mut Unstructured
+)
+-> Result<ExifTag>
+{
+	let hex    = *u.choose(writable_tags())?;
+	let empty  = ExifTag::from_u16(hex).unwrap();
+	let format = empty.format();
+
+	let number_of_components = if empty.has_valid_number_of_components()
+	{
+		u.int_in_range(0..=8)? + (format != ExifTagFormat::STRING) as usize
+	}
+	else
+	{
+		empty.number_of_components() as usize - empty.is_string() as usize
+	};
+
+	let mut raw_data = Vec::new();
+	for _ in 0..(number_of_components * format.bytes_per_component() as usize)
+	{
+		raw_data.push(match format
+		{
+			ExifTagFormat::STRING => u.int_in_range(0x20..=0x7e)?,
+			ExifTagFormat::FLOAT  |
+			ExifTagFormat::DOUBLE => u.int_in_range(0x00..=0x3f)?,
+			_                     => u.arbitrary()?,
+		});
+	}
+
+	return Ok(ExifTag::from_u16_with_data(hex, &format, &raw_data, &Endian::Little, &empty.get_group()).unwrap());
+}
+
+fuzz_target!(|data: &[u8]| {
+	let mut u = Unstructured::new(data);
+
+	let mut metadata = Metadata::new();
+	for _ in 0..u.int_in_range(0..=16).unwrap_or(0)
+	{
+		let Ok(tag) = arbitrary_tag(&mut u)
+		else
+		{
+			break;
+		};
+		metadata.set_tag(tag);
+	}
+	if u.arbitrary().unwrap_or(false)
+	{
+		metadata.set_thumbnail(u.bytes(u.len().min(1024)).unwrap().to_vec());
+	}
+
+	// Wrap the APP1 segment(s) into a minimal JPEG for decoding
+	let file_buffer = [
+		vec![0xff, 0xd8], 
+		metadata.as_u8_vec(FileExtension::JPEG), 
+		vec![0xff, 0xd9]
+	].concat();
+	let decoded = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG).unwrap();
+
+	assert!(metadata.diff(&decoded).is_empty(), "{:?}", metadata.diff(&decoded));
+	assert_eq!(metadata.get_thumbnail(), decoded.get_thumbnail());
+});
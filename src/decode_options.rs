@@ -0,0 +1,88 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+/// Options for decoding the metadata of an image, used by e.g.
+/// `Metadata::new_from_path_with_options`. 
+/// 
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::decode_options::DecodeOptions;
+/// use revolt_little_exif::metadata::Metadata;
+/// 
+/// let mut options = DecodeOptions::new();
+/// options.keep_original_raw = true;
+/// 
+/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.jpg"), &options).unwrap();
+/// let raw = metadata.original_raw();
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+DecodeOptions
+{
+	/// Keep the raw EXIF data as provided by the file type specific reader
+	/// (i.e. before decoding it), accessible via `Metadata::original_raw`.
+	/// Disabled by default.
+	pub keep_original_raw: bool,
+
+	/// The number of leading bytes of a JPEG file that may precede its SOI
+	/// marker (`FFD8`), e.g. a UTF-8 BOM or some junk in front of a mangled
+	/// download. Parsing then begins at the first SOI marker found within
+	/// these bytes. 
+	/// Defaults to 0, i.e. the file has to start with the SOI marker.
+	pub jpeg_leading_bytes_limit: usize,
+
+	/// The maximum nesting depth of IFDs (e.g. 3 for IFD0 -> ExifIFD -> 
+	/// InteropIFD) that gets followed when decoding, protecting against
+	/// maliciously crafted files. IFDs linking to themselves (directly or
+	/// via their SubIFDs) are always rejected.
+	/// Defaults to 8.
+	pub max_ifd_depth: usize,
+
+	/// Skip invalid IFD entries (e.g. with an illegal format or data beyond
+	/// the end of the EXIF data) instead of failing to decode the IFD they
+	/// are part of. Each skipped entry is reported by 
+	/// `Metadata::decode_warnings`.
+	/// Disabled by default.
+	pub skip_invalid_entries: bool,
+
+	/// Verify the CRC of each chunk of a PNG file (or of a PNG encoded image
+	/// in an ICO file), failing with an error that names the affected chunk
+	/// in case of a mismatch. Constructors working on bytes only read and
+	/// verify the chunks up to the first IDAT chunk.
+	/// Disabled by default, as many files have stale CRCs after being edited 
+	/// by buggy tools, which otherwise can't be read.
+	pub verify_png_crc: bool,
+}
+
+impl
+Default
+for
+DecodeOptions
+{
+	fn
+	default
+	()
+	-> DecodeOptions
+	{
+		DecodeOptions {
+			keep_original_raw:        false,
+			jpeg_leading_bytes_limit: 0,
+			max_ifd_depth:            8,
+			skip_invalid_entries:     false,
+			verify_png_crc:           false,
+		}
+	}
+}
+
+impl
+DecodeOptions
+{
+	/// Constructs the default options
+	pub fn
+	new
+	()
+	-> DecodeOptions
+	{
+		DecodeOptions::default()
+	}
+}
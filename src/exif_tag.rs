@@ -21,6 +21,118 @@ ExifTagGroup
 	IFD1,
 }
 
+impl
+ExifTagGroup
+{
+	/// Gets the integer code of the group, which is specific to little_exif
+	/// (the IFDs have no such codes in the specifications) and follows the 
+	/// order in which the groups are written, starting with 0 for `NO_GROUP`.
+	/// Can be converted back via `ExifTagGroup::try_from`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let code = ExifTagGroup::GPSIFD.as_u16();
+	/// assert_eq!(ExifTagGroup::try_from(code).unwrap(), ExifTagGroup::GPSIFD);
+	/// ```
+	pub fn
+	as_u16
+	(
+		&self
+	)
+	-> u16
+	{
+		*self as u16
+	}
+}
+
+impl
+TryFrom<u16>
+for
+ExifTagGroup
+{
+	type Error = std::io::Error;
+
+	fn
+	try_from
+	(
+		code: u16
+	)
+	-> Result<ExifTagGroup, Self::Error>
+	{
+		match code
+		{
+			0 => Ok(ExifTagGroup::NO_GROUP),
+			1 => Ok(ExifTagGroup::IFD0),
+			2 => Ok(ExifTagGroup::ExifIFD),
+			3 => Ok(ExifTagGroup::InteropIFD),
+			4 => Ok(ExifTagGroup::MakerNotesIFD),
+			5 => Ok(ExifTagGroup::GPSIFD),
+			6 => Ok(ExifTagGroup::IFD1),
+			_ => Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidData,
+				format!("Illegal group code {}!", code)
+			)),
+		}
+	}
+}
+
+// Wraps the value of a tag into the `TagValue` variant matching its format
+macro_rules! tag_value {
+	(INT8U,         $value:expr) => { TagValue::Byte(       $value.clone()) };
+	(STRING,        $value:expr) => { TagValue::Ascii(      $value.clone()) };
+	(INT16U,        $value:expr) => { TagValue::Short(      $value.clone()) };
+	(INT32U,        $value:expr) => { TagValue::Long(       $value.clone()) };
+	(RATIONAL64U,   $value:expr) => { TagValue::Rational(   $value.clone()) };
+	(INT8S,         $value:expr) => { TagValue::SByte(      $value.clone()) };
+	(UNDEF,         $value:expr) => { TagValue::Undefined(  $value.clone()) };
+	(INT16S,        $value:expr) => { TagValue::SShort(     $value.clone()) };
+	(INT32S,        $value:expr) => { TagValue::SLong(      $value.clone()) };
+	(RATIONAL64S,   $value:expr) => { TagValue::SRational(  $value.clone()) };
+	(FLOAT,         $value:expr) => { TagValue::Float(      $value.clone()) };
+	(DOUBLE,        $value:expr) => { TagValue::Double(     $value.clone()) };
+}
+
+// The number of bytes allocated on the heap for the value of a tag
+trait
+HeapSize
+{
+	fn heap_size(&self) -> usize;
+}
+
+impl
+HeapSize
+for
+String
+{
+	fn
+	heap_size
+	(
+		&self
+	)
+	-> usize
+	{
+		return self.capacity();
+	}
+}
+
+impl<T>
+HeapSize
+for
+Vec<T>
+{
+	fn
+	heap_size
+	(
+		&self
+	)
+	-> usize
+	{
+		return self.capacity() * std::mem::size_of::<T>();
+	}
+}
+
 macro_rules! build_tag_enum {
 	( 
 		$( (
@@ -94,7 +206,7 @@ macro_rules! build_tag_enum {
 			/// 
 			/// # Examples
 			/// ```no_run
-			/// use little_exif::exif_tag::ExifTag;
+			/// use revolt_little_exif::exif_tag::ExifTag;
 			/// 
 			/// let tag = ExifTag::from_u16(0x010e).unwrap();
 			/// ```
@@ -120,13 +232,16 @@ macro_rules! build_tag_enum {
 			/// to the appropriate format.
 			/// If the hex value is unknown, the other parameters are used to
 			/// generate an appropriate unkown tag for the specified format.
+			/// Returns an error if the format does not match the one of the
+			/// known tag or if the length of the raw data is not a multiple
+			/// of the size of a component of the format.
 			/// 
 			/// # Examples
 			/// ```no_run
-			/// use little_exif::endian::Endian;
-			/// use little_exif::exif_tag::ExifTag;
-			/// use little_exif::exif_tag::ExifTagGroup;
-			/// use little_exif::exif_tag_format::ExifTagFormat;
+			/// use revolt_little_exif::endian::Endian;
+			/// use revolt_little_exif::exif_tag::ExifTag;
+			/// use revolt_little_exif::exif_tag::ExifTagGroup;
+			/// use revolt_little_exif::exif_tag_format::ExifTagFormat;
 			/// 
 			/// let tag = ExifTag::from_u16_with_data(
 			///     0x0113,                   // An unknown tag hex value
@@ -147,6 +262,19 @@ macro_rules! build_tag_enum {
 			)
 			-> Result<ExifTag, String>
 			{
+				if raw_data.len() % format.bytes_per_component() as usize != 0
+				{
+					return Err(format!("Data of tag 0x{:04x} does not consist of whole {:?} components!", hex_value, format));
+				}
+
+				if let Ok(known_tag) = Self::from_u16(hex_value)
+				{
+					if known_tag.format() != *format
+					{
+						return Err(format!("Illegal format {:?} for tag 0x{:04x}, expected {:?}!", format, hex_value, known_tag.format()));
+					}
+				}
+
 				match hex_value
 				{
 					$(
@@ -156,26 +284,43 @@ macro_rules! build_tag_enum {
 					)*
 					_ => {
 						// In this case, the given hex_value represents a tag that is unknown
-						match *format
-						{
-							ExifTagFormat::INT8U        => Ok(ExifTag::UnknownINT8U(        <INT8U          as U8conversion<INT8U>>::from_u8_vec(raw_data, endian),         hex_value, *group)),
-							ExifTagFormat::STRING       => Ok(ExifTag::UnknownSTRING(       <STRING         as U8conversion<STRING>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							ExifTagFormat::INT16U       => Ok(ExifTag::UnknownINT16U(       <INT16U         as U8conversion<INT16U>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							ExifTagFormat::INT32U       => Ok(ExifTag::UnknownINT32U(       <INT32U         as U8conversion<INT32U>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							ExifTagFormat::RATIONAL64U  => Ok(ExifTag::UnknownRATIONAL64U(  <RATIONAL64U    as U8conversion<RATIONAL64U>>::from_u8_vec(raw_data, endian),   hex_value, *group)),
-							ExifTagFormat::INT8S        => Ok(ExifTag::UnknownINT8S(        <INT8S          as U8conversion<INT8S>>::from_u8_vec(raw_data, endian),         hex_value, *group)),
-							ExifTagFormat::UNDEF        => Ok(ExifTag::UnknownUNDEF(        <UNDEF          as U8conversion<UNDEF>>::from_u8_vec(raw_data, endian),         hex_value, *group)),
-							ExifTagFormat::INT16S       => Ok(ExifTag::UnknownINT16S(       <INT16S         as U8conversion<INT16S>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							ExifTagFormat::INT32S       => Ok(ExifTag::UnknownINT32S(       <INT32S         as U8conversion<INT32S>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							ExifTagFormat::RATIONAL64S	=> Ok(ExifTag::UnknownRATIONAL64S(	<RATIONAL64S    as U8conversion<RATIONAL64S>>::from_u8_vec(raw_data, endian),   hex_value, *group)),
-							ExifTagFormat::FLOAT        => Ok(ExifTag::UnknownFLOAT(        <FLOAT          as U8conversion<FLOAT>>::from_u8_vec(raw_data, endian),         hex_value, *group)),
-							ExifTagFormat::DOUBLE       => Ok(ExifTag::UnknownDOUBLE(       <DOUBLE         as U8conversion<DOUBLE>>::from_u8_vec(raw_data, endian),        hex_value, *group)),
-							
-						}
+						Ok(Self::unknown_from_u16_with_data(hex_value, format, raw_data, endian, group))
 					},
 				}
 			}
 
+			/// Generates an unknown tag for the specified format and group 
+			/// using the given raw data, even if the hex value belongs to a
+			/// known tag. Needed e.g. for tags that are found in an IFD other
+			/// than the one little_exif expects them to be in.
+			pub(crate) fn
+			unknown_from_u16_with_data
+			(
+				hex_value: u16,
+				format:    &ExifTagFormat,
+				raw_data:  &Vec<u8>,
+				endian:    &Endian,
+				group:     &ExifTagGroup,
+			)
+			-> ExifTag
+			{
+				match *format
+				{
+					ExifTagFormat::INT8U        => ExifTag::UnknownINT8U(        <INT8U          as U8conversion<INT8U>>::from_u8_vec(raw_data, endian),         hex_value, *group),
+					ExifTagFormat::STRING       => ExifTag::UnknownSTRING(       <STRING         as U8conversion<STRING>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+					ExifTagFormat::INT16U       => ExifTag::UnknownINT16U(       <INT16U         as U8conversion<INT16U>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+					ExifTagFormat::INT32U       => ExifTag::UnknownINT32U(       <INT32U         as U8conversion<INT32U>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+					ExifTagFormat::RATIONAL64U  => ExifTag::UnknownRATIONAL64U(  <RATIONAL64U    as U8conversion<RATIONAL64U>>::from_u8_vec(raw_data, endian),   hex_value, *group),
+					ExifTagFormat::INT8S        => ExifTag::UnknownINT8S(        <INT8S          as U8conversion<INT8S>>::from_u8_vec(raw_data, endian),         hex_value, *group),
+					ExifTagFormat::UNDEF        => ExifTag::UnknownUNDEF(        <UNDEF          as U8conversion<UNDEF>>::from_u8_vec(raw_data, endian),         hex_value, *group),
+					ExifTagFormat::INT16S       => ExifTag::UnknownINT16S(       <INT16S         as U8conversion<INT16S>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+					ExifTagFormat::INT32S       => ExifTag::UnknownINT32S(       <INT32S         as U8conversion<INT32S>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+					ExifTagFormat::RATIONAL64S	=> ExifTag::UnknownRATIONAL64S(	<RATIONAL64S    as U8conversion<RATIONAL64S>>::from_u8_vec(raw_data, endian),   hex_value, *group),
+					ExifTagFormat::FLOAT        => ExifTag::UnknownFLOAT(        <FLOAT          as U8conversion<FLOAT>>::from_u8_vec(raw_data, endian),         hex_value, *group),
+					ExifTagFormat::DOUBLE       => ExifTag::UnknownDOUBLE(       <DOUBLE         as U8conversion<DOUBLE>>::from_u8_vec(raw_data, endian),        hex_value, *group),
+				}
+			}
+
 			/// Gives information about whether the data stored in the tag can
 			/// be written to file.
 			/// Needed e.g. for Offset tags where the given value is useless
@@ -183,7 +328,7 @@ macro_rules! build_tag_enum {
 			/// 
 			/// # Examples
 			/// ```no_run
-			/// use little_exif::exif_tag::ExifTag;
+			/// use revolt_little_exif::exif_tag::ExifTag;
 			/// 
 			/// let writable = ExifTag::ImageDescription(String::new());
 			/// let not_writable = ExifTag::ExifOffset(vec![1u32]);
@@ -313,14 +458,17 @@ macro_rules! build_tag_enum {
 				}
 			}
 
-			/// Gets the number of components for the tag. This might be
-			/// predefined by the specifications for some tags (e.g.
-			/// `BitsPerSample` has to have 3 components of type `INT16U`), for
-			/// other tags this depends on the actual data (e.g. most - but not
-			/// all - `STRING` format type tags).
+			/// Gets the number of components for the tag, which is the number
+			/// of values it actually holds - even if the specifications
+			/// predefine another number for the tag (e.g. `BitsPerSample` with
+			/// a single value for grayscale images instead of 3, see 
+			/// `predefined_number_of_components`), so that the tag gets 
+			/// written back as it was read.
 			/// Note that for `STRING` format type tags this includes the NUL
 			/// terminator (which gets written automatically and should not be
-			/// provided by the user). 
+			/// provided by the user). `STRING` format type tags with a 
+			/// predefined number of components always have that number, as 
+			/// shorter strings get padded with NUL characters.
 			pub fn
 			number_of_components
 			(
@@ -333,8 +481,8 @@ macro_rules! build_tag_enum {
 					$(
 						ExifTag::$tag(value) => {
 
-							// Check if the value has a predefined number of components
-							if $component_number.is_some()
+							// Shorter strings get padded to the predefined length
+							if $component_number.is_some() && self.is_string()
 							{
 								return $component_number.unwrap() as u32;
 							}
@@ -359,6 +507,85 @@ macro_rules! build_tag_enum {
 				}
 			}
 
+			/// Gets the number of bytes the value of the tag takes up when it
+			/// gets encoded, i.e. its number of components (see 
+			/// `number_of_components`) times the size of a component of its 
+			/// format.
+			///
+			/// # Examples
+			/// ```no_run
+			/// use revolt_little_exif::exif_tag::ExifTag;
+			///
+			/// assert_eq!(ExifTag::ImageDescription("Hello".to_string()).value_len_bytes(), 6);
+			/// assert_eq!(ExifTag::ISO(vec![100]).value_len_bytes(), 2);
+			/// ```
+			pub fn
+			value_len_bytes
+			(
+				&self
+			)
+			-> usize
+			{
+				return self.number_of_components() as usize * self.format().bytes_per_component() as usize;
+			}
+
+			/// Gets the number of components predefined by the specifications
+			/// for the tag (e.g. 3 for `BitsPerSample`), including the NUL 
+			/// terminator for `STRING` format type tags. Returns `None` if the
+			/// number depends on the actual data, which is always the case for
+			/// unknown tags.
+			///
+			/// # Examples
+			/// ```no_run
+			/// use revolt_little_exif::exif_tag::ExifTag;
+			///
+			/// assert_eq!(ExifTag::BitsPerSample(vec![8]).predefined_number_of_components(), Some(3));
+			/// assert_eq!(ExifTag::ImageDescription(String::new()).predefined_number_of_components(), None);
+			/// ```
+			pub fn
+			predefined_number_of_components
+			(
+				&self
+			)
+			-> Option<u32>
+			{
+				match self
+				{
+					$(
+						ExifTag::$tag(_) => $component_number.map(|component_number| component_number as u32),
+					)*
+					_ => None,
+				}
+			}
+
+			/// Checks if the value of the tag has the number of components
+			/// predefined by the specifications (e.g. `BitsPerSample` requires
+			/// exactly 3 values). For `STRING` format type tags the NUL
+			/// terminator is taken into account.
+			/// Tags without a predefined number of components (including all
+			/// unknown tags) always have a valid number of components.
+			pub fn
+			has_valid_number_of_components
+			(
+				&self
+			)
+			-> bool
+			{
+				match self
+				{
+					$(
+						ExifTag::$tag(value) => {
+							if let Some(component_number) = $component_number
+							{
+								return value.len() as u32 + self.is_string() as u32 == component_number as u32;
+							}
+							return true;
+						},
+					)*
+					_ => true,
+				}
+			}
+
 			/// Checks if the format type of the tag is `STRING`.
 			/// Needed for generating the EXIF data to know wheter to add a 
 			/// NUL terminator at the end
@@ -408,6 +635,101 @@ macro_rules! build_tag_enum {
 					ExifTag::UnknownDOUBLE(         value, _, _) => value.to_u8_vec(endian),
 				}
 			}
+
+			/// Gets the approximate number of bytes allocated on the heap for
+			/// the value of the tag (i.e. the capacity of its string or 
+			/// vector), not including the size of the `ExifTag` itself.
+			pub fn
+			heap_size
+			(
+				&self
+			)
+			-> usize
+			{
+				match self
+				{
+					$(
+						ExifTag::$tag(value) => value.heap_size(),
+					)*
+					ExifTag::UnknownINT8U(          value, _, _) => value.heap_size(),
+					ExifTag::UnknownSTRING(         value, _, _) => value.heap_size(),
+					ExifTag::UnknownINT16U(         value, _, _) => value.heap_size(),
+					ExifTag::UnknownINT32U(         value, _, _) => value.heap_size(),
+					ExifTag::UnknownRATIONAL64U(    value, _, _) => value.heap_size(),
+					ExifTag::UnknownINT8S(          value, _, _) => value.heap_size(),
+					ExifTag::UnknownUNDEF(          value, _, _) => value.heap_size(),
+					ExifTag::UnknownINT16S(         value, _, _) => value.heap_size(),
+					ExifTag::UnknownINT32S(         value, _, _) => value.heap_size(),
+					ExifTag::UnknownRATIONAL64S(    value, _, _) => value.heap_size(),
+					ExifTag::UnknownFLOAT(          value, _, _) => value.heap_size(),
+					ExifTag::UnknownDOUBLE(         value, _, _) => value.heap_size(),
+				}
+			}
+
+			/// Gets a copy of the value stored in the tag, typed according to
+			/// the format of the tag. 
+			/// 
+			/// # Examples
+			/// ```no_run
+			/// use revolt_little_exif::exif_tag::ExifTag;
+			/// use revolt_little_exif::exif_tag_format::TagValue;
+			/// 
+			/// let tag = ExifTag::ISO(vec![400]);
+			/// assert_eq!(tag.components(), TagValue::Short(vec![400]));
+			/// ```
+			pub fn
+			components
+			(
+				&self
+			)
+			-> TagValue
+			{
+				match self
+				{
+					$(
+						ExifTag::$tag(value) => tag_value!($format_enum, value),
+					)*
+					ExifTag::UnknownINT8U(          value, _, _) => tag_value!(INT8U,       value),
+					ExifTag::UnknownSTRING(         value, _, _) => tag_value!(STRING,      value),
+					ExifTag::UnknownINT16U(         value, _, _) => tag_value!(INT16U,      value),
+					ExifTag::UnknownINT32U(         value, _, _) => tag_value!(INT32U,      value),
+					ExifTag::UnknownRATIONAL64U(    value, _, _) => tag_value!(RATIONAL64U, value),
+					ExifTag::UnknownINT8S(          value, _, _) => tag_value!(INT8S,       value),
+					ExifTag::UnknownUNDEF(          value, _, _) => tag_value!(UNDEF,       value),
+					ExifTag::UnknownINT16S(         value, _, _) => tag_value!(INT16S,      value),
+					ExifTag::UnknownINT32S(         value, _, _) => tag_value!(INT32S,      value),
+					ExifTag::UnknownRATIONAL64S(    value, _, _) => tag_value!(RATIONAL64S, value),
+					ExifTag::UnknownFLOAT(          value, _, _) => tag_value!(FLOAT,       value),
+					ExifTag::UnknownDOUBLE(         value, _, _) => tag_value!(DOUBLE,      value),
+				}
+			}
+		}
+
+		/// Lists all tags known to little_exif (i.e. those having their own 
+		/// variant of `ExifTag`) with their hex value, name, format and group,
+		/// in the order they are defined in. Note that some hex values occur
+		/// more than once, as e.g. GPS tags share them with IFD0 tags.
+		///
+		/// # Examples
+		/// ```no_run
+		/// use revolt_little_exif::exif_tag::ExifTagGroup;
+		///
+		/// let gps_tag_names = revolt_little_exif::all_known_tags()
+		///     .into_iter()
+		///     .filter(|(_, _, _, group)| *group == ExifTagGroup::GPSIFD)
+		///     .map(|(_, name, _, _)| name)
+		///     .collect::<Vec<&str>>();
+		/// ```
+		pub fn
+		all_known_tags
+		()
+		-> Vec<(u16, &'static str, ExifTagFormat, ExifTagGroup)>
+		{
+			return vec![
+				$(
+					($hex_value, stringify!($tag), ExifTagFormat::$format_enum, ExifTagGroup::$group),
+				)*
+			];
 		}
 	};
 }
@@ -418,16 +740,34 @@ macro_rules! build_tag_enum {
 // Note regarding non-writable tags: Apart from
 // - StripOffsets
 // - StripByteCounts
-// - Opto-ElectricConvFactor (OECF)
 // - SpatialFrequencyResponse
 // - DeviceSettingDescription
 // none of them are part of the EXIF 2.32 specification
 // (Source: https://exiftool.org/TagNames/EXIF.html )
+// The offset tags linking to SubIFDs (e.g. ExifOffset) are not writable as
+// their values get computed during the encoding process. The same applies to
+// the ThumbnailOffset and ThumbnailLength tags, which are derived from the
+// thumbnail data stored in the metadata struct.
 
 build_tag_enum![
 	// Tag                        Tag ID  Format         Nr. Components     Writable   Group
 	(InteroperabilityIndex,       0x0001, STRING,        Some::<u32>(4),    true,      InteropIFD),
 
+	(GPSStatus,                   0x0009, STRING,        Some::<u32>(2),    true,      GPSIFD),
+	(ProcessingSoftware,          0x000b, STRING,        None::<u32>,       true,      IFD0),
+	(GPSSpeedRef,                 0x000c, STRING,        Some::<u32>(2),    true,      GPSIFD),
+	(GPSSpeed,                    0x000d, RATIONAL64U,   Some::<u32>(1),    true,      GPSIFD),
+	(GPSTrackRef,                 0x000e, STRING,        Some::<u32>(2),    true,      GPSIFD),
+	(GPSTrack,                    0x000f, RATIONAL64U,   Some::<u32>(1),    true,      GPSIFD),
+	(GPSImgDirectionRef,          0x0010, STRING,        Some::<u32>(2),    true,      GPSIFD),
+	(GPSImgDirection,             0x0011, RATIONAL64U,   Some::<u32>(1),    true,      GPSIFD),
+	(GPSMapDatum,                 0x0012, STRING,        None::<u32>,       true,      GPSIFD),
+	(GPSDifferential,             0x001e, INT16U,        Some::<u32>(1),    true,      GPSIFD),
+	(GPSHPositioningError,        0x001f, RATIONAL64U,   Some::<u32>(1),    true,      GPSIFD),
+
+	(NewSubfileType,              0x00fe, INT32U,        Some::<u32>(1),    true,      IFD0),
+	(SubfileType,                 0x00ff, INT16U,        Some::<u32>(1),    true,      IFD0),
+
 	(ImageWidth,                  0x0100, INT32U,        Some::<u32>(1),    true,      IFD0),       // IFD1?
 	(ImageHeight,                 0x0101, INT32U,        Some::<u32>(1),    true,      IFD0),       // IFD1?
 	(BitsPerSample,               0x0102, INT16U,        Some::<u32>(3),    true,      IFD0),       // IFD1?
@@ -461,8 +801,8 @@ build_tag_enum![
 	(WhitePoint,                  0x013e, RATIONAL64U,   Some::<u32>(2),    true,      IFD0),
 	(PrimaryChromaticities,       0x013f, RATIONAL64U,   Some::<u32>(6),    true,      IFD0),
 
-	(ThumbnailOffset,             0x0201, INT32U,        Some::<u32>(1),    true,      IFD1),       // oh boy, this one seems complicated - the group depends on the file type???
-	(ThumbnailLength,             0x0202, INT32U,        Some::<u32>(1),    true,      IFD1),       // same problems as 0x0201
+	(ThumbnailOffset,             0x0201, INT32U,        Some::<u32>(1),    false,     IFD1),       // oh boy, this one seems complicated - the group depends on the file type???
+	(ThumbnailLength,             0x0202, INT32U,        Some::<u32>(1),    false,     IFD1),       // same problems as 0x0201
 
 	(YCbCrCoefficients,           0x0211, RATIONAL64U,   Some::<u32>(3),    true,      IFD0),       // IFD1?
 	(YCbCrSubSampling,            0x0212, INT16U,        Some::<u32>(2),    true,      IFD0),       // IFD1?
@@ -477,9 +817,9 @@ build_tag_enum![
 
 	(ExposureProgram,             0x8822, INT16U,        Some::<u32>(1),    true,      ExifIFD),
 	(SpectralSensitivity,         0x8824, STRING,        None::<u32>,       true,      ExifIFD),
-	(GPSInfo,                     0x8825, INT32U,        Some::<u32>(1),    true,      IFD0),       // -> GPS Tags: https://exiftool.org/TagNames/GPS.html
+	(GPSInfo,                     0x8825, INT32U,        Some::<u32>(1),    false,     IFD0),       // -> GPS Tags: https://exiftool.org/TagNames/GPS.html
 	(ISO,                         0x8827, INT16U,        None::<u32>,       true,      ExifIFD),
-	(OECF,                        0x8828, UNDEF,         None::<u32>,       false,     NO_GROUP),
+	(OECF,                        0x8828, UNDEF,         None::<u32>,       true,      ExifIFD),
 	(SensitivityType,             0x8830, INT16U,        Some::<u32>(1),    true,      ExifIFD),
 	(StandardOutputSensitivity,   0x8831, INT32U,        Some::<u32>(1),    true,      ExifIFD),
 	(RecommendedExposureIndex,    0x8832, INT32U,        Some::<u32>(1),    true,      ExifIFD),
@@ -508,7 +848,7 @@ build_tag_enum![
 	(Flash,                       0x9209, INT16U,        Some::<u32>(1),    true,      ExifIFD),    // -> EXIF Flash Values: https://exiftool.org/TagNames/EXIF.html#Flash
 	(FocalLength,                 0x920a, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
 
-	(SubjectArea,                 0x9214, INT16U,        Some::<u32>(4),    true,      ExifIFD),
+	(SubjectArea,                 0x9214, INT16U,        None::<u32>,       true,      ExifIFD),    // 2, 3 or 4 values for a point, circle or rectangle
 
 	(MakerNote,                   0x927c, UNDEF,         None::<u32>,       true,      ExifIFD),
 	(UserComment,                 0x9286, UNDEF,         None::<u32>,       true,      ExifIFD),    // First 8 bytes describe the character code (e.g. "JIS" for Japanese characters)
@@ -529,13 +869,13 @@ build_tag_enum![
 	(ExifImageHeight,             0xa003, INT32U,        Some::<u32>(1),    true,      ExifIFD),
 
 	(RelatedSoundFile,            0xa004, STRING,        None::<u32>,       true,      ExifIFD),
-	(InteropOffset,               0xa005, INT32U,        Some::<u32>(1),    true,      ExifIFD),
+	(InteropOffset,               0xa005, INT32U,        Some::<u32>(1),    false,     ExifIFD),
 	(FlashEnergy,                 0xa20b, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
 	(SpatialFrequencyResponse,    0xa20c, INT16U,        Some::<u32>(1),    false,     NO_GROUP),
 	(FocalPlaneXResolution,       0xa20e, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
 	(FocalPlaneYResolution,       0xa20f, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
 	(FocalPlaneResolutionUnit,    0xa210, INT16U,        Some::<u32>(1),    true,      ExifIFD),
-	(SubjectLocation,             0xa214, INT16U,        Some::<u32>(1),    true,      ExifIFD),
+	(SubjectLocation,             0xa214, INT16U,        Some::<u32>(2),    true,      ExifIFD),
 	(ExposureIndex,               0xa215, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
 
 	(SensingMethod,               0xa217, INT16U,        Some::<u32>(1),    true,      ExifIFD),
@@ -560,8 +900,8 @@ build_tag_enum![
 
 	(ImageUniqueID,               0xa420, STRING,        None::<u32>,       true,      ExifIFD),
 
-	(OwnerName,                   0xa430, STRING,        None::<u32>,       true,      ExifIFD),
-	(SerialNumber,                0xa431, STRING,        None::<u32>,       true,      ExifIFD),
+	(OwnerName,                   0xa430, STRING,        None::<u32>,       true,      ExifIFD),    // CameraOwnerName in EXIF 2.3
+	(SerialNumber,                0xa431, STRING,        None::<u32>,       true,      ExifIFD),    // BodySerialNumber in EXIF 2.3
 	(LensInfo,                    0xa432, RATIONAL64U,   Some::<u32>(4),    true,      ExifIFD),
 	(LensMake,                    0xa433, STRING,        None::<u32>,       true,      ExifIFD),
 	(LensModel,                   0xa434, STRING,        None::<u32>,       true,      ExifIFD),
@@ -571,7 +911,11 @@ build_tag_enum![
 	(CompositeImageCount,         0xa461, INT16U,        Some::<u32>(2),    true,      ExifIFD),
 	(CompositeImageExposureTimes, 0xa462, UNDEF,         None::<u32>,       true,      ExifIFD),
 
-	(Gamma,                       0xa500, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD)
+	(Gamma,                       0xa500, RATIONAL64U,   Some::<u32>(1),    true,      ExifIFD),
+
+	// Reserves space for future edits (written e.g. by Windows Photo Gallery),
+	// can also be found in the ExifIFD - see `is_padding_tag`
+	(Padding,                     0xea1c, UNDEF,         None::<u32>,       true,      IFD0)
 ];
 
 impl ExifTag
@@ -580,6 +924,8 @@ impl ExifTag
 	/// Needed for generating the exif data for writing, as the value stored in 
 	/// the tag variables is useless because it needs to be computed during
 	/// the writing process.
+	/// Note that the `MakerNote` is not considered to be an offset tag, as its
+	/// value is a vendor specific blob that gets stored as is.
 	pub fn
 	is_offset_tag
 	(
@@ -591,9 +937,242 @@ impl ExifTag
 		{
 			ExifTag::ExifOffset(_)		=> Some(ExifTagGroup::ExifIFD),
 			ExifTag::GPSInfo(_)			=> Some(ExifTagGroup::GPSIFD),
-			ExifTag::MakerNote(_)		=> Some(ExifTagGroup::MakerNotesIFD),
 			ExifTag::InteropOffset(_)	=> Some(ExifTagGroup::InteropIFD),
 			_ => None
 		}
 	}
+
+	/// Checks if a tag contains GPS information, i.e. is located in the GPS
+	/// IFD (including unknown tags found there) or links to it.
+	pub fn
+	is_gps_tag
+	(
+		&self
+	)
+	-> bool
+	{
+		match *self
+		{
+			ExifTag::GPSInfo(_) => true,
+			_ => self.get_group() == ExifTagGroup::GPSIFD
+		}
+	}
+
+	/// Checks if a tag is a `Padding` tag, which some Microsoft tools add to
+	/// IFD0 and the ExifIFD for reserving space for future edits (often 
+	/// more than a kilobyte of zeros each). These are written back 
+	/// unchanged, so they can be stripped if the space is not worth keeping.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.retain(|tag| !tag.is_padding_tag());
+	/// ```
+	pub fn
+	is_padding_tag
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(
+			*self,
+			ExifTag::Padding(_) |
+			ExifTag::UnknownUNDEF(_, 0xea1c, _)
+		)
+	}
+
+	/// Checks if a tag describes a point in time, e.g. when the image was
+	/// taken or modified, including the sub second and time zone offset tags
+	/// belonging to these.
+	pub fn
+	is_date_tag
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(
+			*self,
+			ExifTag::ModifyDate(_)          |
+			ExifTag::DateTimeOriginal(_)    |
+			ExifTag::CreateDate(_)          |
+			ExifTag::OffsetTime(_)          |
+			ExifTag::OffsetTimeOriginal(_)  |
+			ExifTag::OffsetTimeDigitized(_) |
+			ExifTag::SubSecTime(_)          |
+			ExifTag::SubSecTimeOriginal(_)  |
+			ExifTag::SubSecTimeDigitized(_)
+		)
+	}
+
+	/// Checks if a tag identifies the individual camera or lens that took the
+	/// image or its owner (in contrast to e.g. `Make` and `Model`, which are
+	/// shared by all devices of the same type), or the image itself.
+	pub fn
+	is_camera_identity_tag
+	(
+		&self
+	)
+	-> bool
+	{
+		matches!(
+			*self,
+			ExifTag::ImageUniqueID(_)    |
+			ExifTag::OwnerName(_)        |
+			ExifTag::SerialNumber(_)     |
+			ExifTag::LensSerialNumber(_)
+		)
+	}
+
+	/// Checks if a tag may reveal where, when or with which device an image
+	/// was taken - i.e. is a GPS, date or camera identity tag.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.retain(|tag| !tag.is_potentially_sensitive());
+	/// ```
+	pub fn
+	is_potentially_sensitive
+	(
+		&self
+	)
+	-> bool
+	{
+		return self.is_gps_tag() || self.is_date_tag() || self.is_camera_identity_tag();
+	}
+
+	/// Gets the human readable meaning of the value of an enumerated tag
+	/// (e.g. "Spot" for a `MeteringMode` of 3), according to the names used
+	/// by exiftool (https://exiftool.org/TagNames/EXIF.html).
+	/// Returns `None` if the tag is not an enumerated tag supported by this
+	/// function or if its value is not defined by the specifications.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::SceneCaptureType(vec![1]).interpret(), Some("Landscape"));
+	/// ```
+	pub fn
+	interpret
+	(
+		&self
+	)
+	-> Option<&'static str>
+	{
+		match self
+		{
+			// IFD1 is not known to little_exif, so its Compression tag (which
+			// describes the thumbnail) is an unknown tag
+			ExifTag::Compression(value) |
+			ExifTag::UnknownINT16U(value, 0x0103, ExifTagGroup::IFD1) => match value.first()?
+			{
+				1     => Some("Uncompressed"),
+				2     => Some("CCITT 1D"),
+				3     => Some("T4/Group 3 Fax"),
+				4     => Some("T6/Group 4 Fax"),
+				5     => Some("LZW"),
+				6     => Some("JPEG (old-style)"),
+				7     => Some("JPEG"),
+				8     => Some("Adobe Deflate"),
+				32773 => Some("PackBits"),
+				32946 => Some("Deflate"),
+				_     => None
+			},
+			ExifTag::MeteringMode(value) => match value.first()?
+			{
+				0   => Some("Unknown"),
+				1   => Some("Average"),
+				2   => Some("Center-weighted average"),
+				3   => Some("Spot"),
+				4   => Some("Multi-spot"),
+				5   => Some("Multi-segment"),
+				6   => Some("Partial"),
+				255 => Some("Other"),
+				_   => None
+			},
+			ExifTag::ExposureMode(value) => match value.first()?
+			{
+				0   => Some("Auto"),
+				1   => Some("Manual"),
+				2   => Some("Auto bracket"),
+				_   => None
+			},
+			ExifTag::WhiteBalance(value) => match value.first()?
+			{
+				0   => Some("Auto"),
+				1   => Some("Manual"),
+				_   => None
+			},
+			ExifTag::SceneCaptureType(value) => match value.first()?
+			{
+				0   => Some("Standard"),
+				1   => Some("Landscape"),
+				2   => Some("Portrait"),
+				3   => Some("Night"),
+				4   => Some("Other"),
+				_   => None
+			},
+			_ => None
+		}
+	}
+
+	/// Gets the values of a tag with unsigned integer components of at most
+	/// 16 bits (`INT8U` or `INT16U`), e.g. all three components of a
+	/// `BitsPerSample` tag. Returns `None` for tags of any other format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::BitsPerSample(vec![8, 8, 8]).as_u16_vec(), Some(vec![8, 8, 8]));
+	/// ```
+	pub fn
+	as_u16_vec
+	(
+		&self
+	)
+	-> Option<Vec<u16>>
+	{
+		match self.components()
+		{
+			TagValue::Byte(values)  => Some(values.into_iter().map(u16::from).collect()),
+			TagValue::Short(values) => Some(values),
+			_                       => None
+		}
+	}
+
+	/// Gets the values of a tag with unsigned integer components of at most
+	/// 32 bits (`INT8U`, `INT16U` or `INT32U`), which is useful for tags
+	/// that the specifications allow to be stored as either `INT16U` or 
+	/// `INT32U` (e.g. unknown tags, which keep the format of the file).
+	/// Returns `None` for tags of any other format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// assert_eq!(ExifTag::StripOffsets(vec![8, 1024]).as_u32_vec(), Some(vec![8, 1024]));
+	/// ```
+	pub fn
+	as_u32_vec
+	(
+		&self
+	)
+	-> Option<Vec<u32>>
+	{
+		match self.components()
+		{
+			TagValue::Byte(values)  => Some(values.into_iter().map(u32::from).collect()),
+			TagValue::Short(values) => Some(values.into_iter().map(u32::from).collect()),
+			TagValue::Long(values)  => Some(values),
+			_                       => None
+		}
+	}
 }
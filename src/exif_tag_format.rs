@@ -87,6 +87,61 @@ ExifTagFormat
 		}
 	}
 
+	/// Gets the name exiftool uses for the format (e.g. `"int16u"`), see 
+	/// https://exiftool.org/TagNames/index.html
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag_format::ExifTagFormat;
+	///
+	/// assert_eq!(ExifTagFormat::RATIONAL64U.type_name(), "rational64u");
+	/// ```
+	pub fn
+	type_name
+	(
+		&self
+	)
+	-> &'static str
+	{
+		match *self
+		{
+			ExifTagFormat::INT8U        => "int8u",
+			ExifTagFormat::STRING       => "string",
+			ExifTagFormat::INT16U       => "int16u",
+			ExifTagFormat::INT32U       => "int32u",
+			ExifTagFormat::RATIONAL64U  => "rational64u",
+			ExifTagFormat::INT8S        => "int8s",
+			ExifTagFormat::UNDEF        => "undef",
+			ExifTagFormat::INT16S       => "int16s",
+			ExifTagFormat::INT32S       => "int32s",
+			ExifTagFormat::RATIONAL64S  => "rational64s",
+			ExifTagFormat::FLOAT        => "float",
+			ExifTagFormat::DOUBLE       => "double",
+		}
+	}
+
+	/// Gets the format for the given exiftool type name (see `type_name`),
+	/// ignoring the case. Returns `None` for any other name.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::exif_tag_format::ExifTagFormat;
+	///
+	/// assert_eq!(ExifTagFormat::from_type_name("int16u"), Some(ExifTagFormat::INT16U));
+	/// assert_eq!(ExifTagFormat::from_type_name("int64u"), None);
+	/// ```
+	pub fn
+	from_type_name
+	(
+		type_name: &str
+	)
+	-> Option<ExifTagFormat>
+	{
+		return (0x0001..=0x000c)
+			.filter_map(ExifTagFormat::from_u16)
+			.find(|format| format.type_name().eq_ignore_ascii_case(type_name));
+	}
+
 
 	pub fn
 	bytes_per_component
@@ -113,3 +168,89 @@ ExifTagFormat
 		}
 	}
 }
+
+/// Converts the format value of an IFD entry (e.g. `0x0003` for `INT16U`)
+/// like `ExifTagFormat::from_u16`, but returns an error for illegal values.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::exif_tag_format::ExifTagFormat;
+///
+/// assert_eq!(ExifTagFormat::try_from(0x0003).unwrap(), ExifTagFormat::INT16U);
+/// assert!(ExifTagFormat::try_from(0x0020).is_err());
+/// ```
+impl
+TryFrom<u16>
+for
+ExifTagFormat
+{
+	type Error = std::io::Error;
+
+	fn
+	try_from
+	(
+		hex_code: u16
+	)
+	-> Result<ExifTagFormat, Self::Error>
+	{
+		match ExifTagFormat::from_u16(hex_code)
+		{
+			Some(format) => Ok(format),
+			None         => Err(std::io::Error::new(
+				std::io::ErrorKind::InvalidData,
+				format!("Illegal format value 0x{:04x}!", hex_code)
+			)),
+		}
+	}
+}
+
+/// The value of a tag, typed according to its format and named after the
+/// field types of the TIFF specification. 
+/// Can be obtained for any tag via `ExifTag::components` without having to
+/// match against the individual tag variants.
+#[derive(Clone, Debug, PartialEq)]
+pub enum
+TagValue
+{
+	Byte(       INT8U),
+	Ascii(      STRING),
+	Short(      INT16U),
+	Long(       INT32U),
+	Rational(   RATIONAL64U),
+	SByte(      INT8S),
+	Undefined(  UNDEF),
+	SShort(     INT16S),
+	SLong(      INT32S),
+	SRational(  RATIONAL64S),
+	Float(      FLOAT),
+	Double(     DOUBLE),
+}
+
+impl
+TagValue
+{
+	/// Gets the format corresponding to the type of the value
+	pub fn
+	format
+	(
+		&self
+	)
+	-> ExifTagFormat
+	{
+		match *self
+		{
+			TagValue::Byte(         _) => ExifTagFormat::INT8U,
+			TagValue::Ascii(        _) => ExifTagFormat::STRING,
+			TagValue::Short(        _) => ExifTagFormat::INT16U,
+			TagValue::Long(         _) => ExifTagFormat::INT32U,
+			TagValue::Rational(     _) => ExifTagFormat::RATIONAL64U,
+			TagValue::SByte(        _) => ExifTagFormat::INT8S,
+			TagValue::Undefined(    _) => ExifTagFormat::UNDEF,
+			TagValue::SShort(       _) => ExifTagFormat::INT16S,
+			TagValue::SLong(        _) => ExifTagFormat::INT32S,
+			TagValue::SRational(    _) => ExifTagFormat::RATIONAL64S,
+			TagValue::Float(        _) => ExifTagFormat::FLOAT,
+			TagValue::Double(       _) => ExifTagFormat::DOUBLE,
+		}
+	}
+}
@@ -3,6 +3,15 @@
 
 use std::str::FromStr;
 
+use crate::jpg;
+use crate::png;
+use crate::webp;
+use crate::ico;
+
+/// The number of leading bytes needed by `FileExtension::detect_from_bytes`
+/// to recognize all supported file types (the RIFF and WebP signatures)
+pub(crate) const SNIFF_LENGTH: usize = 12;
+
 #[derive(Debug, PartialEq)]
 #[allow(non_snake_case)]
 pub enum
@@ -10,7 +19,107 @@ FileExtension
 {
 	PNG  {as_zTXt_chunk: bool},
 	JPEG,
-	WEBP
+	WEBP,
+	ICO
+}
+
+impl
+FileExtension
+{
+	/// Checks if little_exif can read metadata from files of this type, so
+	/// that callers can check this before attempting to do so.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// assert!(FileExtension::WEBP.supports_read());
+	/// ```
+	pub fn
+	supports_read
+	(
+		&self
+	)
+	-> bool
+	{
+		match self
+		{
+			FileExtension::PNG {as_zTXt_chunk: _} => true,
+			FileExtension::JPEG                   => true,
+			FileExtension::WEBP                   => true,
+			FileExtension::ICO                    => true,
+		}
+	}
+
+	/// Checks if little_exif can write (and clear) metadata of files of this
+	/// type. Note that some variants of a file type may still be rejected
+	/// when writing (e.g. lossy WebP files in the simple format).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// assert!(FileExtension::JPEG.supports_write());
+	/// ```
+	pub fn
+	supports_write
+	(
+		&self
+	)
+	-> bool
+	{
+		match self
+		{
+			FileExtension::PNG {as_zTXt_chunk: _} => true,
+			FileExtension::JPEG                   => true,
+			FileExtension::WEBP                   => true,
+			FileExtension::ICO                    => false,
+		}
+	}
+
+	/// Detects the file type (JPEG, PNG, WebP or ICO) of the given image data
+	/// by its signature, for which the first 12 bytes of the file are enough.
+	/// Returns `None` if the signature is not one of a supported file type.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let file_buffer = std::fs::read("image").unwrap();
+	/// if let Some(file_type) = FileExtension::detect_from_bytes(&file_buffer)
+	/// {
+	///     println!("Detected a {} file", file_type);
+	/// }
+	/// ```
+	pub fn
+	detect_from_bytes
+	(
+		file_buffer: &[u8]
+	)
+	-> Option<FileExtension>
+	{
+		if file_buffer.starts_with(&jpg::JPG_SIGNATURE)
+		{
+			return Some(FileExtension::JPEG);
+		}
+
+		if file_buffer.starts_with(&png::PNG_SIGNATURE)
+		{
+			return Some(FileExtension::PNG { as_zTXt_chunk: true });
+		}
+
+		if file_buffer.starts_with(&webp::RIFF_SIGNATURE) && file_buffer.get(8..12) == Some(&webp::WEBP_SIGNATURE[..])
+		{
+			return Some(FileExtension::WEBP);
+		}
+
+		if file_buffer.starts_with(&ico::ICO_SIGNATURE) || file_buffer.starts_with(&ico::CUR_SIGNATURE)
+		{
+			return Some(FileExtension::ICO);
+		}
+
+		return None;
+	}
 }
 
 impl 
@@ -33,7 +142,34 @@ FileExtension
 			"jpeg"  => Ok(FileExtension::JPEG),
 			"png"   => Ok(FileExtension::PNG{ as_zTXt_chunk: true}),
 			"webp"  => Ok(FileExtension::WEBP),
+			"ico"   => Ok(FileExtension::ICO),
+			"cur"   => Ok(FileExtension::ICO),
 			_       => Err(()),
 		}
 	}
 }
+
+/// Gives the common file extension of the file type (e.g. `jpg`), which can
+/// be parsed again with `FromStr`.
+impl
+std::fmt::Display
+for
+FileExtension
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		match self
+		{
+			FileExtension::PNG {as_zTXt_chunk: _} => write!(f, "png"),
+			FileExtension::JPEG                   => write!(f, "jpg"),
+			FileExtension::WEBP                   => write!(f, "webp"),
+			FileExtension::ICO                    => write!(f, "ico"),
+		}
+	}
+}
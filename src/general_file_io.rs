@@ -30,5 +30,17 @@ macro_rules! io_error {
 	};
 }
 
+macro_rules! incomplete_data_error {
+	($required_length:expr)
+	=>
+	{
+		Err(std::io::Error::new(
+			std::io::ErrorKind::UnexpectedEof,
+			crate::metadata::IncompleteData::new($required_length)
+		))
+	};
+}
+
 pub(crate) use perform_file_action;
-pub(crate) use io_error;
\ No newline at end of file
+pub(crate) use io_error;
+pub(crate) use incomplete_data_error;
\ No newline at end of file
@@ -0,0 +1,168 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Reading EXIF data from Windows icon (ICO) and cursor (CUR) files. Their
+//! images are either stored as BMP without a header (which can't carry any
+//! metadata) or as complete PNG files (Windows Vista and later), whose EXIF
+//! data is read using the PNG reader. Writing is not supported.
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::endian::*;
+use crate::u8conversion::*;
+use crate::general_file_io::*;
+
+use crate::png;
+
+pub(crate) const ICO_SIGNATURE: [u8; 4] = [0x00, 0x00, 0x01, 0x00];
+pub(crate) const CUR_SIGNATURE: [u8; 4] = [0x00, 0x00, 0x02, 0x00];
+
+// The EXIF data is read from the PNG encoded images
+pub(crate) const ICO_EXIF_HEADER: &[u8] = png::PNG_EXIF_HEADER;
+
+// The ICONDIR header (reserved, type and number of images) and the 
+// ICONDIRENTRY structures following it
+const ICONDIR_LENGTH:       usize = 6;
+const ICONDIR_ENTRY_LENGTH: usize = 16;
+
+/// The encoding of an image stored in an ICO or CUR file
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum
+IcoImageFormat
+{
+	PNG,
+	BMP,
+}
+
+/// An image entry of the ICONDIR structure, with the location of its data
+/// within the file
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct
+IcoEntry
+{
+	pub(crate) format: IcoImageFormat,
+	pub(crate) offset: usize,
+	pub(crate) length: usize,
+}
+
+/// Parses the ICONDIR structure at the start of the given ICO or CUR data,
+/// returning its image entries and checking that their data is contained in
+/// the file. 
+pub(crate) fn
+parse_ico
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<IcoEntry>, std::io::Error>
+{
+	if !file_buffer.starts_with(&ICO_SIGNATURE) && !file_buffer.starts_with(&CUR_SIGNATURE)
+	{
+		return io_error!(InvalidData, "Can't open ICO file - Wrong signature!");
+	}
+
+	if file_buffer.len() < ICONDIR_LENGTH
+	{
+		return io_error!(InvalidData, "Could not read ICONDIR structure!");
+	}
+
+	let number_of_images = from_u8_vec_macro!(u16, &file_buffer[4..6].to_vec(), &Endian::Little) as usize;
+
+	let mut entries = Vec::new();
+	for i in 0..number_of_images
+	{
+		let entry_start = ICONDIR_LENGTH + i * ICONDIR_ENTRY_LENGTH;
+		if file_buffer.len() < entry_start + ICONDIR_ENTRY_LENGTH
+		{
+			return io_error!(InvalidData, "Could not read ICONDIRENTRY structure!");
+		}
+
+		// Width, height, colors, reserved and planes resp. hotspot (8 bytes),
+		// followed by the size and offset of the image data
+		let length = from_u8_vec_macro!(u32, &file_buffer[(entry_start+8)..(entry_start+12)].to_vec(), &Endian::Little) as usize;
+		let offset = from_u8_vec_macro!(u32, &file_buffer[(entry_start+12)..(entry_start+16)].to_vec(), &Endian::Little) as usize;
+
+		let Some(image_data) = file_buffer.get(offset..offset.saturating_add(length))
+		else
+		{
+			return io_error!(InvalidData, format!("Data of image {} exceeds the end of the ICO file!", i));
+		};
+
+		let format = match image_data.starts_with(&png::PNG_SIGNATURE)
+		{
+			true  => IcoImageFormat::PNG,
+			false => IcoImageFormat::BMP,
+		};
+
+		entries.push(IcoEntry { format, offset, length });
+	}
+
+	return Ok(entries);
+}
+
+/// Reads the raw EXIF data of the first PNG encoded image of the ICO or CUR
+/// file at the specified path that has EXIF data, see `read_metadata_from_bytes`
+pub(crate) fn
+read_metadata
+(
+	path:       &Path,
+	verify_crc: bool
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let mut file_buffer = Vec::new();
+	perform_file_action!(File::open(path)?.read_to_end(&mut file_buffer));
+
+	return read_metadata_from_bytes(&file_buffer, verify_crc);
+}
+
+/// Reads the raw EXIF data of the first PNG encoded image in the given ICO
+/// or CUR data that has EXIF data. If `verify_crc` is set, a CRC mismatch
+/// in the chunks of a PNG encoded image is reported instead of continuing
+/// with the next image.
+pub(crate) fn
+read_metadata_from_bytes
+(
+	file_buffer: &[u8],
+	verify_crc:  bool
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	for entry in parse_ico(file_buffer)?
+	{
+		if entry.format != IcoImageFormat::PNG
+		{
+			continue;
+		}
+
+		match png::read_metadata_from_bytes(&file_buffer[entry.offset..(entry.offset+entry.length)], verify_crc)
+		{
+			Ok(raw_exif) 
+				=> return Ok(raw_exif),
+			Err(error) if verify_crc && error.kind() == std::io::ErrorKind::InvalidData
+				=> return Err(error),
+			Err(_) 
+				=> continue,
+		}
+	}
+
+	return io_error!(Other, "No EXIF data found!");
+}
+
+/// Reads the raw EXIF data of all PNG encoded images in the given ICO or CUR
+/// data that have EXIF data, in the order of their ICONDIR entries
+pub(crate) fn
+read_all_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	return Ok(parse_ico(file_buffer)?
+		.into_iter()
+		.filter(|entry| entry.format == IcoImageFormat::PNG)
+		.filter_map(|entry| png::read_metadata_from_bytes(&file_buffer[entry.offset..(entry.offset+entry.length)], false).ok())
+		.collect()
+	);
+}
@@ -2,6 +2,7 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 use std::path::Path;
+use std::io::Cursor;
 use std::io::Read;
 use std::io::Write;
 use std::io::Seek;
@@ -14,10 +15,20 @@ use crate::u8conversion::*;
 use crate::general_file_io::*;
 
 pub(crate) const JPG_SIGNATURE: [u8; 2] = [0xff, 0xd8];
+const JPG_EOI_MARKER:           [u8; 2] = [0xff, 0xd9];
 
 const JPG_MARKER_PREFIX: u8  = 0xff;
 const JPG_APP1_MARKER:   u16 = 0xffe1;
 
+// The EXIF APP1 segments start with the EXIF header, followed by the TIFF data
+pub(crate) const JPG_EXIF_HEADER: &[u8] = &EXIF_HEADER;
+
+const JPG_ADOBE_IDENTIFIER: &[u8] = b"Adobe";
+
+// The maximum amount of TIFF data a single APP1 segment can hold, i.e. the
+// maximum segment length minus the length field and the EXIF header
+const JPG_APP1_MAX_TIFF_LENGTH: usize = 0xffff - 2 - EXIF_HEADER.len();
+
 fn
 encode_metadata_jpg
 (
@@ -28,16 +39,23 @@ encode_metadata_jpg
 	// vector storing the data that will be returned
 	let mut jpg_exif: Vec<u8> = Vec::new();
 
-	// Compute the length of the exif data (includes the two bytes of the
-	// actual length field)
-	let length = 2u16 + (EXIF_HEADER.len() as u16) + (exif_vec.len() as u16);
-
-	// Start with the APP1 marker and the length of the data
-	// Then copy the previously encoded EXIF data 
-	jpg_exif.extend(to_u8_vec_macro!(u16, &JPG_APP1_MARKER, &Endian::Big));
-	jpg_exif.extend(to_u8_vec_macro!(u16, &length, &Endian::Big));
-	jpg_exif.extend(EXIF_HEADER.iter());
-	jpg_exif.extend(exif_vec.iter());
+	// EXIF data exceeding the 64 KB limit of a single segment gets split up
+	// into multiple consecutive APP1 segments, each starting with the EXIF
+	// header. Reassembling them by concatenating the data after the headers
+	// results in the original TIFF structure again
+	for exif_chunk in exif_vec.chunks(JPG_APP1_MAX_TIFF_LENGTH)
+	{
+		// Compute the length of the exif data (includes the two bytes of the
+		// actual length field)
+		let length = 2u16 + (EXIF_HEADER.len() as u16) + (exif_chunk.len() as u16);
+
+		// Start with the APP1 marker and the length of the data
+		// Then copy the previously encoded EXIF data 
+		jpg_exif.extend(to_u8_vec_macro!(u16, &JPG_APP1_MARKER, &Endian::Big));
+		jpg_exif.extend(to_u8_vec_macro!(u16, &length, &Endian::Big));
+		jpg_exif.extend(EXIF_HEADER.iter());
+		jpg_exif.extend(exif_chunk.iter());
+	}
 
 	return jpg_exif;
 }
@@ -62,7 +80,7 @@ check_signature
 	
 	// Check the signature
 	let mut signature_buffer = [0u8; 2];
-	file.read(&mut signature_buffer).unwrap();
+	perform_file_action!(file.read_exact(&mut signature_buffer));
 	let signature_is_valid = signature_buffer.iter()
 		.zip(JPG_SIGNATURE.iter())
 		.filter(|&(read, constant)| read == constant)
@@ -91,124 +109,155 @@ clear_metadata
 		return Err(file_result.err().unwrap());
 	}
 
-	// Setup of variables necessary for going through the file
-	let mut file = file_result.unwrap();                                        // The struct for interacting with the file
-	perform_file_action!(file.seek(SeekFrom::Start(0)));                        // Seek to file start (reason: signature check returns a file where the first two bytes have already been read)
-	let mut seek_counter = 0u64;                                                // A counter for keeping track of where in the file we currently are
-	let mut byte_buffer = [0u8; 1];                                             // A buffer for reading in a byte of data from the file
-	let mut previous_byte_was_marker_prefix = false;                            // A boolean for remembering if the previous byte was a marker prefix (0xFF)
-
 	// Load the entire file into memory instead of reading one byte at a time
 	// to improve the overall speed
 	// Thanks to Xuf3r for this improvement!
-	let mut file_buffer:Vec<u8> = Vec::new();
-	file.read_to_end(&mut file_buffer)?;
+	let mut file = file_result.unwrap();
+	let mut file_buffer: Vec<u8> = Vec::new();
+	perform_file_action!(file.seek(SeekFrom::Start(0)));                        // Seek to file start (reason: signature check returns a file where the first two bytes have already been read)
+	perform_file_action!(file.read_to_end(&mut file_buffer));
+
+	// Write the file
+	// Possible to optimize further by returning the purged bytestream itself?
+	file = std::fs::OpenOptions::new().write(true).truncate(true).open(path)?;
+	perform_file_action!(file.write_all(&clear_metadata_from_bytes(&file_buffer)?));
+
+	return Ok(());
+}
+
+/// Removes all EXIF APP1 segments from the JP(E)G image file at the specified
+/// path except for the first one, see `clear_duplicate_metadata_from_bytes`.
+pub(crate) fn
+clear_duplicate_metadata
+(
+	path: &Path
+)
+-> Result<(), std::io::Error>
+{
+	let file_result = check_signature(path);
 
-	// Iterate for processing the bytes of the file
-	let mut iterator_file = file_buffer.iter();
+	if file_result.is_err()
+	{
+		return Err(file_result.err().unwrap());
+	}
+
+	let mut file = file_result.unwrap();
+	let mut file_buffer: Vec<u8> = Vec::new();
+	perform_file_action!(file.seek(SeekFrom::Start(0)));
+	perform_file_action!(file.read_to_end(&mut file_buffer));
+
+	file = std::fs::OpenOptions::new().write(true).truncate(true).open(path)?;
+	perform_file_action!(file.write_all(&clear_duplicate_metadata_from_bytes(&file_buffer)));
+
+	return Ok(());
+}
 
-	loop
+/// Removes all APP1 segments from the JPEG data in the given buffer by
+/// copying all other segments into a new buffer
+pub(crate) fn
+clear_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if !file_buffer.starts_with(&JPG_SIGNATURE)
 	{
-		// Read next byte into buffer
-		if let Some(byte) = iterator_file.next() 
+		return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+	}
+
+	return Ok(retain_segments(file_buffer, |segment| segment[1] != 0xe1));
+}
+
+/// Removes all EXIF APP1 segments from the JPEG data in the given buffer
+/// except for the first one (together with the segments continuing it in 
+/// case the EXIF data is split across multiple segments), which is the one
+/// little_exif reads the metadata from. Other APP1 segments (e.g. XMP) are
+/// kept as well.
+fn
+clear_duplicate_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Vec<u8>
+{
+	let mut primary_found     = false;
+	let mut primary_continues = false;
+
+	return retain_segments(file_buffer, |segment| {
+		let is_exif = segment[1] == 0xe1 && segment.len() >= 4 && segment[4..].starts_with(&EXIF_HEADER);
+
+		// Only a completely filled segment may have a continuation
+		let is_full = segment.len() == 4 + EXIF_HEADER.len() + JPG_APP1_MAX_TIFF_LENGTH;
+
+		if is_exif && (!primary_found || primary_continues)
 		{
-			byte_buffer[0] = byte.clone();
+			primary_found     = true;
+			primary_continues = is_full;
+			return true;
 		}
 
-		if previous_byte_was_marker_prefix
-		{
-			
-			match byte_buffer[0]
-			{
-				0xe1	=> {
-					// APP1 marker
-
-					// Read in the length of the segment
-					// (which follows immediately after the marker)
-					let mut length_buffer = [0u8; 2];
-
-					if let (Some(&byte1), Some(&byte2)) = (iterator_file.next(), iterator_file.next()) 
-					{
-						length_buffer = [byte1, byte2];
-					}
-
-					// Decode the length to determine how much more data there is
-					let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
-					let remaining_length = length - 2;
-
-					// Skip the segment
-					if remaining_length > 0 
-					{
-						if iterator_file.nth((remaining_length - 1) as usize).is_none()
-						{
-							panic!("Could not skip to end of APP1 segment!");
-						}
-					} 
-					else 
-					{
-						unreachable!("If rem_len is <= 0 then it's not a valid\
-						JPEG - it must have at least a single SOS after APP1")
-					}
-
-					// ...copy data from there onwards into a buffer...
-					let mut file_buffer_clone = file_buffer.clone();
-					let (_, buffer) = file_buffer_clone.split_at_mut(
-						  (seek_counter     as usize)                           // Skip what has already been seeked
-						+ (remaining_length as usize)                           // Skip current segment
-						+ 2                                                     // Skip Marker Prefix and APP1 marker
-						+ 2                                                     // Skip the two length bytes
-					);
-					let buffer: Vec<u8> = buffer.to_vec();
-
-					// This essentially shifts the right-most bytes n bytes to the left
-					// This seeks inside the file_buffer to the position 
-					// (seek_counter as usize), i.e. all bytes that have 
-					// previously been read. 
-					// Then a chunk of the length of the buffer vector is
-					// selected and replaced with the buffer contents, shifting
-					// the contents to the left
-					file_buffer
-						[(seek_counter as usize)..]
-						[..buffer.len()]
-						.copy_from_slice(&buffer);
-
-					// Cut off right-most bytes that are now duplicates due 
-					// to the previous shift-to-left operation
-					let cutoff_index = (seek_counter as usize) + buffer.len();
-					file_buffer = file_buffer[..cutoff_index].to_vec();
-
-					// Reassign iterator to the new file buffer and seek to the
-					// current position
-					iterator_file = file_buffer.iter();
-					iterator_file.nth(seek_counter as usize);
-
-					// Account for the fact that we stepped back the prefix
-					// marker and the marker itself (note the increment at the
-					// end of the iteration, which is why we remove two as one
-					// gets added back again there)
-					seek_counter -= 2;
-				},
-				0xd9	=> break,                                               // EOI marker
-				_		=> (),                                                  // Every other marker
-			}
+		// Any other segment ends a sequence of EXIF APP1 segments
+		primary_continues = false;
+		return !is_exif;
+	});
+}
 
-			previous_byte_was_marker_prefix = false;
-		}
+/// Walks the segments of the JPEG data in the given buffer (which is expected
+/// to start with a valid signature) up to the start of the image data using
+/// `SegmentIter` and copies the ones for which the given closure returns true
+/// into the new file buffer. The closure gets the entire segment including 
+/// its marker and length. Fill bytes, markers without length information and
+/// the image data (with everything after it) are always kept, as well as 
+/// everything following a segment that can't be read (e.g. a truncated one).
+fn
+retain_segments
+(
+	file_buffer: &[u8],
+	mut keep:    impl FnMut(&[u8]) -> bool
+)
+-> Vec<u8>
+{
+	let mut new_file_buffer: Vec<u8> = JPG_SIGNATURE.to_vec();
+	let mut position = JPG_SIGNATURE.len();
+
+	let Ok(segments) = SegmentIter::new(Cursor::new(file_buffer))
+	else
+	{
+		return file_buffer.to_vec();
+	};
+
+	for segment in segments
+	{
+		let Ok(segment) = segment
 		else
 		{
-			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+			break;
+		};
+
+		if matches!(segment.marker, 0xd9 | 0xda)                                // EOI or SOS marker
+		{
+			break;
 		}
 
-		seek_counter += 1;
+		// Fill bytes in front of the marker
+		let segment_start = segment.offset as usize;
+		new_file_buffer.extend(file_buffer[position..segment_start].iter());
 
+		let segment_end = std::cmp::min(segment.data_offset as usize + segment.data_length, file_buffer.len());
+		let has_length = !matches!(segment.marker, 0x01 | 0xd0..=0xd7);
+		if !has_length || keep(&file_buffer[segment_start..segment_end])
+		{
+			new_file_buffer.extend(file_buffer[segment_start..segment_end].iter());
+		}
+
+		position = segment_end;
 	}
-	
-	// Write the file
-	// Possible to optimize further by returning the purged bytestream itself?
-	file = std::fs::OpenOptions::new().write(true).truncate(true).open(path)?;
-	perform_file_action!(file.write_all(&file_buffer));
 
-	return Ok(());
+	// Copy the image data and everything after it
+	new_file_buffer.extend(file_buffer[position..].iter());
+
+	return new_file_buffer;
 }
 
 /// Provides the JPEG specific encoding result as vector of bytes to be used
@@ -223,10 +272,39 @@ as_u8_vec
 	encode_metadata_jpg(general_encoded_metadata)
 }
 
+/// Wraps the given TIFF data (starting with the byte order marker, e.g. as
+/// written by `Metadata::write_exif_blob`) into a minimal JPEG skeleton, 
+/// consisting of the SOI marker, the APP1 segment(s) with the EXIF data and
+/// the EOI marker. The result can be parsed as JPEG file (e.g. for reading
+/// the metadata from it again), but is not a renderable image as it contains
+/// no pixel data. See also `Metadata::as_minimal_jpeg`.
+/// 
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::jpg::wrap_metadata_as_minimal_jpeg;
+/// 
+/// let tiff_data = std::fs::read("image.exif").unwrap();
+/// std::fs::write("metadata.jpg", wrap_metadata_as_minimal_jpeg(&tiff_data)).unwrap();
+/// ```
+pub fn
+wrap_metadata_as_minimal_jpeg
+(
+	metadata: &[u8]
+)
+-> Vec<u8>
+{
+	let mut jpeg = JPG_SIGNATURE.to_vec();
+	jpeg.extend(encode_metadata_jpg(&metadata.to_vec()).iter());
+	jpeg.extend(JPG_EOI_MARKER.iter());
+
+	return jpeg;
+}
+
 /// Writes the given generally encoded metadata to the JP(E)G image file at 
 /// the specified path. 
 /// Note that any previously stored metadata under the APP1 marker gets removed
-/// first before writing the "new" metadata. 
+/// first before writing the "new" metadata, i.e. all APP1 segments (including
+/// duplicate EXIF segments and non-EXIF ones like XMP). 
 pub(crate) fn
 write_metadata
 (
@@ -235,33 +313,50 @@ write_metadata
 )
 -> Result<(), std::io::Error>
 {
-	clear_metadata(path)?;
-
-	// Encode the data specifically for JPG and open the file...
-	let encoded_metadata = encode_metadata_jpg(general_encoded_metadata);
-	let mut file = OpenOptions::new()
-		.write(true)
-		.read(true)
-		.open(path)
-		.expect("Could not open file");
-
-	// ...and copy everything after the signature into a buffer...
-	let mut buffer = Vec::new();
-	perform_file_action!(file.seek(SeekFrom::Start(JPG_SIGNATURE.len() as u64)));
-	perform_file_action!(file.read_to_end(&mut buffer));
+	let file_result = check_signature(path);
 
-	// ...seek back to where the encoded data will be written
-	perform_file_action!(file.seek(SeekFrom::Start(JPG_SIGNATURE.len() as u64)));
+	if file_result.is_err()
+	{
+		return Err(file_result.err().unwrap());
+	}
 
-	// ...and write the exif data...
-	perform_file_action!(file.write_all(&encoded_metadata));
+	// Load the entire file into memory...
+	let mut file = file_result.unwrap();
+	let mut file_buffer: Vec<u8> = Vec::new();
+	perform_file_action!(file.seek(SeekFrom::Start(0)));
+	perform_file_action!(file.read_to_end(&mut file_buffer));
 
-	// ...and the rest of the file from the buffer
-	perform_file_action!(file.write_all(&buffer));
+	// ...and replace its contents
+	let new_file_buffer = write_metadata_to_bytes(&file_buffer, general_encoded_metadata)?;
+	file = std::fs::OpenOptions::new().write(true).truncate(true).open(path)?;
+	perform_file_action!(file.write_all(&new_file_buffer));
 	
 	return Ok(());
 }
 
+/// Writes the given generally encoded metadata to the JPEG data in the given
+/// buffer, returning the resulting file contents. Like for `write_metadata`,
+/// any previously stored metadata under the APP1 marker gets removed and the
+/// new APP1 segment is placed directly after the signature.
+pub(crate) fn
+write_metadata_to_bytes
+(
+	file_buffer:              &[u8],
+	general_encoded_metadata: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let cleared_file_buffer = clear_metadata_from_bytes(file_buffer)?;
+
+	let mut new_file_buffer = JPG_SIGNATURE.to_vec();
+	new_file_buffer.extend(encode_metadata_jpg(general_encoded_metadata).iter());
+	new_file_buffer.extend(cleared_file_buffer[JPG_SIGNATURE.len()..].iter());
+
+	return Ok(new_file_buffer);
+}
+
+/// Reads the raw EXIF data from the JP(E)G image file at the specified path.
+/// EXIF data split across multiple APP1 segments gets reassembled.
 pub(crate) fn
 read_metadata
 (
@@ -276,48 +371,510 @@ read_metadata
 		return Err(file_result.err().unwrap());
 	}
 
-	// Setup of variables necessary for going through the file
-	let mut file = file_result.unwrap();                                        // The struct for interacting with the file
-	let mut byte_buffer = [0u8; 1];                                             // A buffer for reading in a byte of data from the file
-	let mut previous_byte_was_marker_prefix = false;                            // A boolean for remembering if the previous byte was a marker prefix (0xFF)
+	// Load the entire file into memory and walk its segments
+	let mut file = file_result.unwrap();
+	let mut file_buffer: Vec<u8> = Vec::new();
+	perform_file_action!(file.seek(SeekFrom::Start(0)));
+	perform_file_action!(file.read_to_end(&mut file_buffer));
+
+	return read_metadata_from_bytes(&file_buffer);
+}
+
+/// Reads the raw EXIF data from the JP(E)G image file at the specified path
+/// like `read_metadata`, but allows up to the given number of bytes to 
+/// precede the signature (SOI marker). These get ignored.
+pub(crate) fn
+read_metadata_lenient
+(
+	path:                &Path,
+	leading_bytes_limit: usize
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if !path.exists()
+	{
+		return io_error!(NotFound, "Can't open JPG file - File does not exist!");
+	}
+
+	let file_buffer = std::fs::read(path)?;
+
+	if let Some(signature_position) = find_signature(&file_buffer, leading_bytes_limit)
+	{
+		return read_metadata_from_bytes(&file_buffer[signature_position..]);
+	}
+
+	return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+}
+
+/// Writes the given generally encoded metadata to the JP(E)G image file at 
+/// the specified path like `write_metadata`, but allows up to the given 
+/// number of bytes to precede the signature (SOI marker). These are kept
+/// as they are in front of the written JPEG data.
+pub(crate) fn
+write_metadata_lenient
+(
+	path:                     &Path,
+	general_encoded_metadata: &Vec<u8>,
+	leading_bytes_limit:      usize
+)
+-> Result<(), std::io::Error>
+{
+	if !path.exists()
+	{
+		return io_error!(NotFound, "Can't open JPG file - File does not exist!");
+	}
+
+	let file_buffer = std::fs::read(path)?;
+
+	let Some(signature_position) = find_signature(&file_buffer, leading_bytes_limit)
+	else
+	{
+		return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+	};
+
+	let mut new_file_buffer = file_buffer[..signature_position].to_vec();
+	new_file_buffer.extend(write_metadata_to_bytes(&file_buffer[signature_position..], general_encoded_metadata)?);
+	std::fs::write(path, new_file_buffer)?;
+
+	return Ok(());
+}
+
+/// Searches for the signature (SOI marker) within the first bytes of the
+/// given JPEG data, allowing up to the given number of bytes to precede it.
+/// Returns the position of the first signature found.
+fn
+find_signature
+(
+	file_buffer:         &[u8],
+	leading_bytes_limit: usize
+)
+-> Option<usize>
+{
+	let search_end = std::cmp::min(file_buffer.len(), leading_bytes_limit + JPG_SIGNATURE.len());
+	return file_buffer[..search_end]
+		.windows(JPG_SIGNATURE.len())
+		.position(|window| window == JPG_SIGNATURE);
+}
+
+/// Reads the raw EXIF data from the leading bytes of a JPEG file by walking
+/// the segments up to the start of the image data (SOS marker), which is
+/// enough as the APP1 segment has to precede it. 
+/// If the given bytes end before the EXIF data could be read, an error of kind
+/// `UnexpectedEof` states how many bytes are required in total.
+/// A completely filled APP1 segment may be followed by further APP1 segments
+/// continuing the EXIF data, in which case their data (after the EXIF header)
+/// gets appended.
+pub(crate) fn
+read_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if file_buffer.len() < JPG_SIGNATURE.len()
+	{
+		return incomplete_data_error!(JPG_SIGNATURE.len());
+	}
+
+	if file_buffer[0..JPG_SIGNATURE.len()] != JPG_SIGNATURE
+	{
+		return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+	}
 
-	loop
+	let segments = SegmentIter::new(Cursor::new(file_buffer))?;
+	let mut exif_data: Option<Vec<u8>> = None;
+
+	for segment in segments
 	{
-		// Read next byte into buffer
-		perform_file_action!(file.read(&mut byte_buffer));
+		let segment = segment?;
+		match segment.marker
+		{
+			0x01 | 0xd0..=0xd7  => continue,                                    // Markers without length information
+			0xd9 | 0xda         => break,                                       // EOI or SOS marker
+			_                   => (),                                          // Every other marker
+		}
 
-		if previous_byte_was_marker_prefix
+		// APP1 marker - make sure this is the EXIF and not e.g. XMP data
+		if segment.marker == 0xe1
 		{
-			match byte_buffer[0]
+			let data_start  = segment.data_offset as usize;
+			let segment_end = data_start + segment.data_length;
+			if file_buffer.len() < segment_end
+			{
+				return incomplete_data_error!(segment_end);
+			}
+
+			let payload = &file_buffer[data_start..segment_end];
+			if payload.starts_with(&EXIF_HEADER)
 			{
-				0xe1	=> {                                                    // APP1 marker
+				match exif_data.as_mut()
+				{
+					Some(data) => data.extend(payload[EXIF_HEADER.len()..].iter()),
+					None       => exif_data = Some(payload.to_vec()),
+				}
+
+				// Only a completely filled segment may have a continuation
+				if payload.len() - EXIF_HEADER.len() < JPG_APP1_MAX_TIFF_LENGTH
+				{
+					break;
+				}
+
+				continue;
+			}
+		}
+
+		// Any other segment ends a sequence of EXIF APP1 segments
+		if exif_data.is_some()
+		{
+			break;
+		}
+	}
+
+	if let Some(data) = exif_data
+	{
+		return Ok(data);
+	}
+
+	return io_error!(Other, "No EXIF data found!");
+}
+
+/// Reads the color transform flag of the APP14 "Adobe" segment of the JPEG
+/// image file at the specified path, which describes how the color channels
+/// are encoded: 0 for RGB or CMYK (i.e. no transform), 1 for YCbCr and 2 for
+/// YCCK. Returns `None` if there is no such segment before the image data.
+/// 
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::jpg::read_adobe_transform;
+/// 
+/// if let Some(transform) = read_adobe_transform(std::path::Path::new("image.jpg")).unwrap()
+/// {
+///     println!("Adobe color transform: {}", transform);
+/// }
+/// ```
+pub fn
+read_adobe_transform
+(
+	path: &Path
+)
+-> Result<Option<u8>, std::io::Error>
+{
+	// Only reading, so no write access needed - the iterator checks the
+	// signature of the file
+	let reader = std::io::BufReader::new(File::open(path)?);
+
+	let mut segments = SegmentIter::new(reader)?;
+	while let Some(segment) = segments.next()
+	{
+		let segment = segment?;
+		match segment.marker()
+		{
+			0xda => break,                                                      // SOS marker, APP14 has to precede the image data
+			0xee => {
+				// "Adobe", followed by the version, two flag fields (2 bytes 
+				// each) and the transform flag
+				let data = segments.read_data(&segment)?;
+				if data.len() >= 12 && data.starts_with(JPG_ADOBE_IDENTIFIER)
+				{
+					return Ok(Some(data[11]));
+				}
+			},
+			_ => (),
+		}
+	}
+
+	return Ok(None);
+}
+
+/// A segment of a JPEG file as found by `SegmentIter`. Only the position of
+/// its data is stored, which can be read on demand (see 
+/// `SegmentIter::read_data`). 
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+Segment
+{
+	marker:      u8,     // The byte following the marker prefix, e.g. 0xe1 for APP1
+	offset:      u64,    // Where the segment starts (i.e. its marker prefix) within the reader
+	data_offset: u64,    // Where the data starts, i.e. after the marker and length field
+	data_length: usize,  // Length of the data WITHOUT the 2 bytes of the length field
+}
+
+impl
+Segment
+{
+	/// Gets the marker of the segment without the `0xff` prefix, e.g. 
+	/// `0xe1` for an APP1 or `0xda` for an SOS segment
+	pub fn
+	marker
+	(
+		&self
+	)
+	-> u8
+	{
+		self.marker
+	}
+
+	/// Gets the position of the segment (i.e. its marker) within the reader
+	pub fn
+	offset
+	(
+		&self
+	)
+	-> u64
+	{
+		self.offset
+	}
+
+	/// Gets the length of the data of the segment, i.e. without the marker
+	/// and the length field. For segments without length information (e.g.
+	/// the EOI marker) this is 0. For SOS segments, the entropy coded image
+	/// data following them is not included.
+	pub fn
+	data_len
+	(
+		&self
+	)
+	-> usize
+	{
+		self.data_length
+	}
+}
+
+/// An iterator over the segments of a JPEG file, reading just the markers
+/// and length fields and seeking past the data. This includes the segments
+/// after the start of the image data, as the entropy coded data following
+/// each SOS segment gets skipped. The iteration ends after the EOI marker
+/// or an error (e.g. an invalid marker or the data ending prematurely).
+/// If a marker or length field is cut off, the error of kind `UnexpectedEof`
+/// has an `IncompleteData` struct as inner error, which tells how many bytes
+/// (counted from the start of the reader) are required.
+/// The byte based readers and writers of this module walk the segments using
+/// this iterator as well.
+/// 
+/// # Examples
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufReader;
+/// use revolt_little_exif::jpg::SegmentIter;
+/// 
+/// let file = BufReader::new(File::open("image.jpg").unwrap());
+/// let mut segments = SegmentIter::new(file).unwrap();
+/// while let Some(segment) = segments.next()
+/// {
+///     let segment = segment.unwrap();
+///     if segment.marker() == 0xfe
+///     {
+///         let comment = segments.read_data(&segment).unwrap();
+///     }
+/// }
+/// ```
+pub struct
+SegmentIter<R: Read + Seek>
+{
+	reader:   R,
+	in_scan:  bool,                                                         // Whether the previous segment was SOS, followed by entropy coded data
+	finished: bool,
+}
+
+impl<R: Read + Seek>
+SegmentIter<R>
+{
+	/// Constructs the iterator, checking that the reader continues with the
+	/// JPEG signature (SOI marker) at its current position
+	pub fn
+	new
+	(
+		mut reader: R
+	)
+	-> Result<SegmentIter<R>, std::io::Error>
+	{
+		let mut signature_buffer = [0u8; 2];
+		reader.read_exact(&mut signature_buffer)?;
+
+		if signature_buffer != JPG_SIGNATURE
+		{
+			return io_error!(InvalidData, "Can't open JPG file - Wrong signature!");
+		}
+
+		return Ok(SegmentIter { reader: reader, in_scan: false, finished: false });
+	}
+
+	/// Reads the data of the given segment (without the marker and length 
+	/// field). The position of the reader is restored afterwards, so the
+	/// iteration may continue.
+	pub fn
+	read_data
+	(
+		&mut self,
+		segment: &Segment
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		let position = self.reader.stream_position()?;
+
+		let mut data = vec![0u8; segment.data_length];
+		self.reader.seek(SeekFrom::Start(segment.data_offset))?;
+		let read_result = self.reader.read_exact(&mut data);
+		self.reader.seek(SeekFrom::Start(position))?;
+
+		read_result?;
+		return Ok(data);
+	}
 
-					// Read in the length of the segment
-					// (which follows immediately after the marker)
-					let mut length_buffer = [0u8; 2];
-					perform_file_action!(file.read(&mut length_buffer));
+	/// Gets back the underlying reader
+	pub fn
+	into_inner
+	(
+		self
+	)
+	-> R
+	{
+		self.reader
+	}
 
-					// Decode the length to determine how much more data there is
-					let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big);
-					let remaining_length = (length - 2) as usize;
+	/// Seeks to the marker following the entropy coded data. Within this
+	/// data, a marker prefix is followed by 0x00 (an actual 0xff byte), fill
+	/// bytes or a restart marker, which all belong to the data.
+	fn
+	skip_entropy_coded_data
+	(
+		&mut self
+	)
+	-> Result<(), std::io::Error>
+	{
+		let mut buffer = [0u8; 4096];
+		let mut previous_is_prefix = false;
 
-					// Read in the remaining data
-					let mut buffer = vec![0u8; remaining_length];
-					perform_file_action!(file.read(&mut buffer));
+		loop
+		{
+			let chunk_start = self.reader.stream_position()?;
+			let bytes_read  = self.reader.read(&mut buffer)?;
 
-					return Ok(buffer);
-				},
-				0xd9	=> break,                                               // EOI marker
-				_		=> (),                                                  // Every other marker
+			if bytes_read == 0
+			{
+				return io_error!(UnexpectedEof, "JPG image data ends without a marker!");
 			}
 
-			previous_byte_was_marker_prefix = false;
+			for (index, byte) in buffer[..bytes_read].iter().enumerate()
+			{
+				if previous_is_prefix && !matches!(byte, 0x00 | 0xd0..=0xd7 | 0xff)
+				{
+					// The prefix may have been the last byte of the previous chunk
+					self.reader.seek(SeekFrom::Start(chunk_start + index as u64 - 1))?;
+					return Ok(());
+				}
+				previous_is_prefix = *byte == JPG_MARKER_PREFIX;
+			}
 		}
-		else
+	}
+
+	/// Fills the given buffer from the reader. If the reader ends before, 
+	/// an error of kind `UnexpectedEof` states how many bytes (counted from
+	/// the start of the reader) are required in total.
+	fn
+	read_exact_or_incomplete
+	(
+		&mut self,
+		buffer: &mut [u8]
+	)
+	-> Result<(), std::io::Error>
+	{
+		let required_length = self.reader.stream_position()? as usize + buffer.len();
+
+		if let Err(error) = self.reader.read_exact(buffer)
 		{
-			previous_byte_was_marker_prefix = byte_buffer[0] == JPG_MARKER_PREFIX;
+			if error.kind() == std::io::ErrorKind::UnexpectedEof
+			{
+				return incomplete_data_error!(required_length);
+			}
+			return Err(error);
 		}
+		return Ok(());
 	}
 
-	return io_error!(Other, "No EXIF data found!");
-}
\ No newline at end of file
+	fn
+	read_segment
+	(
+		&mut self
+	)
+	-> Result<Segment, std::io::Error>
+	{
+		if self.in_scan
+		{
+			self.skip_entropy_coded_data()?;
+			self.in_scan = false;
+		}
+
+		let mut offset = self.reader.stream_position()?;
+
+		let mut marker_buffer = [0u8; 2];
+		self.read_exact_or_incomplete(&mut marker_buffer)?;
+
+		if marker_buffer[0] != JPG_MARKER_PREFIX
+		{
+			return io_error!(InvalidData, "Expected JPG marker prefix!");
+		}
+
+		// Skip fill bytes preceding the actual marker
+		while marker_buffer[1] == JPG_MARKER_PREFIX
+		{
+			offset += 1;
+			self.read_exact_or_incomplete(&mut marker_buffer[1..])?;
+		}
+
+		let marker = marker_buffer[1];
+
+		// Markers without length information
+		if matches!(marker, 0x01 | 0xd0..=0xd7 | 0xd9)
+		{
+			let data_offset = self.reader.stream_position()?;
+			return Ok(Segment { marker: marker, offset: offset, data_offset: data_offset, data_length: 0 });
+		}
+
+		let mut length_buffer = [0u8; 2];
+		self.read_exact_or_incomplete(&mut length_buffer)?;
+		let length = from_u8_vec_macro!(u16, &length_buffer.to_vec(), &Endian::Big) as usize;
+
+		if length < 2
+		{
+			return io_error!(InvalidData, "Invalid JPG segment length!");
+		}
+
+		let data_offset = self.reader.stream_position()?;
+		self.reader.seek(SeekFrom::Current(length as i64 - 2))?;
+
+		self.in_scan = marker == 0xda;
+
+		return Ok(Segment { marker: marker, offset: offset, data_offset: data_offset, data_length: length - 2 });
+	}
+}
+
+impl<R: Read + Seek>
+Iterator
+for
+SegmentIter<R>
+{
+	type Item = Result<Segment, std::io::Error>;
+
+	fn
+	next
+	(
+		&mut self
+	)
+	-> Option<Self::Item>
+	{
+		if self.finished
+		{
+			return None;
+		}
+
+		let result = self.read_segment();
+		match &result
+		{
+			Ok(segment) => self.finished = segment.marker == 0xd9,              // EOI marker
+			Err(_)      => self.finished = true,
+		}
+
+		return Some(result);
+	}
+}
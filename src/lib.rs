@@ -9,8 +9,8 @@
 //! # Usage
 //! ## Write EXIF data
 //! ```no_run
-//! use little_exif::metadata::Metadata;
-//! use little_exif::exif_tag::ExifTag;
+//! use revolt_little_exif::metadata::Metadata;
+//! use revolt_little_exif::exif_tag::ExifTag;
 //! 
 //! let mut metadata = Metadata::new();
 //! metadata.set_tag(
@@ -24,11 +24,14 @@
 #![crate_name = "revolt_little_exif"]
 
 mod general_file_io;
-mod png;
+pub mod png;
 mod png_chunk;
-mod jpg;
-mod webp;
+pub mod jpg;
+pub mod webp;
+mod ico;
+mod pdf;
 mod riff_chunk;
+mod trailer;
 
 pub mod endian;
 pub mod rational;
@@ -36,4 +39,17 @@ pub mod u8conversion;
 pub mod exif_tag;
 pub mod exif_tag_format;
 pub mod filetype;
-pub mod metadata;
\ No newline at end of file
+pub mod metadata;
+pub mod decode_options;
+pub mod maker_note;
+pub mod preview;
+pub mod prelude;
+pub mod util;
+pub mod xmp;
+
+#[cfg(feature = "orientation")]
+pub mod orientation;
+
+pub use exif_tag::all_known_tags;
+pub use metadata::read_camera;
+pub use metadata::transcode_metadata;
@@ -0,0 +1,107 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Identifying the vendor specific format of the `MakerNote` tag.
+//! The MakerNote is always kept as an opaque blob of bytes, which gets
+//! written back unchanged: Formats like the one of newer Nikon cameras use
+//! offsets relative to the start of the MakerNote and therefore stay valid
+//! when little_exif relocates the MakerNote within the EXIF data.
+//! As MakerNotes may use a byte order different from the one of the EXIF
+//! data (see `MakerNote::endian`), the endianness of the EXIF data is never
+//! applied to them.
+
+use crate::endian::Endian;
+
+const NIKON_IDENTIFIER: [u8; 6] = [0x4e, 0x69, 0x6b, 0x6f, 0x6e, 0x00];    // "Nikon\0"
+
+// Type 3 MakerNotes have the version and two unused bytes after the
+// identifier, followed by their own TIFF header
+const NIKON_TIFF_HEADER_OFFSET: usize = 10;
+
+/// The format of a MakerNote, as identified by its header bytes
+#[derive(Debug, PartialEq)]
+pub enum
+MakerNote
+{
+	/// Nikon MakerNote starting with `"Nikon\0"`, a version and a TIFF header
+	/// with its own byte order, to which the offsets of its IFD are relative
+	NikonType3 { endian: Endian },
+
+	/// Nikon MakerNote starting with `"Nikon\0"` without a TIFF header, as
+	/// written by older cameras
+	NikonType1,
+
+	/// Any other MakerNote format (including the Nikon type 2 format, which
+	/// has no header at all)
+	Unknown,
+}
+
+impl
+MakerNote
+{
+	/// Identifies the format of the given MakerNote data, e.g. the value of
+	/// the `MakerNote` tag.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::endian::Endian;
+	/// use revolt_little_exif::maker_note::MakerNote;
+	///
+	/// let data = b"Nikon\0\x02\x10\0\0MM\0\x2a\0\0\0\x08";
+	/// assert_eq!(MakerNote::from_bytes(data), MakerNote::NikonType3 { endian: Endian::Big });
+	/// ```
+	pub fn
+	from_bytes
+	(
+		data: &[u8]
+	)
+	-> MakerNote
+	{
+		if !data.starts_with(&NIKON_IDENTIFIER)
+		{
+			return MakerNote::Unknown;
+		}
+
+		let tiff_header = data.get(NIKON_TIFF_HEADER_OFFSET..).unwrap_or(&[]);
+
+		if tiff_header.starts_with(&Endian::Little.header()[0..4])
+		{
+			return MakerNote::NikonType3 { endian: Endian::Little };
+		}
+
+		if tiff_header.starts_with(&Endian::Big.header()[0..4])
+		{
+			return MakerNote::NikonType3 { endian: Endian::Big };
+		}
+
+		return MakerNote::NikonType1;
+	}
+
+	/// Gets the byte order the MakerNote declares for its own IFD, which may
+	/// differ from the one of the EXIF data containing it and hence has to
+	/// be used when interpreting its entries. Returns `None` for formats 
+	/// without such a declaration, which are assumed to use the byte order 
+	/// of the EXIF data.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::endian::Endian;
+	/// use revolt_little_exif::maker_note::MakerNote;
+	///
+	/// let data = b"Nikon\0\x02\x10\0\0II\x2a\0\x08\0\0\0";
+	/// assert_eq!(MakerNote::from_bytes(data).endian(), Some(&Endian::Little));
+	/// ```
+	pub fn
+	endian
+	(
+		&self
+	)
+	-> Option<&Endian>
+	{
+		match self
+		{
+			MakerNote::NikonType3 { endian } => Some(endian),
+			_                                => None,
+		}
+	}
+}
@@ -4,26 +4,239 @@
 use std::path::Path;
 use std::str::FromStr;
 
+#[cfg(feature = "content_hash")]
+use sha2::Digest;
+#[cfg(feature = "content_hash")]
+use sha2::Sha256;
+
 use crate::endian::*;
 use crate::u8conversion::*;
 use crate::exif_tag::ExifTag;
 use crate::exif_tag::ExifTagGroup;
 use crate::exif_tag_format::ExifTagFormat;
+use crate::exif_tag_format::TagValue;
+use crate::rational::uR64;
 use crate::filetype::FileExtension;
+use crate::filetype::SNIFF_LENGTH;
+use crate::decode_options::DecodeOptions;
+use crate::maker_note::MakerNote;
+use crate::preview;
+use crate::preview::Preview;
+use crate::xmp::Xmp;
 use crate::general_file_io::*;
 
 use crate::jpg;
 use crate::png;
 use crate::webp;
+use crate::ico;
+use crate::pdf;
+use crate::trailer;
 
 const IFD_ENTRY_LENGTH: u32     = 12;
 const IFD_END:          [u8; 4] = [0x00, 0x00, 0x00, 0x00];
 
+// The Compression value of IFD1 for thumbnails stored as JPEG file
+const JPEG_THUMBNAIL_COMPRESSION: u16 = 6;
+
+// The groups that get encoded by default, i.e. all IFDs little_exif writes
+const ALL_GROUPS: [ExifTagGroup; 5] = [
+	ExifTagGroup::IFD0,
+	ExifTagGroup::ExifIFD,
+	ExifTagGroup::InteropIFD,
+	ExifTagGroup::GPSIFD,
+	ExifTagGroup::IFD1,
+];
+
+// The shutter speed denominators (for exposures shorter than a second) and 
+// f-numbers marked on cameras in steps of a third stop, which the values 
+// converted from APEX get rounded to if they are close enough to one
+const STANDARD_SHUTTER_SPEED_DENOMINATORS: [f64; 39] = [
+	2.0, 3.0, 4.0, 5.0, 6.0, 8.0, 10.0, 13.0, 15.0, 20.0, 25.0, 30.0, 40.0, 
+	50.0, 60.0, 80.0, 100.0, 125.0, 160.0, 200.0, 250.0, 320.0, 400.0, 500.0, 
+	640.0, 800.0, 1000.0, 1250.0, 1600.0, 2000.0, 2500.0, 3200.0, 4000.0, 
+	5000.0, 6400.0, 8000.0, 10000.0, 12800.0, 16000.0,
+];
+const STANDARD_F_NUMBERS: [f64; 37] = [
+	1.0, 1.1, 1.2, 1.4, 1.6, 1.8, 2.0, 2.2, 2.5, 2.8, 3.2, 3.5, 4.0, 4.5, 5.0,
+	5.6, 6.3, 7.1, 8.0, 9.0, 10.0, 11.0, 13.0, 14.0, 16.0, 18.0, 20.0, 22.0, 
+	25.0, 29.0, 32.0, 36.0, 40.0, 45.0, 51.0, 57.0, 64.0,
+];
+
+/// The reference for directions stored in the GPS IFD (e.g. by the 
+/// `GPSImgDirectionRef` tag), see `Metadata::get_gps_img_direction`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum
+NorthReference
+{
+	True,
+	Magnetic,
+}
+
+/// The location and area of the main subject of the image in pixels, as
+/// described by the `SubjectArea` tag (see `Metadata::get_subject_area`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum
+SubjectArea
+{
+	Point     { x: u16, y: u16 },
+	Circle    { x: u16, y: u16, diameter: u16 },
+	Rectangle { x: u16, y: u16, width: u16, height: u16 },                  // The center of the rectangle and its dimensions
+}
+
+/// Tells how many bytes (counted from the start of the file) are required for
+/// reading the metadata if the data given to `Metadata::from_header_bytes` 
+/// was not enough. Gets returned as inner error of an `std::io::Error` of kind
+/// `UnexpectedEof`.
+#[derive(Debug, PartialEq)]
+pub struct
+IncompleteData
+{
+	required_length: usize
+}
+
+impl
+IncompleteData
+{
+	pub(crate) fn
+	new
+	(
+		required_length: usize
+	)
+	-> IncompleteData
+	{
+		IncompleteData { required_length }
+	}
+
+	/// Gets the number of bytes that are required at least.
+	/// Note that this might not be enough yet, as e.g. the length of the
+	/// next segment or chunk is only known after reading its header.
+	pub fn
+	required_length
+	(
+		&self
+	)
+	-> usize
+	{
+		self.required_length
+	}
+}
+
+impl
+std::fmt::Display
+for
+IncompleteData
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		write!(f, "Not enough data - at least {} bytes are required!", self.required_length)
+	}
+}
+
+impl std::error::Error for IncompleteData {}
+
+/// A problem encountered while decoding the metadata that did not prevent the
+/// decoding as a whole, e.g. an IFD entry that got skipped due to 
+/// `DecodeOptions::skip_invalid_entries`. See `Metadata::decode_warnings`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+DecodeWarning
+{
+	/// The IFD the problem occurred in
+	pub group:   ExifTagGroup,
+
+	/// The offset of the affected entry relative to the start of its IFD, 
+	/// or 0 if the entire IFD is affected
+	pub offset:  u32,
+
+	/// The hex value of the affected entry's tag, if the problem concerns a
+	/// single entry
+	pub hex_tag: Option<u16>,
+
+	/// A description of the problem
+	pub message: String,
+}
+
+impl
+std::fmt::Display
+for
+DecodeWarning
+{
+	fn
+	fmt
+	(
+		&self,
+		f: &mut std::fmt::Formatter<'_>
+	)
+	-> std::fmt::Result
+	{
+		match self.hex_tag
+		{
+			Some(hex_tag) => write!(f, "Skipped entry 0x{:04x} at {:?}+0x{:x}: {}", hex_tag, self.group, self.offset, self.message),
+			None          => write!(f, "Skipped {:?}: {}", self.group, self.message),
+		}
+	}
+}
+
+/// The state shared by the (recursive) calls of `Metadata::decode_ifd` while
+/// decoding the IFDs of some TIFF data
+struct
+DecodeContext<'a>
+{
+	source_order: Vec<(ExifTagGroup, u16, u16)>,                            // The IFD entries (group, hex value, format) in the order they were decoded
+	warnings:     Vec<DecodeWarning>,                                       // The problems that did not prevent the decoding
+	ancestors:    Vec<u32>,                                                 // The offsets of the IFDs linking to the current one
+	options:      &'a DecodeOptions,
+}
+
+/// The differences between two `Metadata` objects as computed by
+/// `Metadata::diff`. Tags are identified by their hex value and group, so 
+/// tags with the same hex value in e.g. IFD0 and IFD1 are treated separately.
+#[derive(Debug, PartialEq)]
+pub struct
+MetadataDiff<'a>
+{
+	/// Tags only present in the other metadata
+	pub added:   Vec<&'a ExifTag>,
+
+	/// Tags only present in the original metadata
+	pub removed: Vec<&'a ExifTag>,
+
+	/// Tags present in both, but with different values (old, new)
+	pub changed: Vec<(&'a ExifTag, &'a ExifTag)>,
+}
+
+impl<'a>
+MetadataDiff<'a>
+{
+	/// Checks if there are no differences at all
+	pub fn
+	is_empty
+	(
+		&self
+	)
+	-> bool
+	{
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
 pub struct
 Metadata
 {
-	data:   Vec<ExifTag>,
-	endian: Endian 
+	data:         Vec<ExifTag>,
+	endian:       Endian,
+	thumbnail:    Option<Vec<u8>>,
+	original_raw: Option<Vec<u8>>,
+	source_order: Vec<(ExifTagGroup, u16, u16)>,                            // The IFD entries (group, hex value, format) in the order they were decoded
+	preserve_source_order: bool,
+	decode_warnings: Vec<DecodeWarning>,                                    // The problems that did not prevent the decoding
+	previews:     Vec<Preview>,                                             // The previews besides the thumbnail, which are not written
 }
 
 impl
@@ -36,7 +249,7 @@ Metadata
 	/// 
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
+	/// use revolt_little_exif::metadata::Metadata;
 	///
 	/// let mut metadata: Metadata = Metadata::new();
 	/// ```
@@ -45,7 +258,16 @@ Metadata
 	()
 	-> Metadata
 	{
-		Metadata { endian: Endian::Little, data: Vec::new() }
+		Metadata { 
+			endian:                Endian::Little, 
+			data:                  Vec::new(), 
+			thumbnail:             None, 
+			original_raw:          None, 
+			source_order:          Vec::new(), 
+			preserve_source_order: false,
+			decode_warnings:       Vec::new(),
+			previews:              Vec::new(),
+		}
 	}
 
 	/// Constructs a new `Metadata` object with the metadata from the image at the specified path.
@@ -54,7 +276,7 @@ Metadata
 	///
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
+	/// use revolt_little_exif::metadata::Metadata;
 	/// 
 	/// let mut metadata: Metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
 	/// ```
@@ -64,6 +286,79 @@ Metadata
 		path: &Path
 	)
 	-> Result<Metadata, std::io::Error>
+	{
+		Self::new_from_path_with_options(path, &DecodeOptions::new())
+	}
+
+	/// Constructs a new `Metadata` object with the metadata from the image at
+	/// the specified path like `new_from_path`, using the given options for
+	/// decoding (e.g. for keeping the raw EXIF data).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let mut options = DecodeOptions::new();
+	/// options.keep_original_raw = true;
+	///
+	/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.png"), &options).unwrap();
+	/// ```
+	pub fn
+	new_from_path_with_options
+	(
+		path:    &Path,
+		options: &DecodeOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let file_type         = Self::file_type_from_path(path)?;
+		let exif_header       = Self::exif_header_of(&file_type);
+		let reports_png_error = Self::reports_png_verification_error(&file_type, options);
+
+		let raw_pre_decode_general = match file_type
+		{
+			FileExtension::JPEG if options.jpeg_leading_bytes_limit > 0
+				=> jpg::read_metadata_lenient(path, options.jpeg_leading_bytes_limit),
+			_
+				=> Self::read_raw_from_path(path, file_type, options.verify_png_crc),
+		};
+
+		if let Err(error) = &raw_pre_decode_general
+		{
+			if reports_png_error && error.kind() == std::io::ErrorKind::InvalidData
+			{
+				return Err(raw_pre_decode_general.err().unwrap());
+			}
+		}
+
+		return Ok(Self::from_pre_decode_general(raw_pre_decode_general, exif_header, options));
+	}
+
+	/// Checks if an error of kind `InvalidData` when reading the raw EXIF data
+	/// of the given file type gets reported to the caller. This is the case 
+	/// for PNG and ICO files if their chunks get verified (e.g. in case of a
+	/// CRC mismatch), as unlike other decoding errors, this indicates a 
+	/// damaged file.
+	fn
+	reports_png_verification_error
+	(
+		file_type: &FileExtension,
+		options:   &DecodeOptions
+	)
+	-> bool
+	{
+		return options.verify_png_crc && matches!(file_type, FileExtension::PNG {as_zTXt_chunk: _} | FileExtension::ICO);
+	}
+
+	/// Determines the file type of the image at the specified path using
+	/// its extension, making sure that the file exists.
+	fn
+	file_type_from_path
+	(
+		path: &Path
+	)
+	-> Result<FileExtension, std::io::Error>
 	{
 		if !path.exists()
 		{
@@ -88,37 +383,31 @@ Metadata
 			return io_error!(Unsupported, "Can't read Metadata - Unsupported file type!");
 		}
 
-		// Call the file specific decoders as a starting point for obtaining
-		// the raw EXIF data that gets further processed
-		let raw_pre_decode_general = match raw_file_type.unwrap()
+		return Ok(raw_file_type.unwrap());
+	}
+
+	/// Calls the file specific decoders as a starting point for obtaining
+	/// the raw EXIF data that gets further processed
+	fn
+	read_raw_from_path
+	(
+		path:           &Path,
+		raw_file_type:  FileExtension,
+		verify_png_crc: bool
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		match raw_file_type
 		{
 			FileExtension::JPEG 
 				=>  jpg::read_metadata(&path),
 			FileExtension::PNG {as_zTXt_chunk: _} 
-				=>  png::read_metadata(&path),
+				=>  png::read_metadata(&path, verify_png_crc),
 			FileExtension::WEBP 
 				=> webp::read_metadata(&path),
-		};
-
-		if let Ok(pre_decode_general) = raw_pre_decode_general
-		{
-			let decoding_result = Self::decode_metadata_general(&pre_decode_general);
-			if let Ok((endian, data)) = decoding_result
-			{
-				return Ok(Metadata { endian, data });
-			}
-			else
-			{
-				eprintln!("{}", decoding_result.err().unwrap());
-			}
-		}
-		else
-		{
-			eprintln!("Error during decoding: {:?}", raw_pre_decode_general.err().unwrap());
+			FileExtension::ICO 
+				=>  ico::read_metadata(&path, verify_png_crc),
 		}
-
-		eprintln!("WARNING: Can't read metadata from file - Create new & empty struct");
-		return Ok(Metadata::new());
 	}
 
 	/// Constructs a new `Metadata` object with the metadata from the image at the specified path with the given filetype.
@@ -132,186 +421,1993 @@ Metadata
 	)
 	-> Result<Metadata, std::io::Error>
 	{
-		// Call the file specific decoders as a starting point for obtaining
-		// the raw EXIF data that gets further processed
-		let raw_pre_decode_general = match raw_file_type
-		{
-			FileExtension::JPEG 
-				=>  jpg::read_metadata(&path),
-			FileExtension::PNG {as_zTXt_chunk: _} 
-				=>  png::read_metadata(&path),
-			FileExtension::WEBP 
-				=> webp::read_metadata(&path),
-		};
-
-		if let Ok(pre_decode_general) = raw_pre_decode_general
-		{
-			let decoding_result = Self::decode_metadata_general(&pre_decode_general);
-			if let Ok((endian, data)) = decoding_result
-			{
-				return Ok(Metadata { endian, data });
-			}
-			else
-			{
-				eprintln!("{}", decoding_result.err().unwrap());
-			}
-		}
-		else
-		{
-			eprintln!("Error during decoding: {:?}", raw_pre_decode_general.err().unwrap());
-		}
-
-		eprintln!("WARNING: Can't read metadata from file - Create new & empty struct");
-		return Ok(Metadata::new());
+		let exif_header            = Self::exif_header_of(&raw_file_type);
+		let raw_pre_decode_general = Self::read_raw_from_path(path, raw_file_type, false);
+		return Ok(Self::from_pre_decode_general(raw_pre_decode_general, exif_header, &DecodeOptions::new()));
 	}
 	
-	/// Gets a shared reference to the list of all tags currently stored in the object.
+	/// Constructs a new `Metadata` object from the leading bytes of an image
+	/// file of the given type, e.g. the first few kilobytes fetched via a 
+	/// range request. This is enough if the bytes cover the APP1 segment for 
+	/// JPEG, the chunks before the image data for PNG or the EXIF chunk for
+	/// WebP (which is usually located at the end of the file).
+	/// - If the bytes are not enough, an error of kind `UnexpectedEof` gets
+	///   returned. Its inner error is an `IncompleteData` struct that tells
+	///   how many bytes (counted from the start of the file) are required.
+	/// - If unable to decode the metadata, a new, empty object gets created and returned.
 	///
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
-	/// 
-	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
-	/// for tag in metadata.data()
+	/// use revolt_little_exif::metadata::IncompleteData;
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let partial = std::fs::read("image.jpg").unwrap()[..131072].to_vec();
+	/// match Metadata::from_header_bytes(&partial, FileExtension::JPEG)
 	/// {
-	///     // do something with the tags	
+	///     Ok(metadata) => { /* do something with the metadata */ },
+	///     Err(error)   => {
+	///         if let Some(incomplete) = error.get_ref().and_then(|inner| inner.downcast_ref::<IncompleteData>())
+	///         {
+	///             println!("Need {} bytes", incomplete.required_length());
+	///         }
+	///     }
 	/// }
 	/// ```
 	pub fn
-	data
+	from_header_bytes
 	(
-		&self
+		partial:   &[u8],
+		file_type: FileExtension
 	)
-	-> &Vec<ExifTag>
+	-> Result<Metadata, std::io::Error>
 	{
-		&self.data
+		Self::from_header_bytes_with_options(partial, file_type, &DecodeOptions::new())
 	}
 
-	/// Gets the endianness of the metadata
+	/// Constructs a new `Metadata` object from the leading bytes of an image
+	/// file of the given type like `from_header_bytes`, using the given 
+	/// options for decoding. If `verify_png_crc` is set, the CRCs of the PNG
+	/// chunks up to the first IDAT chunk get verified.
 	///
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
-	/// 
-	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
-	/// let tag_data = metadata.get_tag_by_hex(0x010e).unwrap().value_as_u8_vec(metadata.get_endian());
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let mut options = DecodeOptions::new();
+	/// options.verify_png_crc = true;
+	///
+	/// let partial = std::fs::read("image.png").unwrap()[..65536].to_vec();
+	/// let metadata = Metadata::from_header_bytes_with_options(&partial, FileExtension::PNG {as_zTXt_chunk: true}, &options);
 	/// ```
 	pub fn
-	get_endian
+	from_header_bytes_with_options
 	(
-		&self
+		partial:   &[u8],
+		file_type: FileExtension,
+		options:   &DecodeOptions
 	)
-	-> &Endian
+	-> Result<Metadata, std::io::Error>
 	{
-		&self.endian
+		let exif_header       = Self::exif_header_of(&file_type);
+		let reports_png_error = Self::reports_png_verification_error(&file_type, options);
+		let raw_pre_decode_general = match file_type
+		{
+			FileExtension::JPEG 
+				=>  jpg::read_metadata_from_bytes(partial),
+			FileExtension::PNG {as_zTXt_chunk: _} 
+				=>  png::read_metadata_from_bytes(partial, options.verify_png_crc),
+			FileExtension::WEBP 
+				=> webp::read_metadata_from_bytes(partial),
+			FileExtension::ICO 
+				=>  ico::read_metadata_from_bytes(partial, options.verify_png_crc),
+		};
+
+		// Not having enough data is the one error the caller needs to know
+		// about, as it can be resolved by providing more bytes
+		if let Err(error) = &raw_pre_decode_general
+		{
+			if error.kind() == std::io::ErrorKind::UnexpectedEof
+				|| (reports_png_error && error.kind() == std::io::ErrorKind::InvalidData)
+			{
+				return Err(raw_pre_decode_general.err().unwrap());
+			}
+		}
+
+		return Ok(Self::from_pre_decode_general(raw_pre_decode_general, exif_header, options));
 	}
 
-	/// Gets the stored tag in the metadata for the given tag. 
-	/// Returns `None` if the tag is not present in the metadata struct.
+	/// Decodes the given raw EXIF data, i.e. the TIFF data starting with its
+	/// byte order information (`II` or `MM`), optionally preceded by the EXIF
+	/// header (`Exif\0\0`). This is intended for embedding little_exif in
+	/// other tools that extract the EXIF data from a container themselves.
+	/// If `assume_endian` is given, the byte order information is not checked
+	/// and the data gets decoded using the given endianness instead, e.g. for
+	/// container formats that specify it out-of-band.
+	/// - Unlike the other constructors, an error gets returned if the data
+	///   can't be decoded instead of a new, empty object.
 	///
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
-	/// use little_exif::exif_tag::ExifTag;
-	/// 
-	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
-	/// let tag = metadata.get_tag(&ExifTag::ImageDescription(String::new()));
+	/// use revolt_little_exif::endian::Endian;
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let raw_exif = std::fs::read("image.exif").unwrap();
+	/// let metadata = Metadata::decode(&raw_exif, Some(Endian::Little)).unwrap();
 	/// ```
 	pub fn
-	get_tag
+	decode
 	(
-		&self,
-		input_tag: &ExifTag
+		bytes:         &[u8],
+		assume_endian: Option<Endian>
 	)
-	-> Option<&ExifTag> 
+	-> Result<Metadata, std::io::Error>
 	{
-		self.get_tag_by_hex(input_tag.as_u16())
+		let tiff_data = bytes.strip_prefix(&EXIF_HEADER[..]).unwrap_or(bytes).to_vec();
+
+		let Some(endian) = assume_endian
+		else
+		{
+			return Self::decode_metadata_general(&tiff_data, &[], &DecodeOptions::new());
+		};
+
+		Self::check_data_length(&tiff_data, &[])?;
+		return Self::decode_tiff_data(&tiff_data, endian, &DecodeOptions::new());
 	}
 
-	/// Gets the sored tag in the metadata by its hex value.
-	/// Returns `None`if the tag is not present in the metadata struct.
-	/// 
+	/// Constructs a new `Metadata` object from the image data in the given
+	/// buffer, detecting its file type (JPEG, PNG, WebP or ICO) by its
+	/// signature.
+	/// If the file type is unknown or its reader finds no (decodable) EXIF 
+	/// data, the tail of the buffer gets searched for EXIF data appended at
+	/// the end of the file as a last resort, which e.g. is the case for some
+	/// TGA files. Such EXIF data has to start with the EXIF header (`Exif\0\0`)
+	/// and is only accepted if its IFDs can be decoded and contain tags.
+	/// - If no EXIF data can be found, a new, empty object gets returned.
+	///
 	/// # Examples
 	/// ```no_run
-	/// // Note that the tag identifier of course does not need to be written in hex format
-	/// // Hex notation only used in this example for more clarity
-	/// use little_exif::metadata::Metadata;
-	/// 
-	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
-	/// let tag = metadata.get_tag_by_hex(0x010e);
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let file_buffer = std::fs::read("image.tga").unwrap();
+	/// let metadata = Metadata::new_from_vec_autodetect(&file_buffer).unwrap();
 	/// ```
 	pub fn
-	get_tag_by_hex
+	new_from_vec_autodetect
 	(
-		&self,
-		input_tag_hex: u16
+		file_buffer: &[u8]
 	)
-	-> Option<&ExifTag>
+	-> Result<Metadata, std::io::Error>
 	{
-		for tag in &self.data
-		{
-			if tag.as_u16() == input_tag_hex
-			{
-				return Some(tag);
-			}
-		}
-		return None;
+		Self::new_from_vec_autodetect_with_options(file_buffer, &DecodeOptions::new())
 	}
 
-	/// Sets the tag in the metadata struct. If the tag is already in there it gets replaced
+	/// Constructs a new `Metadata` object from the image data in the given
+	/// buffer like `new_from_vec_autodetect`, using the given options for
+	/// decoding. If `verify_png_crc` is set, the CRCs of the PNG chunks up to
+	/// the first IDAT chunk get verified, returning an error in case of a 
+	/// mismatch.
 	///
 	/// # Examples
 	/// ```no_run
-	/// use little_exif::metadata::Metadata;
-	/// use little_exif::exif_tag::ExifTag;
-	/// 
-	/// let mut metadata = Metadata::new();
-	/// metadata.set_tag(
-	///     ExifTag::ISO(vec![1234])
-	/// );
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut options = DecodeOptions::new();
+	/// options.verify_png_crc = true;
+	///
+	/// let file_buffer = std::fs::read("image.png").unwrap();
+	/// let metadata = Metadata::new_from_vec_autodetect_with_options(&file_buffer, &options);
 	/// ```
 	pub fn
-	set_tag
+	new_from_vec_autodetect_with_options
 	(
-		&mut self,
-		input_tag: ExifTag,
+		file_buffer: &[u8],
+		options:     &DecodeOptions
 	)
+	-> Result<Metadata, std::io::Error>
 	{
-		self.data.retain(|tag| tag.as_u16() != input_tag.as_u16());
-		self.data.push(input_tag);
+		if let Some(file_type) = FileExtension::detect_from_bytes(file_buffer)
+		{
+			let exif_header       = Self::exif_header_of(&file_type);
+			let reports_png_error = Self::reports_png_verification_error(&file_type, options);
+			let raw_pre_decode_general = match file_type
+			{
+				FileExtension::JPEG 
+					=>  jpg::read_metadata_from_bytes(file_buffer),
+				FileExtension::PNG {as_zTXt_chunk: _} 
+					=>  png::read_metadata_from_bytes(file_buffer, options.verify_png_crc),
+				FileExtension::WEBP 
+					=> webp::read_metadata_from_bytes(file_buffer),
+				FileExtension::ICO 
+					=>  ico::read_metadata_from_bytes(file_buffer, options.verify_png_crc),
+			};
 
-		// Sort the tags by the IFD they will go into the file later on
-		self.data.sort_by(
-			|a, b| 
-			if a.get_group() == b.get_group() 
+			match raw_pre_decode_general
 			{
-				// Same group, but unknown should go last 
-				if a.is_unknown() == b.is_unknown()
-				{
-					std::cmp::Ordering::Equal
-				}
-				else if !a.is_unknown() && b.is_unknown()
-				{
-					std::cmp::Ordering::Less
-				}
-				else
-				{
-					std::cmp::Ordering::Greater
-				}
-				
+				Ok(pre_decode_general) => {
+					if let Ok(metadata) = Self::decode_metadata_general(&pre_decode_general, exif_header, options)
+					{
+						return Ok(metadata);
+					}
+				},
+				Err(error) if reports_png_error && error.kind() == std::io::ErrorKind::InvalidData
+					=> return Err(error),
+				Err(_) => {},
+			}
+		}
+
+		for candidate in trailer::exif_candidates(file_buffer)
+		{
+			if let Ok(metadata) = Self::decode_metadata_general(&candidate.to_vec(), &EXIF_HEADER, &options)
+			{
+				if !metadata.data.is_empty()
+				{
+					return Ok(metadata);
+				}
+			}
+		}
+
+		return Ok(Metadata::new());
+	}
+
+	/// Constructs a new `Metadata` object from the image data provided by the
+	/// given reader (e.g. a request body), detecting its file type like 
+	/// `new_from_vec_autodetect`. Only the bytes needed for detecting the file
+	/// type are read at first, followed by further bytes as required: For 
+	/// JPEG and PNG, reading stops after the EXIF data (see 
+	/// `from_header_bytes`), other file types are read until their end. As 
+	/// the read bytes get buffered, the reader does not need to be seekable.
+	/// - If no EXIF data can be found, a new, empty object gets returned.
+	/// - An error is only returned if reading from the reader fails.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_reader_autodetect(std::io::stdin()).unwrap();
+	/// ```
+	pub fn
+	new_from_reader_autodetect<R: std::io::Read>
+	(
+		reader: R
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		Self::new_from_reader_autodetect_with_options(reader, &DecodeOptions::new())
+	}
+
+	/// Constructs a new `Metadata` object from the image data provided by the
+	/// given reader like `new_from_reader_autodetect`, using the given options
+	/// for decoding. If `verify_png_crc` is set, a CRC mismatch in the PNG
+	/// chunks up to the first IDAT chunk results in an error as well.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut options = DecodeOptions::new();
+	/// options.verify_png_crc = true;
+	///
+	/// let metadata = Metadata::new_from_reader_autodetect_with_options(std::io::stdin(), &options);
+	/// ```
+	pub fn
+	new_from_reader_autodetect_with_options<R: std::io::Read>
+	(
+		mut reader: R,
+		options:    &DecodeOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		use std::io::Read;
+
+		let mut file_buffer = Vec::new();
+		(&mut reader).take(SNIFF_LENGTH as u64).read_to_end(&mut file_buffer)?;
+
+		match FileExtension::detect_from_bytes(&file_buffer)
+		{
+			Some(FileExtension::JPEG) | Some(FileExtension::PNG {as_zTXt_chunk: _}) => loop
+			{
+				// The leading bytes stay the same, so the file type does too
+				let file_type = FileExtension::detect_from_bytes(&file_buffer).unwrap();
+				let error = match Self::from_header_bytes_with_options(&file_buffer, file_type, options)
+				{
+					Ok(metadata) => return Ok(metadata),
+					Err(error)   => error,
+				};
+
+				let required_length = error.get_ref()
+					.and_then(|inner| inner.downcast_ref::<IncompleteData>())
+					.map(|incomplete| incomplete.required_length())
+					.unwrap_or(file_buffer.len());
+
+				// Read the missing bytes - if the data ends before, it is
+				// searched like a complete file
+				let missing_length = required_length.saturating_sub(file_buffer.len()) as u64;
+				if missing_length == 0 || (&mut reader).take(missing_length).read_to_end(&mut file_buffer)? == 0
+				{
+					return Self::new_from_vec_autodetect_with_options(&file_buffer, options);
+				}
+			},
+			_ => {
+				reader.read_to_end(&mut file_buffer)?;
+				return Self::new_from_vec_autodetect_with_options(&file_buffer, options);
+			},
+		}
+	}
+
+	/// Constructs a `Metadata` object for each image of the ICO or CUR file 
+	/// at the specified path that has metadata, in the order the images are 
+	/// listed in the file. Only PNG encoded images can carry metadata, while
+	/// `new_from_path` just returns the metadata of the first one.
+	/// Returns an error if the file is not an ICO or CUR file or its image 
+	/// entries can't be parsed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let all_metadata = Metadata::new_all_from_ico_path(std::path::Path::new("favicon.ico")).unwrap();
+	/// for metadata in all_metadata
+	/// {
+	///     println!("{:?}", metadata.get_software_trimmed());
+	/// }
+	/// ```
+	pub fn
+	new_all_from_ico_path
+	(
+		path: &Path
+	)
+	-> Result<Vec<Metadata>, std::io::Error>
+	{
+		let FileExtension::ICO = Self::file_type_from_path(path)?
+		else
+		{
+			return io_error!(Unsupported, "Can't read Metadata of all images - Not an ICO or CUR file!");
+		};
+
+		let file_buffer = std::fs::read(path)?;
+		return Ok(ico::read_all_metadata_from_bytes(&file_buffer)?
+			.into_iter()
+			.filter_map(|raw_exif| Self::decode_metadata_general(&raw_exif, ico::ICO_EXIF_HEADER, &DecodeOptions::new()).ok())
+			.collect()
+		);
+	}
+
+	/// Constructs a `Metadata` object for each JPEG image embedded in the PDF
+	/// document at the specified path that has metadata, in the order their
+	/// streams occur in the file. Only images stored using just the 
+	/// `DCTDecode` filter are found (see the `pdf` module for details).
+	/// Returns an error if the file can't be read or is not a PDF document.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// for metadata in Metadata::new_all_from_pdf_path(std::path::Path::new("document.pdf")).unwrap()
+	/// {
+	///     println!("{:?}", metadata.get_make_trimmed());
+	/// }
+	/// ```
+	pub fn
+	new_all_from_pdf_path
+	(
+		path: &Path
+	)
+	-> Result<Vec<Metadata>, std::io::Error>
+	{
+		return Ok(pdf::read_all_metadata(path)?
+			.into_iter()
+			.filter_map(|raw_exif| Self::decode_metadata_general(&raw_exif, jpg::JPG_EXIF_HEADER, &DecodeOptions::new()).ok())
+			.collect()
+		);
+	}
+
+	/// Decodes the raw EXIF data obtained by one of the file specific readers,
+	/// which starts with the given EXIF header (see `exif_header_of`).
+	/// If either the reading or the decoding failed, a new, empty object gets
+	/// created and returned.
+	fn
+	from_pre_decode_general
+	(
+		raw_pre_decode_general: Result<Vec<u8>, std::io::Error>,
+		exif_header:            &[u8],
+		options:                &DecodeOptions
+	)
+	-> Metadata
+	{
+		if let Ok(pre_decode_general) = raw_pre_decode_general
+		{
+			let decoding_result = Self::decode_metadata_general(&pre_decode_general, exif_header, options);
+			if let Ok(mut metadata) = decoding_result
+			{
+				if options.keep_original_raw
+				{
+					metadata.original_raw = Some(pre_decode_general);
+				}
+				return metadata;
+			}
+			else
+			{
+				eprintln!("{}", decoding_result.err().unwrap());
+			}
+		}
+		else
+		{
+			eprintln!("Error during decoding: {:?}", raw_pre_decode_general.err().unwrap());
+		}
+
+		eprintln!("WARNING: Can't read metadata from file - Create new & empty struct");
+		return Metadata::new();
+	}
+	
+	/// Gets a shared reference to the list of all tags currently stored in the object.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// for tag in metadata.data()
+	/// {
+	///     // do something with the tags	
+	/// }
+	/// ```
+	pub fn
+	data
+	(
+		&self
+	)
+	-> &Vec<ExifTag>
+	{
+		&self.data
+	}
+
+	/// Gets the endianness of the metadata
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// let tag_data = metadata.get_tag_by_hex(0x010e).unwrap().value_as_u8_vec(metadata.get_endian());
+	/// ```
+	pub fn
+	get_endian
+	(
+		&self
+	)
+	-> &Endian
+	{
+		&self.endian
+	}
+
+	/// Gets the raw EXIF data as provided by the file type specific reader,
+	/// i.e. the bytes the metadata was decoded from (starting with the EXIF 
+	/// header `Exif\0\0` for JPEG and PNG, with the TIFF header for WebP). 
+	/// Only available if the metadata was decoded with
+	/// `DecodeOptions::keep_original_raw` enabled and the decoding succeeded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let mut options = DecodeOptions::new();
+	/// options.keep_original_raw = true;
+	///
+	/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.png"), &options).unwrap();
+	/// if let Some(raw) = metadata.original_raw()
+	/// {
+	///     println!("{} bytes of EXIF data", raw.len());
+	/// }
+	/// ```
+	pub fn
+	original_raw
+	(
+		&self
+	)
+	-> Option<&[u8]>
+	{
+		self.original_raw.as_deref()
+	}
+
+	/// Gets the problems that were encountered while decoding the metadata
+	/// without preventing the decoding as a whole, e.g. IFD entries skipped
+	/// due to `DecodeOptions::skip_invalid_entries` or an IFD1 that could not
+	/// be decoded. An empty list means that all entries were decoded.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::decode_options::DecodeOptions;
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut options = DecodeOptions::new();
+	/// options.skip_invalid_entries = true;
+	///
+	/// let metadata = Metadata::new_from_path_with_options(std::path::Path::new("image.jpg"), &options).unwrap();
+	/// for warning in metadata.decode_warnings()
+	/// {
+	///     println!("{}", warning);
+	/// }
+	/// ```
+	pub fn
+	decode_warnings
+	(
+		&self
+	)
+	-> &Vec<DecodeWarning>
+	{
+		&self.decode_warnings
+	}
+
+	/// Gets the number of IFD entries (including the offset tags linking to
+	/// SubIFDs) the metadata was decoded from, regardless of whether they
+	/// could be decoded or not. Together with `decode_warnings`, this tells
+	/// how much of the source was actually decoded.
+	/// Returns 0 for metadata that was not decoded from a file.
+	pub fn
+	raw_entry_count
+	(
+		&self
+	)
+	-> usize
+	{
+		self.source_order.len()
+	}
+
+	/// Enables or disables replaying the order of the IFD entries the metadata
+	/// was decoded from when encoding it (e.g. by `write_to_file`), instead
+	/// of sorting them ascending by their hex value. Tags that were not 
+	/// decoded (e.g. set afterwards) follow after them. Disabled by default.
+	///
+	/// The formats of the decoded entries get replayed as well: Known tags 
+	/// stored using another integer format than specified (e.g. an 
+	/// `ImageWidth` as INT16U instead of INT32U) are written using that
+	/// format again, as long as their values fit into it. Unknown tags always
+	/// keep their format.
+	///
+	/// Together with the endianness (which is always kept), a read-modify-
+	/// write touching a single tag then only changes the bytes of that tag -
+	/// as long as the source places the data of the tags like little_exif
+	/// does (after the entries of each IFD, in the same order), which e.g. is
+	/// the case for files written by little_exif. Otherwise, the offsets of 
+	/// the IFDs and the data referenced by their entries may still differ.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let path = std::path::Path::new("image.jpg");
+	/// let mut metadata = Metadata::new_from_path(path).unwrap();
+	/// metadata.set_preserve_source_order(true);
+	/// metadata.set_tag(ExifTag::Artist("Jane Doe".to_string()));
+	/// metadata.write_to_file(path).unwrap();
+	/// ```
+	pub fn
+	set_preserve_source_order
+	(
+		&mut self,
+		preserve: bool
+	)
+	{
+		self.preserve_source_order = preserve;
+	}
+
+	/// Gets the IFD entries in the exact order they get written when encoding
+	/// the metadata (e.g. by `write_to_file`) as pairs of group and hex value,
+	/// including the offset tags linking to SubIFDs and the thumbnail, which
+	/// are computed during encoding. Each IFD is followed by its SubIFDs 
+	/// (e.g. IFD0 by ExifIFD, InteropIFD and GPSIFD), with IFD1 at the end.
+	/// Tags that can't be written are left out.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for (group, hex) in metadata.write_plan()
+	/// {
+	///     println!("{:?} 0x{:04x}", group, hex);
+	/// }
+	/// ```
+	pub fn
+	write_plan
+	(
+		&self
+	)
+	-> Vec<(ExifTagGroup, u16)>
+	{
+		let mut plan = Vec::new();
+		self.write_plan_of_ifd_and_subifds(ExifTagGroup::IFD0, &mut plan);
+
+		let mut ifd1_tags = Vec::new();
+		if let Some(thumbnail) = &self.thumbnail
+		{
+			ifd1_tags.push(ExifTag::ThumbnailOffset(vec![0]));
+			ifd1_tags.push(ExifTag::ThumbnailLength(vec![0]));
+			if self.needs_thumbnail_compression(thumbnail)
+			{
+				ifd1_tags.push(ExifTag::UnknownINT16U(vec![JPEG_THUMBNAIL_COMPRESSION], 0x0103, ExifTagGroup::IFD1));
+			}
+		}
+		plan.extend(self.ifd_tags(ExifTagGroup::IFD1, &ifd1_tags, &ALL_GROUPS, &|_| true)
+			.iter()
+			.map(|tag| (ExifTagGroup::IFD1, tag.as_u16()))
+		);
+
+		return plan;
+	}
+
+	/// Gets the thumbnail image stored in IFD1 (for JPEG compressed thumbnails
+	/// this is a complete JPEG file). Returns `None` if there is no thumbnail
+	/// or if it was not fully contained in the EXIF data it was decoded from 
+	/// (`ThumbnailOffset` + `ThumbnailLength` exceeding the EXIF data, which
+	/// e.g. can't be valid for JPEGs as the APP1 segment is limited to 64KB).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(thumbnail) = metadata.get_thumbnail()
+	/// {
+	///     std::fs::write("thumbnail.jpg", thumbnail).unwrap();
+	/// }
+	/// ```
+	pub fn
+	get_thumbnail
+	(
+		&self
+	)
+	-> Option<&Vec<u8>>
+	{
+		self.thumbnail.as_ref()
+	}
+
+	/// Gets all preview images embedded in the EXIF data, starting with the
+	/// thumbnail of IFD1 (see `get_thumbnail`), followed by the previews
+	/// referenced by the `JPEGInterchangeFormat` and 
+	/// `JPEGInterchangeFormatLength` tags (0x0201 and 0x0202) of the IFDs 
+	/// after IFD1 (IFD2, IFD3, ...) and of the SubIFDs linked via the
+	/// `SubIFDs` tag (0x014a) of IFD0 and these IFDs (see the `preview` 
+	/// module). For JPEG previews, the dimensions are taken from the image
+	/// itself, otherwise from the `ImageWidth` and `ImageLength` tags of the
+	/// IFD (if present).
+	/// Note that only the thumbnail gets written when encoding the metadata,
+	/// the other previews are read only.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for preview in metadata.previews()
+	/// {
+	///     println!("{:?}x{:?} {:?}", preview.width, preview.height, preview.format);
+	/// }
+	/// ```
+	pub fn
+	previews
+	(
+		&self
+	)
+	-> Vec<Preview>
+	{
+		let mut previews = Vec::new();
+		if let Some(thumbnail) = &self.thumbnail
+		{
+			let ifd1_value = |hex_value: u16| self.data.iter()
+				.find(|tag| tag.get_group() == ExifTagGroup::IFD1 && tag.as_u16() == hex_value)
+				.and_then(|tag| tag.as_u32_vec())
+				.and_then(|value| value.first().copied());
+			previews.push(Preview::new(thumbnail.clone(), ifd1_value(0x0100), ifd1_value(0x0101)));
+		}
+		previews.extend(self.previews.iter().cloned());
+		return previews;
+	}
+
+	/// Sets the thumbnail image that gets written to IFD1, e.g. a small JPEG
+	/// file. The `ThumbnailOffset` and `ThumbnailLength` tags are computed
+	/// when encoding. For JPEG thumbnails the `Compression` tag of IFD1 is 
+	/// always written as 6 (as required by the specifications for finding 
+	/// them), replacing any other value set as unknown tag of IFD1.
+	/// Note that other IFD1 tags describing the thumbnail have to be set 
+	/// separately as unknown tags of the group IFD1, as little_exif expects
+	/// these in IFD0.
+	/// Also note that for JPEGs the entire EXIF data, including the
+	/// thumbnail, has to fit into the APP1 segment of at most 64KB.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.set_thumbnail(std::fs::read("thumbnail.jpg").unwrap());
+	/// metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+	/// ```
+	pub fn
+	set_thumbnail
+	(
+		&mut self,
+		thumbnail: Vec<u8>
+	)
+	{
+		self.set_tag(ExifTag::ThumbnailLength(vec![thumbnail.len() as u32]));
+		if is_jpeg(&thumbnail)
+		{
+			self.set_tag(ExifTag::UnknownINT16U(vec![JPEG_THUMBNAIL_COMPRESSION], 0x0103, ExifTagGroup::IFD1));
+		}
+		self.thumbnail = Some(thumbnail);
+	}
+
+	/// Removes the thumbnail image and the tags referring to it, including
+	/// its `Compression` tag in IFD1.
+	pub fn
+	clear_thumbnail
+	(
+		&mut self
+	)
+	{
+		self.data.retain(|tag| !matches!(tag, 
+			ExifTag::ThumbnailOffset(_) | 
+			ExifTag::ThumbnailLength(_) | 
+			ExifTag::UnknownINT16U(_, 0x0103, ExifTagGroup::IFD1)
+		));
+		self.thumbnail = None;
+	}
+
+	/// Gets the stored tag in the metadata for the given tag. 
+	/// Returns `None` if the tag is not present in the metadata struct.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// let tag = metadata.get_tag(&ExifTag::ImageDescription(String::new()));
+	/// ```
+	pub fn
+	get_tag
+	(
+		&self,
+		input_tag: &ExifTag
+	)
+	-> Option<&ExifTag> 
+	{
+		self.get_tag_by_hex(input_tag.as_u16())
+	}
+
+	/// Gets the sored tag in the metadata by its hex value.
+	/// Returns `None`if the tag is not present in the metadata struct.
+	/// 
+	/// # Examples
+	/// ```no_run
+	/// // Note that the tag identifier of course does not need to be written in hex format
+	/// // Hex notation only used in this example for more clarity
+	/// use revolt_little_exif::metadata::Metadata;
+	/// 
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// let tag = metadata.get_tag_by_hex(0x010e);
+	/// ```
+	pub fn
+	get_tag_by_hex
+	(
+		&self,
+		input_tag_hex: u16
+	)
+	-> Option<&ExifTag>
+	{
+		for tag in &self.data
+		{
+			if tag.as_u16() == input_tag_hex
+			{
+				return Some(tag);
+			}
+		}
+		return None;
+	}
+
+	/// Gets the stored tag with the given hex value in the given group. 
+	/// Unlike `get_tag_by_hex`, this tells apart tags of different IFDs that
+	/// share their hex value, e.g. `InteroperabilityIndex` of the InteropIFD
+	/// and `GPSLatitudeRef` of the GPSIFD (both 0x0001).
+	fn
+	get_tag_by_hex_in_group
+	(
+		&self,
+		input_tag_hex: u16,
+		group:         ExifTagGroup
+	)
+	-> Option<&ExifTag>
+	{
+		return self.data.iter().find(|tag| tag.as_u16() == input_tag_hex && tag.get_group() == group);
+	}
+
+	/// Gets the first value of the tag with the given hex value in the given
+	/// group if it has unsigned integer components of at most 16 bits (see 
+	/// `ExifTag::as_u16_vec`). The group is required as the hex values are
+	/// only unique within an IFD, e.g. 0x000b is `ProcessingSoftware` in IFD0
+	/// but `GPSDOP` in the GPSIFD. Returns `None` if the tag is not present,
+	/// has another format or no components.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(iso) = metadata.get_u16(0x8827, ExifTagGroup::ExifIFD)
+	/// {
+	///     println!("ISO {}", iso);
+	/// }
+	/// ```
+	pub fn
+	get_u16
+	(
+		&self,
+		input_tag_hex: u16,
+		group:         ExifTagGroup
+	)
+	-> Option<u16>
+	{
+		return self.get_tag_by_hex_in_group(input_tag_hex, group)?.as_u16_vec()?.first().copied();
+	}
+
+	/// Gets the first value of the tag with the given hex value in the given
+	/// group like `get_u16`, but for unsigned integer components of up to 32
+	/// bits (see `ExifTag::as_u32_vec`).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let width = metadata.get_u32(0xa002, ExifTagGroup::ExifIFD);
+	/// ```
+	pub fn
+	get_u32
+	(
+		&self,
+		input_tag_hex: u16,
+		group:         ExifTagGroup
+	)
+	-> Option<u32>
+	{
+		return self.get_tag_by_hex_in_group(input_tag_hex, group)?.as_u32_vec()?.first().copied();
+	}
+
+	/// Gets the value of the `STRING` tag with the given hex value in the
+	/// given group (see `get_u16`) without the trailing NUL characters. 
+	/// Returns `None` if the tag is not present or has another format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let description = metadata.get_string(0x010e, ExifTagGroup::IFD0);
+	/// ```
+	pub fn
+	get_string
+	(
+		&self,
+		input_tag_hex: u16,
+		group:         ExifTagGroup
+	)
+	-> Option<String>
+	{
+		match self.get_tag_by_hex_in_group(input_tag_hex, group)?.components()
+		{
+			TagValue::Ascii(value) => Some(value.trim_end_matches('\0').to_string()),
+			_                      => None,
+		}
+	}
+
+	/// Gets the values of the `RATIONAL64U` tag with the given hex value in
+	/// the given group (see `get_u16`), e.g. of `ExposureTime`. Returns 
+	/// `None` if the tag is not present or has another format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(exposure_time) = metadata.get_rational(0x829a, ExifTagGroup::ExifIFD)
+	/// {
+	///     println!("{:?}", exposure_time);
+	/// }
+	/// ```
+	pub fn
+	get_rational
+	(
+		&self,
+		input_tag_hex: u16,
+		group:         ExifTagGroup
+	)
+	-> Option<Vec<uR64>>
+	{
+		match self.get_tag_by_hex_in_group(input_tag_hex, group)?.components()
+		{
+			TagValue::Rational(value) => Some(value),
+			_                         => None,
+		}
+	}
+
+	/// Gets all tags that belong to the given group, i.e. the IFD they were
+	/// decoded from resp. will be written to (e.g. `IFD1` for the tags
+	/// describing the thumbnail, like its resolution). 
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// for tag in metadata.tags_in_group(ExifTagGroup::IFD1)
+	/// {
+	///     // do something with the tags of IFD1
+	/// }
+	/// ```
+	pub fn
+	tags_in_group
+	(
+		&self,
+		group: ExifTagGroup
+	)
+	-> Vec<&ExifTag>
+	{
+		self.data.iter().filter(|tag| tag.get_group() == group).collect()
+	}
+
+	/// Retains only the tags for which the given predicate returns `true`,
+	/// keeping their order.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// metadata.retain(|tag| !tag.is_gps_tag());
+	/// ```
+	pub fn
+	retain
+	<F>
+	(
+		&mut self,
+		predicate: F
+	)
+	where F: FnMut(&ExifTag) -> bool
+	{
+		self.data.retain(predicate);
+	}
+
+	/// Gets the pixel dimensions (width, height) of the image as stored in the
+	/// metadata. The tags are checked in the following order, using the
+	/// first pair where both width and height are present:
+	/// - `ExifImageWidth`/`ExifImageHeight` (PixelXDimension/PixelYDimension,
+	///   ExifIFD)
+	/// - `ImageWidth`/`ImageHeight` (ImageWidth/ImageLength, IFD0)
+	///
+	/// Returns `None` if none of these pairs is present. Note that the image
+	/// container (e.g. the IHDR chunk of a PNG) is not taken into account, as
+	/// the metadata struct is independent of the file it was read from.
+	///
+	/// Also note that these values describe the stored image data and thus may
+	/// be swapped relative to the displayed image - callers should consult the
+	/// `Orientation` tag (values 5 to 8 indicate a rotation by 90 degrees).
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((width, height)) = metadata.get_dimensions()
+	/// {
+	///     println!("{}x{}", width, height);
+	/// }
+	/// ```
+	pub fn
+	get_dimensions
+	(
+		&self
+	)
+	-> Option<(u32, u32)>
+	{
+		let exif_width  = self.get_tag(&ExifTag::ExifImageWidth(Vec::new()));
+		let exif_height = self.get_tag(&ExifTag::ExifImageHeight(Vec::new()));
+
+		if let (
+			Some(ExifTag::ExifImageWidth(width)),
+			Some(ExifTag::ExifImageHeight(height))
+		) = (exif_width, exif_height)
+		{
+			if let (Some(w), Some(h)) = (width.first(), height.first())
+			{
+				return Some((*w, *h));
+			}
+		}
+
+		let ifd0_width  = self.get_tag(&ExifTag::ImageWidth(Vec::new()));
+		let ifd0_height = self.get_tag(&ExifTag::ImageHeight(Vec::new()));
+
+		if let (
+			Some(ExifTag::ImageWidth(width)),
+			Some(ExifTag::ImageHeight(height))
+		) = (ifd0_width, ifd0_height)
+		{
+			if let (Some(w), Some(h)) = (width.first(), height.first())
+			{
+				return Some((*w, *h));
+			}
+		}
+
+		return None;
+	}
+
+	/// Gets the value of the `Make` tag without the trailing whitespace and
+	/// NUL padding that many cameras add (e.g. `"Canon   "`).
+	/// The value stored in the tag itself is left unchanged so that it gets
+	/// written back as it was read.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(make) = metadata.get_make_trimmed()
+	/// {
+	///     println!("{}", make);
+	/// }
+	/// ```
+	pub fn
+	get_make_trimmed
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::Make(make)) = self.get_tag(&ExifTag::Make(String::new()))
+		{
+			return Some(trim_string_padding(make));
+		}
+		return None;
+	}
+
+	/// Gets the value of the `Model` tag without trailing whitespace and NUL
+	/// padding, like `get_make_trimmed`.
+	pub fn
+	get_model_trimmed
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::Model(model)) = self.get_tag(&ExifTag::Model(String::new()))
+		{
+			return Some(trim_string_padding(model));
+		}
+		return None;
+	}
+
+	/// Gets the value of the `Software` tag (naming the software that last
+	/// edited the image) without trailing whitespace and NUL padding, like
+	/// `get_make_trimmed`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(software) = metadata.get_software_trimmed()
+	/// {
+	///     println!("Last edited with {}", software);
+	/// }
+	/// ```
+	pub fn
+	get_software_trimmed
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::Software(software)) = self.get_tag(&ExifTag::Software(String::new()))
+		{
+			return Some(trim_string_padding(software));
+		}
+		return None;
+	}
+
+	/// Gets the value of the `ProcessingSoftware` tag (naming the software
+	/// that processed the image, e.g. a RAW converter) without trailing 
+	/// whitespace and NUL padding, like `get_make_trimmed`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(software) = metadata.get_processing_software_trimmed()
+	/// {
+	///     println!("Processed with {}", software);
+	/// }
+	/// ```
+	pub fn
+	get_processing_software_trimmed
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		// Not using `get_tag`, as the GPSIFD may contain a `GPSDOP` tag with the
+		// same hex value
+		return self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::ProcessingSoftware(software) => Some(trim_string_padding(software)),
+			_                                     => None,
+		});
+	}
+
+	/// Sets the `Software` tag of IFD0, naming the software that edited the
+	/// image (e.g. the tool calling little_exif), like `set_tag` does.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_software("MyTool 1.0");
+	/// assert_eq!(metadata.get_software_trimmed(), Some("MyTool 1.0"));
+	/// ```
+	pub fn
+	set_software
+	(
+		&mut self,
+		software: &str
+	)
+	{
+		self.set_tag(ExifTag::Software(software.to_string()));
+	}
+
+	/// Gets the date and time the file was last changed, stored by the 
+	/// `ModifyDate` tag (named `DateTime` by the specifications) in IFD0.
+	/// Returns `None` if the tag is missing or not set (i.e. empty or only
+	/// consisting of blanks, colons and zeros, as used for unknown dates).
+	pub fn
+	get_modify_date
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::ModifyDate(date)) = self.get_tag(&ExifTag::ModifyDate(String::new()))
+		{
+			return date_if_set(date);
+		}
+		return None;
+	}
+
+	/// Gets the date and time the image was captured, stored by the 
+	/// `DateTimeOriginal` tag in the ExifIFD. See `get_modify_date` for
+	/// when `None` is returned.
+	pub fn
+	get_date_time_original
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::DateTimeOriginal(date)) = self.get_tag(&ExifTag::DateTimeOriginal(String::new()))
+		{
+			return date_if_set(date);
+		}
+		return None;
+	}
+
+	/// Gets the date and time the image was stored as digital data (e.g. when
+	/// it was scanned), stored by the `CreateDate` tag (named 
+	/// `DateTimeDigitized` by the specifications) in the ExifIFD. See 
+	/// `get_modify_date` for when `None` is returned.
+	pub fn
+	get_create_date
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		if let Some(ExifTag::CreateDate(date)) = self.get_tag(&ExifTag::CreateDate(String::new()))
+		{
+			return date_if_set(date);
+		}
+		return None;
+	}
+
+	/// Gets the best guess for the time the image was captured, in the 
+	/// format `YYYY:MM:DD HH:MM:SS`: `DateTimeOriginal` if set, otherwise 
+	/// `CreateDate` (digitized) and finally `ModifyDate` as last resort, 
+	/// as the latter gets updated by editing software.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(capture_time) = metadata.best_capture_time()
+	/// {
+	///     println!("Captured at {}", capture_time);
+	/// }
+	/// ```
+	pub fn
+	best_capture_time
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		return self.get_date_time_original()
+			.or_else(|| self.get_create_date())
+			.or_else(|| self.get_modify_date());
+	}
+
+	/// Gets the exposure time in seconds from the `ShutterSpeedValue` tag, 
+	/// which stores it in APEX units (`Tv = log2(1/t)`). Exposures shorter
+	/// than a second are rounded to the nearest shutter speed marked on 
+	/// cameras (e.g. 1/250 for a `Tv` of 7.97) if they are within a sixth of
+	/// a stop of it, otherwise to a whole denominator. Exposures of a quarter
+	/// of a second or longer that are not close to such a shutter speed are
+	/// rounded to a tenth of a second.
+	/// Returns `None` if the tag is not set or its value is invalid.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(seconds) = metadata.get_shutter_speed_seconds()
+	/// {
+	///     println!("1/{}s", 1.0 / seconds);
+	/// }
+	/// ```
+	pub fn
+	get_shutter_speed_seconds
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		let apex: f64 = match self.get_tag(&ExifTag::ShutterSpeedValue(Vec::new()))
+		{
+			Some(ExifTag::ShutterSpeedValue(value)) if value.len() == 1 && value[0].denominator != 0
+				=> value[0].clone().into(),
+			_   => return None,
+		};
+
+		let seconds = (-apex).exp2();
+		if !seconds.is_finite() || seconds <= 0.0
+		{
+			return None;
+		}
+
+		if seconds < 1.0
+		{
+			if let Some(denominator) = round_to_standard_value(1.0 / seconds, &STANDARD_SHUTTER_SPEED_DENOMINATORS, 1.0 / 6.0)
+			{
+				return Some(1.0 / denominator);
+			}
+
+			if seconds < 0.25
+			{
+				return Some(1.0 / (1.0 / seconds).round());
+			}
+		}
+
+		// Shown in tenths of seconds by cameras (e.g. 0.8" or 1.3")
+		return Some((seconds * 10.0).round() / 10.0);
+	}
+
+	/// Gets the f-number from the `ApertureValue` tag, which stores it in
+	/// APEX units (`Av = 2 * log2(N)`). The result is rounded to the nearest
+	/// f-number marked on lenses (e.g. 2.8 for an `Av` of 2.97) if it is 
+	/// within a sixth of a stop of it, otherwise to one decimal place.
+	/// Returns `None` if the tag is not set or its value is invalid.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(f_number) = metadata.get_aperture_fnumber()
+	/// {
+	///     println!("f/{}", f_number);
+	/// }
+	/// ```
+	pub fn
+	get_aperture_fnumber
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		let apex: f64 = match self.get_tag(&ExifTag::ApertureValue(Vec::new()))
+		{
+			Some(ExifTag::ApertureValue(value)) if value.len() == 1 && value[0].denominator != 0
+				=> value[0].clone().into(),
+			_   => return None,
+		};
+
+		let f_number = (apex / 2.0).exp2();
+		if !f_number.is_finite()
+		{
+			return None;
+		}
+
+		// A stop changes the f-number by a factor of sqrt(2), so the distance
+		// in stops is twice the one of the binary logarithms
+		return Some(round_to_standard_value(f_number, &STANDARD_F_NUMBERS, 1.0 / 12.0)
+			.unwrap_or_else(|| (f_number * 10.0).round() / 10.0));
+	}
+
+	/// Gets the speed of the GPS receiver in km/h from the `GPSSpeed` tag,
+	/// converted according to the unit given by `GPSSpeedRef` (`"K"` for 
+	/// km/h, `"M"` for mph and `"N"` for knots). Without a `GPSSpeedRef` 
+	/// tag, km/h is assumed as by the specifications.
+	/// Returns `None` if there is no (valid) `GPSSpeed` tag or the unit is 
+	/// not one of the above.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(speed) = metadata.get_gps_speed_kmh()
+	/// {
+	///     println!("Moving at {:.1} km/h", speed);
+	/// }
+	/// ```
+	pub fn
+	get_gps_speed_kmh
+	(
+		&self
+	)
+	-> Option<f64>
+	{
+		let speed = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSSpeed(value) if value.len() == 1 && value[0].denominator != 0
+				=> Some(Into::<f64>::into(value[0].clone())),
+			_   => None,
+		})?;
+
+		let unit = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSSpeedRef(unit) => Some(trim_string_padding(unit)),
+			_                          => None,
+		});
+
+		match unit.unwrap_or("K")
+		{
+			"K" => Some(speed),
+			"M" => Some(speed * 1.609344),
+			"N" => Some(speed * 1.852),
+			_   => None,
+		}
+	}
+
+	/// Gets the direction of the image when it was captured in degrees from
+	/// the `GPSImgDirection` tag, along with the reference given by 
+	/// `GPSImgDirectionRef` (`"T"` for true and `"M"` for magnetic north).
+	/// Without a `GPSImgDirectionRef` tag, true north is assumed as by the
+	/// specifications.
+	/// Returns `None` if there is no (valid) `GPSImgDirection` tag or the 
+	/// reference is not one of the above.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::metadata::NorthReference;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((direction, NorthReference::Magnetic)) = metadata.get_gps_img_direction()
+	/// {
+	///     println!("Facing {:.0}° (magnetic)", direction);
+	/// }
+	/// ```
+	pub fn
+	get_gps_img_direction
+	(
+		&self
+	)
+	-> Option<(f64, NorthReference)>
+	{
+		let direction = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSImgDirection(value) => Some(value),
+			_                               => None,
+		})?;
+		let reference = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSImgDirectionRef(reference) => Some(reference.as_str()),
+			_                                      => None,
+		});
+
+		return gps_direction(direction, reference);
+	}
+
+	/// Gets the direction of the movement of the GPS receiver in degrees from
+	/// the `GPSTrack` tag, along with the reference given by `GPSTrackRef`,
+	/// like `get_gps_img_direction`.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some((track, reference)) = metadata.get_gps_track()
+	/// {
+	///     println!("Moving towards {:.0}° ({:?} north)", track, reference);
+	/// }
+	/// ```
+	pub fn
+	get_gps_track
+	(
+		&self
+	)
+	-> Option<(f64, NorthReference)>
+	{
+		let track = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSTrack(value) => Some(value),
+			_                        => None,
+		})?;
+		let reference = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::GPSTrackRef(reference) => Some(reference.as_str()),
+			_                               => None,
+		});
+
+		return gps_direction(track, reference);
+	}
+
+	/// Gets the main subject of the image described by the `SubjectArea` tag,
+	/// whose number of values tells whether it is a point (2 values: the 
+	/// coordinates), a circle (3 values: the center and diameter) or a
+	/// rectangle (4 values: the center, width and height).
+	/// Returns `None` if the tag is not set or has another number of values.
+	/// The `SubjectLocation` tag of the ExifIFD stores just the point.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::metadata::SubjectArea;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(SubjectArea::Rectangle { x, y, width, height }) = metadata.get_subject_area()
+	/// {
+	///     println!("Focused on a {}x{} area around ({}, {})", width, height, x, y);
+	/// }
+	/// ```
+	pub fn
+	get_subject_area
+	(
+		&self
+	)
+	-> Option<SubjectArea>
+	{
+		let Some(ExifTag::SubjectArea(value)) = self.get_tag(&ExifTag::SubjectArea(vec![]))
+		else
+		{
+			return None;
+		};
+
+		match value.as_slice()
+		{
+			[x, y]                => Some(SubjectArea::Point     { x: *x, y: *y }),
+			[x, y, diameter]      => Some(SubjectArea::Circle    { x: *x, y: *y, diameter: *diameter }),
+			[x, y, width, height] => Some(SubjectArea::Rectangle { x: *x, y: *y, width: *width, height: *height }),
+			_                     => None,
+		}
+	}
+
+	/// Parses the `CFAPattern` tag, which describes the color filter array
+	/// of the image sensor, into the horizontal and vertical repeat of the
+	/// pattern and the color values of its cells (in rows, e.g. 0 = red,
+	/// 1 = green, 2 = blue).
+	/// The repeat dimensions should be stored using the byte order of the
+	/// EXIF data, but some cameras don't, so the other byte order is used
+	/// if the dimensions don't fit the size of the pattern otherwise.
+	/// Returns `None` if the tag is not set or malformed.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.dng")).unwrap();
+	/// if let Some((repeat_x, repeat_y, pattern)) = metadata.get_cfa_pattern()
+	/// {
+	///     println!("{}x{} pattern: {:?}", repeat_x, repeat_y, pattern);
+	/// }
+	/// ```
+	pub fn
+	get_cfa_pattern
+	(
+		&self
+	)
+	-> Option<(u16, u16, &[u8])>
+	{
+		let data = match self.get_tag(&ExifTag::CFAPattern(Vec::new()))
+		{
+			Some(ExifTag::CFAPattern(data)) => data,
+			_                               => return None,
+		};
+
+		if data.len() < 4
+		{
+			return None;
+		}
+
+		let other_endian = match self.endian
+		{
+			Endian::Little => Endian::Big,
+			Endian::Big    => Endian::Little,
+		};
+
+		for endian in [&self.endian, &other_endian]
+		{
+			let repeat_x = from_u8_vec_macro!(u16, &data[0..2].to_vec(), endian);
+			let repeat_y = from_u8_vec_macro!(u16, &data[2..4].to_vec(), endian);
+
+			if repeat_x as usize * repeat_y as usize == data.len() - 4
+			{
+				return Some((repeat_x, repeat_y, &data[4..]));
+			}
+		}
+
+		return None;
+	}
+
+	/// Identifies the vendor specific format of the `MakerNote` tag (see
+	/// `MakerNote::from_bytes`), or `None` if there is no such tag. 
+	/// The MakerNote itself is preserved unchanged when writing.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::maker_note::MakerNote;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(MakerNote::NikonType3 { endian }) = metadata.get_maker_note_type()
+	/// {
+	///     println!("Nikon MakerNote using {:?} endian", endian);
+	/// }
+	/// ```
+	pub fn
+	get_maker_note_type
+	(
+		&self
+	)
+	-> Option<MakerNote>
+	{
+		match self.get_tag(&ExifTag::MakerNote(Vec::new()))
+		{
+			Some(ExifTag::MakerNote(data)) => Some(MakerNote::from_bytes(data)),
+			_                              => None,
+		}
+	}
+
+	/// Sets the tag in the metadata struct like `set_tag`, but first checks
+	/// that its value has the number of components required by the
+	/// specifications (e.g. exactly one `INT16U` for `Orientation`).
+	/// Returns an error and leaves the metadata unchanged otherwise.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// assert!(metadata.set_tag_checked(ExifTag::Orientation(vec![1])).is_ok());
+	/// assert!(metadata.set_tag_checked(ExifTag::Orientation(vec![1, 2])).is_err());
+	/// ```
+	pub fn
+	set_tag_checked
+	(
+		&mut self,
+		input_tag: ExifTag,
+	)
+	-> Result<(), std::io::Error>
+	{
+		if !input_tag.has_valid_number_of_components()
+		{
+			return io_error!(InvalidInput, format!(
+				"Invalid number of components for tag {:?} - expected {}!",
+				input_tag, input_tag.predefined_number_of_components().unwrap_or_default()
+			));
+		}
+
+		self.set_tag(input_tag);
+		return Ok(());
+	}
+
+	/// Sets the tag in the metadata struct. If the tag is already in there 
+	/// (i.e. a tag with the same hex value in the same IFD) it gets replaced
+	/// Note that the values of offset tags linking to SubIFDs (e.g. 
+	/// `ExifOffset`, see `ExifTag::is_offset_tag`) are computed when encoding,
+	/// so setting them does not affect the written data. These tags are also
+	/// never part of decoded metadata, as they just describe its structure.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	/// 
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(
+	///     ExifTag::ISO(vec![1234])
+	/// );
+	/// ```
+	pub fn
+	set_tag
+	(
+		&mut self,
+		input_tag: ExifTag,
+	)
+	{
+		// Only replace the tag in the same IFD, as e.g. IFD0 and IFD1 may
+		// both contain tags with the same hex value (like `XResolution`)
+		self.data.retain(|tag| tag.as_u16() != input_tag.as_u16() || tag.get_group() != input_tag.get_group());
+		self.data.push(input_tag);
+
+		// Sort the tags by the IFD they will go into the file later on and
+		// within an IFD ascending by their hex value, regardless of whether
+		// they are known or unknown, as required by the specifications
+		self.data.sort_by(
+			|a, b| a.get_group().partial_cmp(&b.get_group())                   // e.g. IFD0 < ExifIFD
+				.unwrap_or(std::cmp::Ordering::Equal)
+				.then(a.as_u16().cmp(&b.as_u16()))
+		);
+	}
+
+	/// Sets a tag given by its hex value, format and group using raw bytes,
+	/// which are interpreted using the endianness of the metadata. This 
+	/// allows writing tags unknown to little_exif (e.g. vendor specific
+	/// ones), which are stored as the matching `Unknown...` variant. 
+	/// If the hex value belongs to a known tag of that group, the known tag
+	/// is set instead, which requires the format to match.
+	/// Returns an error if the length of the bytes is not a multiple of the
+	/// size of a component of the format.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	/// use revolt_little_exif::exif_tag_format::ExifTagFormat;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_raw_tag(0xc4a5, ExifTagFormat::UNDEF, ExifTagGroup::IFD0, vec![0x50, 0x72, 0x69, 0x6e]).unwrap();
+	/// ```
+	pub fn
+	set_raw_tag
+	(
+		&mut self,
+		hex_value: u16,
+		format:    ExifTagFormat,
+		group:     ExifTagGroup,
+		bytes:     Vec<u8>
+	)
+	-> Result<(), std::io::Error>
+	{
+		let is_known = ExifTag::from_u16(hex_value).is_ok_and(|tag| tag.get_group() == group);
+
+		let tag = match is_known
+		{
+			true  => ExifTag::from_u16_with_data(hex_value, &format, &bytes, &self.endian, &group),
+			false => match bytes.len() % format.bytes_per_component() as usize
+			{
+				0 => Ok(ExifTag::unknown_from_u16_with_data(hex_value, &format, &bytes, &self.endian, &group)),
+				_ => Err(format!("Data of tag 0x{:04x} does not consist of whole {:?} components!", hex_value, format)),
+			},
+		};
+
+		match tag
+		{
+			Ok(tag)    => self.set_tag(tag),
+			Err(error) => return io_error!(InvalidInput, error),
+		}
+		return Ok(());
+	}
+
+	/// Moves the value of the tag with the given hex value in the given group
+	/// to the tag with the other hex value within the same IFD, e.g. to remap
+	/// a deprecated or aliased tag to the canonical one. The group is needed
+	/// as hex values are only unique within an IFD (see `get_u16`).
+	/// The value is kept as is, which requires its format to be the format 
+	/// of the target tag if that one is known to little_exif for the IFD
+	/// (otherwise an unknown tag of the same format is created, just like
+	/// `set_raw_tag` does). A tag already present with the target hex value 
+	/// in that IFD gets replaced.
+	/// Returns an error if there is no tag with the given hex value in the
+	/// group or the formats are incompatible, in which case the metadata is
+	/// unchanged.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// // Remap the value of an unknown STRING tag to ImageDescription
+	/// if metadata.rename_tag(0x0320, 0x010e, ExifTagGroup::IFD0).is_err()
+	/// {
+	///     println!("Could not remap tag");
+	/// }
+	/// ```
+	pub fn
+	rename_tag
+	(
+		&mut self,
+		from_hex: u16,
+		to_hex:   u16,
+		group:    ExifTagGroup
+	)
+	-> Result<(), std::io::Error>
+	{
+		let Some(tag) = self.get_tag_by_hex_in_group(from_hex, group)
+		else
+		{
+			return io_error!(NotFound, format!("Can't rename tag 0x{:04x} in {:?} - Tag not found!", from_hex, group));
+		};
+
+		if from_hex == to_hex
+		{
+			return Ok(());
+		}
+
+		let format = tag.format();
+		let value  = tag.value_as_u8_vec(&self.endian);
+
+		// Setting the target first, so that nothing changes if this fails
+		self.set_raw_tag(to_hex, format, group, value)?;
+		self.data.retain(|tag| tag.as_u16() != from_hex || tag.get_group() != group);
+		return Ok(());
+	}
+
+	/// Brings the metadata into a canonical form suitable for writing a
+	/// conformant file: Unknown tags in IFD0 or the ExifIFD that are actually
+	/// known tags of the other of the two IFDs (e.g. an `ISO` tag found in
+	/// IFD0) are moved to the IFD specified for them, converting integer
+	/// values to the format of the known tag if this is possible without loss.
+	/// If the target IFD already contains that tag, the misplaced one gets
+	/// removed. Afterwards, the tags are sorted like `set_tag` does and
+	/// `set_preserve_source_order` gets disabled.
+	/// Note that writing normalized metadata may therefore change the encoded
+	/// bytes compared to the source even if no tag was set.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let path = std::path::Path::new("image.jpg");
+	/// let mut metadata = Metadata::new_from_path(path).unwrap();
+	/// metadata.normalize();
+	/// metadata.write_to_file(path).unwrap();
+	/// ```
+	pub fn
+	normalize
+	(
+		&mut self
+	)
+	{
+		// Known tags go first so that they take precedence over misplaced
+		// unknown ones with the same hex value
+		let (known, unknown): (Vec<ExifTag>, Vec<ExifTag>) = std::mem::take(&mut self.data)
+			.into_iter()
+			.partition(|tag| !tag.is_unknown());
+
+		for tag in known
+		{
+			self.set_tag(tag);
+		}
+
+		for tag in unknown
+		{
+			let canonical_tag = match self.canonical_tag_of(&tag)
+			{
+				Some(canonical_tag) => canonical_tag,
+				None                => { self.set_tag(tag); continue; }
+			};
+
+			if !self.data.iter().any(|existing| existing.as_u16() == canonical_tag.as_u16() && existing.get_group() == canonical_tag.get_group())
+			{
+				self.set_tag(canonical_tag);
 			}
-			else
+		}
+
+		self.preserve_source_order = false;
+	}
+
+	/// Gets the known tag a misplaced unknown tag of IFD0 or the ExifIFD 
+	/// stands for, or `None` if there is no such tag or its value can't be
+	/// converted to the format of the known tag
+	fn
+	canonical_tag_of
+	(
+		&self,
+		tag: &ExifTag
+	)
+	-> Option<ExifTag>
+	{
+		const MAIN_GROUPS: [ExifTagGroup; 2] = [ExifTagGroup::IFD0, ExifTagGroup::ExifIFD];
+
+		let known_tag = ExifTag::from_u16(tag.as_u16()).ok()?;
+		let group     = known_tag.get_group();
+
+		if !tag.is_unknown()
+		|| !MAIN_GROUPS.contains(&tag.get_group())
+		|| !MAIN_GROUPS.contains(&group)
+		{
+			return None;
+		}
+
+		let format = known_tag.format();
+		let bytes  = match tag.format() == format
+		{
+			true  => tag.value_as_u8_vec(&self.endian),
+			false => integer_components_as_u8_vec(&tag.components(), &format, &self.endian)?,
+		};
+
+		return ExifTag::from_u16_with_data(tag.as_u16(), &format, &bytes, &self.endian, &group).ok();
+	}
+
+	/// Gets the approximate number of bytes used by the metadata struct, 
+	/// including the heap allocated values of its tags, the thumbnail and
+	/// other previews and the raw data kept by 
+	/// `DecodeOptions::keep_original_raw`, e.g. for deciding whether to 
+	/// cache the decoded metadata of many images.
+	/// Allocator overhead is not taken into account.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// println!("{} bytes", metadata.approximate_memory_usage());
+	/// ```
+	pub fn
+	approximate_memory_usage
+	(
+		&self
+	)
+	-> usize
+	{
+		return 0
+		+ std::mem::size_of::<Metadata>()
+		+ self.data.capacity()          * std::mem::size_of::<ExifTag>()
+		+ self.data.iter().map(|tag| tag.heap_size()).sum::<usize>()
+		+ self.thumbnail.as_ref().map_or(0, |thumbnail| thumbnail.capacity())
+		+ self.original_raw.as_ref().map_or(0, |raw| raw.capacity())
+		+ self.source_order.capacity()  * std::mem::size_of::<(ExifTagGroup, u16, u16)>()
+		+ self.decode_warnings.capacity() * std::mem::size_of::<DecodeWarning>()
+		+ self.decode_warnings.iter().map(|warning| warning.message.capacity()).sum::<usize>()
+		+ self.previews.capacity()      * std::mem::size_of::<Preview>()
+		+ self.previews.iter().map(|preview| preview.data.capacity()).sum::<usize>();
+	}
+
+	/// Computes a SHA-256 hash of the tags, which is independent of the file
+	/// type, the endianness and the layout of the encoded data. Therefore,
+	/// it can be used for checking if two images carry the same metadata.
+	///
+	/// The hashed data is constructed as follows:
+	/// - Tags whose values are computed during encoding (e.g. `ExifOffset`,
+	///   `ThumbnailOffset`, i.e. `is_writable` returns `false`) are ignored,
+	///   as well as the thumbnail itself
+	/// - For each remaining tag an entry is built, consisting of the index of
+	///   its group (as given by the order of `ExifTagGroup`, 1 byte), its hex
+	///   value (2 bytes), its format (2 bytes), its number of components (4 
+	///   bytes) and its value as encoded in the file - everything in big 
+	///   endian notation
+	/// - The entries get sorted by group and hex value (and, if these are
+	///   equal, by their bytes) and are concatenated
+	///
+	/// Requires the `content_hash` feature, which is enabled by default.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let png  = Metadata::new_from_path(std::path::Path::new("image.png")).unwrap();
+	/// let jpeg = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let same_metadata = png.content_hash() == jpeg.content_hash();
+	/// ```
+	#[cfg(feature = "content_hash")]
+	pub fn
+	content_hash
+	(
+		&self
+	)
+	-> [u8; 32]
+	{
+		let mut entries: Vec<Vec<u8>> = Vec::new();
+		for tag in &self.data
+		{
+			if !tag.is_writable()
 			{
-				if a.get_group() < b.get_group()                                // e.g. IFD0 < ExifIFD
-				{
-					std::cmp::Ordering::Less
-				}
-				else
-				{
-					std::cmp::Ordering::Greater
-				}
+				continue;
 			}
+
+			let mut entry = Vec::new();
+			entry.push(tag.get_group() as u8);
+			entry.extend(tag.as_u16().to_be_bytes().iter());
+			entry.extend(tag.format().as_u16().to_be_bytes().iter());
+			entry.extend(tag.number_of_components().to_be_bytes().iter());
+			entry.extend(tag.value_as_u8_vec(&Endian::Big).iter());
+			entries.push(entry);
+		}
+
+		// Lexicographic ordering also orders by group and hex value first
+		entries.sort();
+
+		return Sha256::digest(entries.concat()).into();
+	}
+
+	/// Compares the tags with the ones of the other metadata, listing which
+	/// tags were added, removed or changed in `other` relative to `self`.
+	/// Like for `content_hash`, tags whose values are computed during 
+	/// encoding (e.g. `ExifOffset`) are ignored, as well as the thumbnail.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let before = Metadata::new_from_path(std::path::Path::new("before.jpg")).unwrap();
+	/// let after  = Metadata::new_from_path(std::path::Path::new("after.jpg")).unwrap();
+	///
+	/// for (old, new) in before.diff(&after).changed
+	/// {
+	///     println!("0x{:04x}: {:?} -> {:?}", old.as_u16(), old, new);
+	/// }
+	/// ```
+	pub fn
+	diff<'a>
+	(
+		&'a self,
+		other: &'a Metadata
+	)
+	-> MetadataDiff<'a>
+	{
+		let find = |metadata: &'a Metadata, tag: &ExifTag| metadata.data.iter().find(
+			|candidate| candidate.as_u16() == tag.as_u16() && candidate.get_group() == tag.get_group()
 		);
+
+		let mut diff = MetadataDiff { added: Vec::new(), removed: Vec::new(), changed: Vec::new() };
+
+		for tag in self.data.iter().filter(|tag| tag.is_writable())
+		{
+			match find(other, tag)
+			{
+				Some(other_tag) if other_tag != tag => diff.changed.push((tag, other_tag)),
+				Some(_)                             => (),
+				None                                => diff.removed.push(tag),
+			}
+		}
+
+		for tag in other.data.iter().filter(|tag| tag.is_writable())
+		{
+			if find(self, tag).is_none()
+			{
+				diff.added.push(tag);
+			}
+		}
+
+		return diff;
 	}
 
 	/// Converts the metadata into a file specific vector of bytes
@@ -321,6 +2417,14 @@ Metadata
 	/// have to determine where to write this, update the file size information
 	/// and so on - check file type specific implementations or documentation
 	/// for further details
+	/// The result only depends on the tags (not the order they were set in,
+	/// as the entries of each IFD are sorted by their hex value), the 
+	/// thumbnail and the endianness, so encoding equal metadata always 
+	/// results in identical bytes (e.g. for content addressed storage). This
+	/// doesn't apply if `set_preserve_source_order` is enabled, where the 
+	/// order of the decoded entries is replayed instead.
+	/// If the thumbnail is a JPEG file, the `Compression` tag of IFD1 is
+	/// written as 6, regardless of its value in the metadata.
 	pub fn
 	as_u8_vec
 	(
@@ -331,27 +2435,335 @@ Metadata
 	{
 		let general_encoded_metadata = self.encode_metadata_general();
 
-		match for_file_type
+		return Self::wrap_for_file_type(&general_encoded_metadata, for_file_type);
+	}
+
+	/// Converts the metadata into a file specific vector of bytes like
+	/// `as_u8_vec`, but only encodes the tags of the given groups, e.g. to
+	/// leave out GPS data and the thumbnail (which is only written if IFD1 is
+	/// included) due to size constraints. 
+	/// The offset tags linking to the SubIFDs are written as needed, i.e. 
+	/// requesting only `ExifIFD` results in an IFD0 consisting of just the
+	/// `ExifOffset` tag.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let without_gps_and_thumbnail = metadata.as_u8_vec_filtered(
+	///     FileExtension::JPEG,
+	///     &[ExifTagGroup::IFD0, ExifTagGroup::ExifIFD]
+	/// );
+	/// ```
+	pub fn
+	as_u8_vec_filtered
+	(
+		&self,
+		for_file_type: FileExtension,
+		groups:        &[ExifTagGroup]
+	)
+	-> Vec<u8>
+	{
+		let general_encoded_metadata = self.encode_metadata_general_filtered(groups, &|_| true);
+
+		return Self::wrap_for_file_type(&general_encoded_metadata, for_file_type);
+	}
+
+	/// Converts the metadata into a file specific vector of bytes like
+	/// `as_u8_vec`, but only encodes the tags for which the given filter
+	/// returns true. Unlike `retain`, this leaves the metadata unchanged, 
+	/// e.g. for writing a copy without sensitive tags while keeping all of
+	/// them for other outputs. 
+	/// The offset tags linking to the SubIFDs and the thumbnail related tags
+	/// are computed while encoding and therefore not passed to the filter.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let scrubbed = metadata.as_u8_vec_with_filter(
+	///     FileExtension::JPEG,
+	///     |tag| !tag.is_potentially_sensitive()
+	/// );
+	/// ```
+	pub fn
+	as_u8_vec_with_filter
+	(
+		&self,
+		for_file_type: FileExtension,
+		filter:        impl Fn(&ExifTag) -> bool
+	)
+	-> Vec<u8>
+	{
+		let general_encoded_metadata = self.encode_metadata_general_filtered(&ALL_GROUPS, &filter);
+
+		return Self::wrap_for_file_type(&general_encoded_metadata, for_file_type);
+	}
+
+	/// Converts the metadata into a file specific vector of bytes like
+	/// `as_u8_vec`, but verifies the result first by decoding it again. The
+	/// bytes only get returned if this results in the same tags (see `diff`)
+	/// and thumbnail, which guards against bugs in the offset computations of
+	/// the encoder that would otherwise result in corrupt output.
+	/// The `Compression` tag of IFD1 is not compared if there is a thumbnail,
+	/// as it gets corrected during encoding if required.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// let app1_segment = metadata.encode_checked(FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	encode_checked
+	(
+		&self,
+		for_file_type: FileExtension
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		let general_encoded_metadata = self.encode_metadata_general();
+
+		self.validate_offsets_consistent(&general_encoded_metadata)?;
+
+		return Ok(Self::wrap_for_file_type(&general_encoded_metadata, for_file_type));
+	}
+
+	/// Checks that the given general encoding of the metadata decodes to the
+	/// same tags and thumbnail again, see `encode_checked`
+	fn
+	validate_offsets_consistent
+	(
+		&self,
+		general_encoded_metadata: &Vec<u8>
+	)
+	-> Result<(), std::io::Error>
+	{
+		let decoded = Self::decode_metadata_general(general_encoded_metadata, &[], &DecodeOptions::new())?;
+		let diff    = self.diff(&decoded);
+
+		let is_corrected_by_encoder = |tag: &&ExifTag| {
+			self.thumbnail.is_some() && tag.as_u16() == 0x0103 && tag.get_group() == ExifTagGroup::IFD1
+		};
+
+		let mismatch = diff.removed.iter()
+			.chain(diff.added.iter())
+			.chain(diff.changed.iter().map(|(tag, _)| tag))
+			.find(|tag| !is_corrected_by_encoder(tag));
+
+		if let Some(tag) = mismatch
+		{
+			return io_error!(InvalidData, format!(
+				"Verification of encoded metadata failed - Tag 0x{:04x} in {:?} does not decode to the original value!",
+				tag.as_u16(), tag.get_group()
+			));
+		}
+
+		if decoded.thumbnail != self.thumbnail
+		{
+			return io_error!(InvalidData, "Verification of encoded metadata failed - Thumbnail does not decode to the original data!");
+		}
+
+		return Ok(());
+	}
+
+	/// Encodes the metadata into a minimal JPEG skeleton without any pixel
+	/// data, see `jpg::wrap_metadata_as_minimal_jpeg`. Useful e.g. for test
+	/// fixtures or for storing metadata in a tiny JPEG container, but not a
+	/// renderable image.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+	/// std::fs::write("metadata.jpg", metadata.as_minimal_jpeg()).unwrap();
+	/// ```
+	pub fn
+	as_minimal_jpeg
+	(
+		&self
+	)
+	-> Vec<u8>
+	{
+		return jpg::wrap_metadata_as_minimal_jpeg(&self.encode_metadata_general());
+	}
+
+	/// Encodes just the directory of the IFD of the given group, using the
+	/// endianness of the metadata: the number of entries, the entries 
+	/// themselves, a link to the next IFD (set to zero) and the area with
+	/// the values that don't fit into their entries. The offsets to these
+	/// values are relative to the start of the returned bytes, so they need
+	/// to be adjusted when placing the IFD elsewhere in TIFF data.
+	/// Tags computed during encoding - the offsets to SubIFDs as well as 
+	/// the offset and length of the thumbnail - are not included.
+	/// Returns `None` if there are no tags for that group.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTagGroup;
+	///
+	/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+	/// if let Some(gps_ifd) = metadata.ifd_bytes(ExifTagGroup::GPSIFD)
+	/// {
+	///     println!("GPS IFD has {} bytes", gps_ifd.len());
+	/// }
+	/// ```
+	pub fn
+	ifd_bytes
+	(
+		&self,
+		group: ExifTagGroup
+	)
+	-> Option<Vec<u8>>
+	{
+		return self.encode_ifd(group, 0, &[0x00; 4], &Vec::new(), &[group], &|_| true)
+			.map(|(_, ifd_vec)| ifd_vec);
+	}
+
+	/// Removes the metadata from the image file at the specified path.
+	/// For JPEGs, this removes all APP1 segments - including duplicate EXIF
+	/// segments as well as non-EXIF ones like XMP. See 
+	/// `clear_duplicate_metadata` for only removing the duplicates.
+	pub fn
+	clear_metadata
+	(
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		if !path.exists()
+		{
+			return io_error!(Other, "Can't clear Metadata - File does not exist!");
+		}
+
+		let raw_file_type_str = path.extension();
+		if raw_file_type_str.is_none()
+		{
+			return io_error!(Other, "Can't get extension from given path!");
+		}
+		let file_type_str = raw_file_type_str.unwrap().to_str();
+		if file_type_str.is_none()
+		{
+			return io_error!(Other, "Can't convert file type to string!");
+		}
+
+		let raw_file_type = FileExtension::from_str(file_type_str.unwrap().to_lowercase().as_str());
+		if raw_file_type.is_err()
+		{
+			return io_error!(Unsupported, "Can't clear Metadata - Unsupported file type!");
+		}
+
+		match raw_file_type.unwrap()
+		{
+			FileExtension::JPEG 
+				=>  jpg::clear_metadata(&path),
+			FileExtension::PNG {as_zTXt_chunk: _}
+				=>  png::clear_metadata(&path),
+			FileExtension::WEBP 
+				=> webp::clear_metadata(&path),
+			FileExtension::ICO 
+				=> io_error!(Unsupported, "Can't clear Metadata - ICO files are read-only!"),
+		}
+	}
+
+	/// Removes the metadata from the given image data of the specified file
+	/// type like `clear_metadata` and returns the resulting image data,
+	/// leaving the given data untouched.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let image = std::fs::read("image.jpg").unwrap();
+	/// let stripped = Metadata::clear_metadata_from_bytes(&image, FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	clear_metadata_from_bytes
+	(
+		file_buffer: &[u8],
+		file_type:   FileExtension
+	)
+	-> Result<Vec<u8>, std::io::Error>
+	{
+		match file_type
+		{
+			FileExtension::JPEG
+				=>  jpg::clear_metadata_from_bytes(file_buffer),
+			FileExtension::PNG {as_zTXt_chunk: _}
+				=>  png::clear_metadata_from_bytes(file_buffer),
+			FileExtension::WEBP
+				=> webp::clear_metadata_from_bytes(file_buffer),
+			FileExtension::ICO
+				=> io_error!(Unsupported, "Can't clear Metadata - ICO files are read-only!"),
+		}
+	}
+
+	/// Removes the duplicate EXIF data from the JP(E)G image file at the 
+	/// specified path, e.g. for files that somehow ended up with two EXIF
+	/// APP1 segments. Only the first EXIF APP1 segment (and the segments
+	/// continuing it if the EXIF data is split up) is kept, which is the one
+	/// the metadata is read from. All other segments, including non-EXIF 
+	/// APP1 segments like XMP, are left untouched.
+	/// Returns an error for other file types than JPEG.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// Metadata::clear_duplicate_metadata(std::path::Path::new("image.jpg")).unwrap();
+	/// ```
+	pub fn
+	clear_duplicate_metadata
+	(
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		if !path.exists()
+		{
+			return io_error!(Other, "Can't clear Metadata - File does not exist!");
+		}
+
+		match Self::file_type_from_path(path)?
 		{
-			FileExtension::PNG {as_zTXt_chunk} 
-				=>  png::as_u8_vec(&general_encoded_metadata, as_zTXt_chunk),
 			FileExtension::JPEG 
-				=>  jpg::as_u8_vec(&general_encoded_metadata),
-			FileExtension::WEBP 
-				=> webp::as_u8_vec(&general_encoded_metadata),
+				=> jpg::clear_duplicate_metadata(path),
+			_ 
+				=> io_error!(Unsupported, "Can't clear duplicate Metadata - Only supported for JPEGs!"),
 		}
 	}
 
+	/// Writes the metadata to the specified file.
+	/// For JPEGs, all APP1 segments are removed first (i.e. including 
+	/// duplicate EXIF segments and non-EXIF ones like XMP), followed by
+	/// inserting the new EXIF data directly after the signature.
+	/// This could return an error for multiple reasons:
+	/// - The file does not exist at the given path
+	/// - Interpreting the given path fails
+	/// - The file type is not supported
 	pub fn
-	clear_metadata
+	write_to_file
 	(
+		&self,
 		path: &Path
 	)
 	-> Result<(), std::io::Error>
 	{
 		if !path.exists()
 		{
-			return io_error!(Other, "Can't clear Metadata - File does not exist!");
+			return io_error!(Other, "Can't write Metadata - File does not exist!");
 		}
 
 		let raw_file_type_str = path.extension();
@@ -368,30 +2780,43 @@ Metadata
 		let raw_file_type = FileExtension::from_str(file_type_str.unwrap().to_lowercase().as_str());
 		if raw_file_type.is_err()
 		{
-			return io_error!(Unsupported, "Can't clear Metadata - Unsupported file type!");
+			return io_error!(Unsupported, "Can't read Metadata - Unsupported file type!");
 		}
 
 		match raw_file_type.unwrap()
 		{
 			FileExtension::JPEG 
-				=>  jpg::clear_metadata(&path),
+				=>  jpg::write_metadata(&path, &self.encode_metadata_general()),
 			FileExtension::PNG {as_zTXt_chunk: _}
-				=>  png::clear_metadata(&path),
+				=>  png::write_metadata(&path, &self.encode_metadata_general()),
 			FileExtension::WEBP 
-				=> webp::clear_metadata(&path),
+				=> webp::write_metadata(&path, &self.encode_metadata_general()),
+			FileExtension::ICO 
+				=> io_error!(Unsupported, "Can't write Metadata - ICO files are read-only!"),
 		}
 	}
 
-	/// Writes the metadata to the specified file.
-	/// This could return an error for multiple reasons:
-	/// - The file does not exist at the given path
-	/// - Interpreting the given path fails
-	/// - The file type is not supported
+	/// Writes the metadata to the specified file like `write_to_file`, but 
+	/// tolerates up to the given number of bytes preceding the signature of
+	/// a JPEG file (e.g. a UTF-8 BOM prepended by a broken pipeline, see
+	/// `DecodeOptions::jpeg_leading_bytes_limit`). These bytes are preserved
+	/// in front of the JPEG data. Other file types are written as usual.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+	/// metadata.write_to_file_lenient(std::path::Path::new("image.jpg"), 16).unwrap();
+	/// ```
 	pub fn
-	write_to_file
+	write_to_file_lenient
 	(
 		&self,
-		path: &Path
+		path:                     &Path,
+		jpeg_leading_bytes_limit: usize
 	)
 	-> Result<(), std::io::Error>
 	{
@@ -400,120 +2825,628 @@ Metadata
 			return io_error!(Other, "Can't write Metadata - File does not exist!");
 		}
 
-		let raw_file_type_str = path.extension();
-		if raw_file_type_str.is_none()
+		match Self::file_type_from_path(path)?
 		{
-			return io_error!(Other, "Can't get extension from given path!");
+			FileExtension::JPEG
+				=> jpg::write_metadata_lenient(path, &self.encode_metadata_general(), jpeg_leading_bytes_limit),
+			_
+				=> self.write_to_file(path),
 		}
-		let file_type_str = raw_file_type_str.unwrap().to_str();
-		if file_type_str.is_none()
+	}
+
+	/// Writes the metadata to the specified file like `write_to_file`, after
+	/// setting the `Software` tag to the given name (see `set_software`) to 
+	/// mark that the file was edited by that software. The tag remains set
+	/// afterwards, so the metadata reflects what was written.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	///
+	/// let path = std::path::Path::new("image.jpg");
+	/// let mut metadata = Metadata::new_from_path(path).unwrap();
+	/// metadata.write_to_file_stamped(path, "MyTool 1.0").unwrap();
+	/// ```
+	pub fn
+	write_to_file_stamped
+	(
+		&mut self,
+		path:          &Path,
+		software_name: &str
+	)
+	-> Result<(), std::io::Error>
+	{
+		self.set_software(software_name);
+		return self.write_to_file(path);
+	}
+
+	/// Writes the metadata together with the given XMP properties (e.g. the
+	/// rating and keywords, which have no EXIF counterpart) to the specified
+	/// file, which only gets written once. The EXIF data is written like by
+	/// `write_to_file`, while the XMP packet replaces the previous one (see
+	/// `Xmp::write_to_file`). Currently, this is only supported for WebP.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::xmp::Xmp;
+	///
+	/// let path = std::path::Path::new("image.webp");
+	/// let mut xmp = Xmp::new_from_path(path).unwrap();
+	/// xmp.add_keyword("Vacation");
+	///
+	/// Metadata::new_from_path(path).unwrap().write_to_file_with_xmp(path, &xmp).unwrap();
+	/// ```
+	pub fn
+	write_to_file_with_xmp
+	(
+		&self,
+		path: &Path,
+		xmp:  &Xmp
+	)
+	-> Result<(), std::io::Error>
+	{
+		let FileExtension::WEBP = Self::file_type_from_path(path)?
+		else
 		{
-			return io_error!(Other, "Can't convert file type to string!");
+			return io_error!(Unsupported, "Can't write Metadata with XMP - Only supported for WebP files!");
+		};
+
+		let file_buffer = std::fs::read(path)?;
+		let new_file_buffer = webp::write_metadata_to_bytes(&file_buffer, &self.encode_metadata_general())?;
+		let new_file_buffer = webp::write_xmp_to_bytes(&new_file_buffer, &xmp.as_u8_vec())?;
+		perform_file_action!(std::fs::write(path, new_file_buffer));
+		return Ok(());
+	}
+
+	/// Writes the metadata to the given image data of the specified file type
+	/// (e.g. read from stdin) like `write_to_file` and writes the resulting
+	/// image to the given output (e.g. stdout) instead of a file.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use std::io::Read;
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	/// use revolt_little_exif::filetype::FileExtension;
+	///
+	/// let mut input = Vec::new();
+	/// std::io::stdin().read_to_end(&mut input).unwrap();
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+	/// metadata.write_to_writer(&input, std::io::stdout(), FileExtension::JPEG).unwrap();
+	/// ```
+	pub fn
+	write_to_writer<W: std::io::Write>
+	(
+		&self,
+		input:     &[u8],
+		mut output: W,
+		file_type: FileExtension
+	)
+	-> Result<(), std::io::Error>
+	{
+		let general_encoded_metadata = self.encode_metadata_general();
+
+		let new_file_buffer = match file_type
+		{
+			FileExtension::JPEG
+				=>  jpg::write_metadata_to_bytes(input, &general_encoded_metadata),
+			FileExtension::PNG {as_zTXt_chunk: _}
+				=>  png::write_metadata_to_bytes(input, &general_encoded_metadata),
+			FileExtension::WEBP
+				=> webp::write_metadata_to_bytes(input, &general_encoded_metadata),
+			FileExtension::ICO
+				=> io_error!(Unsupported, "Can't write Metadata - ICO files are read-only!"),
+		}?;
+
+		output.write_all(&new_file_buffer)?;
+		return output.flush();
+	}
+
+	/// Computes by how many bytes the specified file would grow (or shrink,
+	/// if negative) when writing the metadata to it with `write_to_file`, 
+	/// i.e. the size of the new EXIF data including its container (e.g. the
+	/// APP1 segments for JPEG) minus the size of the metadata removed when
+	/// writing. The file itself is not modified.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let path = std::path::Path::new("image.jpg");
+	/// let mut metadata = Metadata::new_from_path(path).unwrap();
+	/// metadata.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+	/// println!("The file grows by {} bytes", metadata.size_delta(path).unwrap());
+	/// ```
+	pub fn
+	size_delta
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<i64, std::io::Error>
+	{
+		let file_type   = Self::file_type_from_path(path)?;
+		let file_buffer = std::fs::read(path)?;
+
+		let mut new_file_buffer = Vec::new();
+		self.write_to_writer(&file_buffer, &mut new_file_buffer, file_type)?;
+
+		return Ok(new_file_buffer.len() as i64 - file_buffer.len() as i64);
+	}
+
+	/// Writes the metadata to the specified file like `write_to_file`, but
+	/// restores the access and modification times the file had before writing.
+	///
+	/// Platform limitations: The times are set via `std::fs::File::set_times`,
+	/// so their precision depends on the file system (e.g. FAT only stores the
+	/// modification time in 2 second steps and no access time). The creation
+	/// resp. change time (ctime) can't be restored on any platform. Also, on
+	/// file systems mounted with `noatime` the access time may not be updated.
+	/// If restoring the times fails, the metadata is written nevertheless and
+	/// an error is returned.
+	pub fn
+	write_to_file_preserving_times
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		if !path.exists()
+		{
+			return io_error!(Other, "Can't write Metadata - File does not exist!");
 		}
 
-		let raw_file_type = FileExtension::from_str(file_type_str.unwrap().to_lowercase().as_str());
-		if raw_file_type.is_err()
+		let file_metadata = std::fs::metadata(path)?;
+		let times = std::fs::FileTimes::new()
+			.set_accessed(file_metadata.accessed()?)
+			.set_modified(file_metadata.modified()?);
+
+		self.write_to_file(path)?;
+
+		let file = std::fs::OpenOptions::new()
+			.write(true)
+			.open(path)?;
+		return file.set_times(times);
+	}
+
+	/// Writes the metadata to the specified file like `write_to_file`, but
+	/// only if the metadata currently stored in the file differs (as given
+	/// by `diff`, additionally taking the thumbnail into account).
+	/// Returns whether the file was written, so that files (and their times)
+	/// are not touched if they already carry the correct metadata.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(ExifTag::Artist("Jane Doe".to_string()));
+	///
+	/// if metadata.write_if_changed(std::path::Path::new("image.jpg")).unwrap()
+	/// {
+	///     println!("Updated image.jpg");
+	/// }
+	/// ```
+	pub fn
+	write_if_changed
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<bool, std::io::Error>
+	{
+		let current = Self::new_from_path(path)?;
+
+		if self.diff(&current).is_empty() && self.thumbnail == current.thumbnail
 		{
-			return io_error!(Unsupported, "Can't read Metadata - Unsupported file type!");
+			return Ok(false);
 		}
 
-		match raw_file_type.unwrap()
+		self.write_to_file(path)?;
+		return Ok(true);
+	}
+
+	/// Writes the metadata as standalone EXIF file (e.g. `image.exif`), i.e.
+	/// the raw TIFF block starting with the byte order marker, without any
+	/// container specific wrapping or the `Exif\0\0` header. Such files can
+	/// be inspected with other tools such as exiftool or used as sidecar.
+	/// In contrast to `write_to_file` a new file is created if none exists
+	/// at the given path, an existing one gets overwritten.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use revolt_little_exif::metadata::Metadata;
+	/// use revolt_little_exif::exif_tag::ExifTag;
+	///
+	/// let mut metadata = Metadata::new();
+	/// metadata.set_tag(
+	///     ExifTag::ImageDescription("Hello World!".to_string())
+	/// );
+	/// metadata.write_exif_blob(std::path::Path::new("image.exif")).unwrap();
+	/// ```
+	pub fn
+	write_exif_blob
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		return std::fs::write(path, self.encode_metadata_general());
+	}
+
+	/// Wraps the general encoding of the metadata (i.e. the TIFF data) into
+	/// the file specific format, e.g. an APP1 segment for JPEG
+	fn
+	wrap_for_file_type
+	(
+		general_encoded_metadata: &Vec<u8>,
+		for_file_type:            FileExtension
+	)
+	-> Vec<u8>
+	{
+		match for_file_type
 		{
+			FileExtension::PNG {as_zTXt_chunk} 
+				=>  png::as_u8_vec(general_encoded_metadata, as_zTXt_chunk),
 			FileExtension::JPEG 
-				=>  jpg::write_metadata(&path, &self.encode_metadata_general()),
-			FileExtension::PNG {as_zTXt_chunk: _}
-				=>  png::write_metadata(&path, &self.encode_metadata_general()),
+				=>  jpg::as_u8_vec(general_encoded_metadata),
 			FileExtension::WEBP 
-				=> webp::write_metadata(&path, &self.encode_metadata_general()),
+				=> webp::as_u8_vec(general_encoded_metadata),
+			FileExtension::ICO                                                  // The EXIF data is stored in the PNG encoded images
+				=>  png::as_u8_vec(general_encoded_metadata, true),
 		}
 	}
 
+	/// Gets the header preceding the TIFF data in the raw EXIF data provided
+	/// by the reader of the given file type: `Exif\0\0` for JPEG (as part of
+	/// the APP1 segment) and PNG (as part of the zTXt chunk), nothing for 
+	/// WebP, whose EXIF chunk directly starts with the TIFF header.
 	fn
-	decode_metadata_general
+	exif_header_of
 	(
-		encoded_data: &Vec<u8>
+		file_type: &FileExtension
 	)
-	-> Result<(Endian, Vec<ExifTag>), std::io::Error>
+	-> &'static [u8]
 	{
+		match file_type
+		{
+			FileExtension::JPEG                   => jpg::JPG_EXIF_HEADER,
+			FileExtension::PNG {as_zTXt_chunk: _} => png::PNG_EXIF_HEADER,
+			FileExtension::WEBP                   => webp::WEBP_EXIF_HEADER,
+			FileExtension::ICO                    =>  ico::ICO_EXIF_HEADER,
+		}
+	}
 
-		// Ensure that we have enough data
-		if encoded_data.len() < (EXIF_HEADER.len() + Endian::Big.header().len() + 2 + IFD_END.len())
+	/// Ensures that the data is long enough for the given EXIF header (which
+	/// may be empty), the TIFF header and an empty IFD0
+	fn
+	check_data_length
+	(
+		encoded_data: &[u8],
+		exif_header:  &[u8]
+	)
+	-> Result<(), std::io::Error>
+	{
+		if encoded_data.len() < (exif_header.len() + Endian::Big.header().len() + 2 + IFD_END.len())
 		{
-			return io_error!(Other, "Not enough data for encoding!");
+			return io_error!(Other, "Not enough data for decoding!");
 		}
+		return Ok(());
+	}
+
+	/// Validates the given EXIF header (which may be empty) at the start of
+	/// the data and determines the endianness. Returns the latter together
+	/// with the TIFF data, i.e. the data after the EXIF header that the 
+	/// offsets within it are relative to.
+	fn
+	decode_tiff_header
+	(
+		encoded_data: &Vec<u8>,
+		exif_header:  &[u8]
+	)
+	-> Result<(Endian, Vec<u8>), std::io::Error>
+	{
+		Self::check_data_length(encoded_data, exif_header)?;
 
 		// Validate EXIF header
-		for i in 0..EXIF_HEADER.len()
+		if !encoded_data.starts_with(exif_header)
 		{
-			if encoded_data[i] != EXIF_HEADER[i]
-			{
-				return io_error!(Other, "Could not validate EXIF header!");
-			}
+			return io_error!(Other, "Could not validate EXIF header!");
 		}
 
 		// Determine endian
+		let tiff_data = &encoded_data[exif_header.len()..];
 		let endian;
-		if encoded_data[6] == 0x49 && encoded_data[7] == 0x49                   // "II"
+		if tiff_data[0] == 0x49 && tiff_data[1] == 0x49                         // "II"
 		{
 			endian = Endian::Little;
 		}
-		else if encoded_data[6] == 0x4d && encoded_data[7] == 0x4d              // "MM"
+		else if tiff_data[0] == 0x4d && tiff_data[1] == 0x4d                    // "MM"
 		{
 			endian = Endian::Big;
 		}
 		else
 		{
-			return io_error!(Other, "Illegal endian information!");
+			return io_error!(Other, "Illegal endian information!");
+		}
+
+		// The offsets used within the EXIF data are relative to the start of
+		// the TIFF header, i.e. the byte order information
+		return Ok((endian, tiff_data.to_vec()));
+	}
+
+	/// Gets the offset of IFD0 given by the TIFF header of the TIFF data.
+	/// Usually, IFD0 directly follows the header at offset 8, but some files
+	/// place other data (e.g. a preview) in between. Offsets beyond the end
+	/// of the data are detected when decoding the IFD.
+	fn
+	ifd0_offset_of
+	(
+		tiff_data: &[u8],
+		endian:    &Endian
+	)
+	-> Result<u32, std::io::Error>
+	{
+		let Some(offset_bytes) = tiff_data.get(4..8)
+		else
+		{
+			return io_error!(Other, "Not enough data for decoding the TIFF header!");
+		};
+
+		let ifd0_offset = from_u8_vec_macro!(u32, &offset_bytes.to_vec(), endian);
+		if ifd0_offset < Endian::Big.header().len() as u32
+		{
+			return io_error!(InvalidData, format!("IFD0 offset {} points into the TIFF header!", ifd0_offset));
+		}
+
+		return Ok(ifd0_offset);
+	}
+
+	fn
+	decode_metadata_general
+	(
+		encoded_data: &Vec<u8>,
+		exif_header:  &[u8],
+		options:      &DecodeOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		let (endian, tiff_data) = Self::decode_tiff_header(encoded_data, exif_header)?;
+		return Self::decode_tiff_data(&tiff_data, endian, options);
+	}
+
+	/// Decodes the IFDs of the given TIFF data (starting with the byte order
+	/// information) using the given endianness, see `decode_tiff_header`
+	fn
+	decode_tiff_data
+	(
+		tiff_data: &Vec<u8>,
+		endian:    Endian,
+		options:   &DecodeOptions
+	)
+	-> Result<Metadata, std::io::Error>
+	{
+		// Decode all the tags
+		let mut all_tags = Vec::new();
+		let mut context  = DecodeContext {
+			source_order: Vec::new(),
+			warnings:     Vec::new(),
+			ancestors:    Vec::new(),
+			options
+		};
+
+		// Start with IFD0, which usually directly follows the TIFF header
+		let ifd0_offset = Self::ifd0_offset_of(tiff_data, &endian)?;
+		let ifd0_result = Self::decode_ifd(
+			tiff_data,
+			&ExifTagGroup::IFD0,
+			ifd0_offset,
+			&endian,
+			&mut context
+		);
+
+		let next_ifd_offset;
+		if let Ok((ifd0_and_subifd_tags, ifd1_offset)) = ifd0_result
+		{
+			all_tags.extend(ifd0_and_subifd_tags);
+			next_ifd_offset = ifd1_offset;
+		}
+		else
+		{
+			return io_error!(Other, "Could not get IFD0 tags!");
+		}
+
+		// Continue with IFD1 (if IFD0 links to it), containing the thumbnail
+		let mut thumbnail   = None;
+		let mut ifd2_offset = 0;
+		if next_ifd_offset != 0
+		{
+			// IFD0 counts as ancestor for detecting IFD1 pointing back to it
+			context.ancestors.push(ifd0_offset);
+			let ifd1_result = Self::decode_ifd(
+				tiff_data,
+				&ExifTagGroup::IFD1,
+				next_ifd_offset,
+				&endian,
+				&mut context
+			);
+			context.ancestors.pop();
+
+			if let Ok((ifd1_tags, ifd1_link)) = ifd1_result
+			{
+				thumbnail   = Self::decode_thumbnail(tiff_data, &ifd1_tags, &mut context.warnings);
+				ifd2_offset = ifd1_link;
+				all_tags.extend(ifd1_tags);
+			}
+			else
+			{
+				let error = ifd1_result.err().unwrap();
+				context.warnings.push(DecodeWarning { 
+					group:   ExifTagGroup::IFD1, 
+					offset:  0, 
+					hex_tag: None, 
+					message: error.to_string() 
+				});
+			}
+		}
+
+		// Further previews can only be found while the TIFF data is available
+		let previews = preview::decode_previews(tiff_data, ifd0_offset, ifd2_offset, &endian, options.max_ifd_depth);
+
+		return Ok(Metadata { 
+			endian, 
+			data:                  all_tags, 
+			thumbnail, 
+			original_raw:          None, 
+			source_order:          context.source_order, 
+			preserve_source_order: false,
+			decode_warnings:       context.warnings,
+			previews,
+		});
+	}
+
+	/// Gets the thumbnail data referenced by the `ThumbnailOffset` and
+	/// `ThumbnailLength` tags of IFD1 (if present) from the TIFF data.
+	/// If the thumbnail reaches beyond the end of the TIFF data (which is the 
+	/// case for some files, e.g. violating the 64KB limit of the APP1 segment
+	/// in JPEGs), it gets ignored and reported via `warnings`.
+	fn
+	decode_thumbnail
+	(
+		tiff_data: &Vec<u8>,
+		ifd1_tags: &Vec<ExifTag>,
+		warnings:  &mut Vec<DecodeWarning>
+	)
+	-> Option<Vec<u8>>
+	{
+		let mut thumbnail_offset = None;
+		let mut thumbnail_length = None;
+		for tag in ifd1_tags
+		{
+			match tag
+			{
+				ExifTag::ThumbnailOffset(value) => thumbnail_offset = value.first().copied(),
+				ExifTag::ThumbnailLength(value) => thumbnail_length = value.first().copied(),
+				_ => (),
+			}
 		}
 
-		// Decode all the tags
-		let mut all_tags = Vec::new();
+		let thumbnail_start = thumbnail_offset? as usize;
+		let thumbnail_end   = thumbnail_start.checked_add(thumbnail_length? as usize);
 
-		// Start with IFD0
-		if let Ok(ifd0_and_subifd_tags) = Self::decode_ifd(
-			&encoded_data[14..].to_vec(),
-			&ExifTagGroup::IFD0,
-			8,                                                                  // TODO: What if IFD0 is at another offset? Can this even happen?
-			&endian
-		)
-		{
-			all_tags.extend(ifd0_and_subifd_tags);
-		}
-		else
+		// An overflow of the end is out of bounds as well
+		match thumbnail_end
 		{
-			return io_error!(Other, "Could not get IFD0 tags!");
+			Some(thumbnail_end) if thumbnail_end <= tiff_data.len()
+				=> return Some(tiff_data[thumbnail_start..thumbnail_end].to_vec()),
+			_
+				=> {
+					warnings.push(DecodeWarning {
+						group:   ExifTagGroup::IFD1,
+						offset:  0,
+						hex_tag: Some(ExifTag::ThumbnailOffset(Vec::new()).as_u16()),
+						message: format!(
+							"Thumbnail (offset {}, length {}) exceeds the EXIF data of {} bytes - Ignoring thumbnail",
+							thumbnail_start,
+							thumbnail_length?,
+							tiff_data.len()
+						),
+					});
+					return None;
+				},
 		}
-
-		return Ok((endian, all_tags));
 	}
 	
-
+	/// Decodes the IFD of the given group that starts at the given offset 
+	/// within the TIFF data, including all of its SubIFDs.
+	/// Returns the decoded tags and the offset of the next IFD (or 0 if there
+	/// is no next IFD). The group and hex value of each entry (including the
+	/// offset tags) get appended to the source order in the order they occur.
+	/// The offsets of the IFDs linking to this one are given by the 
+	/// ancestors of the context, which allows for detecting cycles (e.g. a 
+	/// SubIFD offset pointing back to IFD0) and limiting the depth of the 
+	/// recursion to the maximum depth given by the options. Invalid entries
+	/// either fail the decoding or get skipped and reported via the warnings
+	/// of the context, depending on the options.
 	fn
 	decode_ifd
 	(
-		encoded_data: &Vec<u8>,
+		tiff_data: &Vec<u8>,
 		group: &ExifTagGroup,
 		given_offset: u32,
-		endian: &Endian
+		endian: &Endian,
+		context: &mut DecodeContext
 	)
-	-> Result<Vec<ExifTag>, std::io::Error>
+	-> Result<(Vec<ExifTag>, u32), std::io::Error>
 	{
+		let options = context.options;
+
+		if context.ancestors.contains(&given_offset)
+		{
+			return io_error!(InvalidData, format!("IFD at offset {} links to itself!", given_offset));
+		}
+
+		if context.ancestors.len() >= options.max_ifd_depth
+		{
+			return io_error!(InvalidData, format!("IFD at offset {} exceeds the maximum depth of {}!", given_offset, options.max_ifd_depth));
+		}
+
+		let ifd_start = given_offset as usize;
+
 		// The first two bytes give us the number of entries in this IFD
-		let number_of_entries = from_u8_vec_macro!(u16, &encoded_data[0..2].to_vec(), endian);
+		if tiff_data.len() < ifd_start + 2
+		{
+			return io_error!(Other, "IFD starts beyond the end of the EXIF data!");
+		}
+		let number_of_entries = from_u8_vec_macro!(u16, &tiff_data[ifd_start..(ifd_start+2)].to_vec(), endian);
 
-		// Assert that we have enough data to unpack
-		assert!(2 + IFD_ENTRY_LENGTH as usize * number_of_entries as usize + IFD_END.len() <= encoded_data.len());
+		// Ensure that we have enough data to unpack
+		let ifd_end = ifd_start + 2 + IFD_ENTRY_LENGTH as usize * number_of_entries as usize;
+		if tiff_data.len() < ifd_end + IFD_END.len()
+		{
+			return io_error!(Other, "IFD exceeds the end of the EXIF data!");
+		}
 
 		let mut tags: Vec<ExifTag> = Vec::new();
 		for i in 0..number_of_entries
 		{
 			// index within the given data where the current entry starts
-			let ifd_start_index = (2 + (i as u32)*IFD_ENTRY_LENGTH) as usize;
+			let ifd_entry_index = ifd_start + 2 + i as usize * IFD_ENTRY_LENGTH as usize;
 
 			// Decode the first 8 bytes with the tag, format and component number
-			let hex_tag = from_u8_vec_macro!(u16, &encoded_data[(ifd_start_index)..(ifd_start_index+2)].to_vec(), endian);
-			let hex_format = from_u8_vec_macro!(u16, &encoded_data[(ifd_start_index+2)..(ifd_start_index+4)].to_vec(), endian);
-			let hex_component_number = from_u8_vec_macro!(u32, &encoded_data[(ifd_start_index+4)..(ifd_start_index+8)].to_vec(), endian);
+			let hex_tag = from_u8_vec_macro!(u16, &tiff_data[(ifd_entry_index)..(ifd_entry_index+2)].to_vec(), endian);
+			let hex_format = from_u8_vec_macro!(u16, &tiff_data[(ifd_entry_index+2)..(ifd_entry_index+4)].to_vec(), endian);
+			let hex_component_number = from_u8_vec_macro!(u32, &tiff_data[(ifd_entry_index+4)..(ifd_entry_index+8)].to_vec(), endian);
+
+			context.source_order.push((*group, hex_tag, hex_format));
+
+			// Either fails the decoding of the IFD or skips the current entry
+			// with a warning, depending on the options
+			macro_rules! invalid_entry {
+				($kind:ident, $message:expr) => {
+					{
+						let message: String = $message.into();
+						if !options.skip_invalid_entries
+						{
+							return io_error!($kind, message);
+						}
+						context.warnings.push(DecodeWarning {
+							group:   *group,
+							offset:  (ifd_entry_index - ifd_start) as u32,
+							hex_tag: Some(hex_tag),
+							message
+						});
+						continue;
+					}
+				};
+			}
 
 			// Decoding the format
 			let format;
@@ -523,69 +3456,187 @@ Metadata
 			}
 			else
 			{
-				return io_error!(Other, "Illegal format value!");
+				invalid_entry!(Other, "Illegal format value!");
 			}
 
-			// Check if the tag is known and compatible with the given format
-			// Return error if incompatible
-			// Use one of the unkown tags if unknown
-			if let Ok(tag) = ExifTag::from_u16(hex_tag)
-			{
-				if tag.format().as_u16() != format.as_u16()
-				{
-					return io_error!(Other, "Illegal format for known tag!");
-				}
-			}
+			// Check if the tag is known for this IFD and compatible with the
+			// given format. Return error if incompatible
+			// Use one of the unkown tags if unknown or located in another IFD
+			// (e.g. GPS tags share their hex values with tags of IFD0)
+			let known_tag = ExifTag::from_u16(hex_tag).ok().filter(
+				|tag| tag.get_group() == *group || tag.get_group() == ExifTagGroup::NO_GROUP
+			);
+
 
 			// Calculating the number of required bytes to determine if next
 			// 4 bytes are data or an offset to data
-			let byte_count = format.bytes_per_component() * hex_component_number;
+			let byte_count = format.bytes_per_component() as usize * hex_component_number as usize;
 
 			let raw_data;
 			if byte_count > 4
 			{
 				// Compute the offset
-				let hex_offset = from_u8_vec_macro!(u32, &encoded_data[(ifd_start_index+8)..(ifd_start_index+12)].to_vec(), endian) - given_offset;
-				raw_data = encoded_data[(hex_offset as usize)..((hex_offset+byte_count) as usize)].to_vec();
+				let data_start = from_u8_vec_macro!(u32, &tiff_data[(ifd_entry_index+8)..(ifd_entry_index+12)].to_vec(), endian) as usize;
+				if tiff_data.len() < data_start + byte_count
+				{
+					invalid_entry!(Other, format!("Data of tag 0x{:04x} exceeds the end of the EXIF data!", hex_tag));
+				}
+				raw_data = tiff_data[data_start..(data_start+byte_count)].to_vec();
 			}
 			else
 			{
-				// The 4 bytes are the actual data
-				raw_data = encoded_data[(ifd_start_index+8)..(ifd_start_index+12)].to_vec();
+				// The first byte_count of the 4 bytes are the actual data
+				raw_data = tiff_data[(ifd_entry_index+8)..(ifd_entry_index+8+byte_count)].to_vec();
 			}
 
+			// A known tag stored using another integer format than specified
+			// (e.g. an `ImageWidth` as INT16U instead of INT32U) gets converted
+			// to the specified one, if this is possible without loss
+			let raw_data = match &known_tag
+			{
+				Some(tag) if tag.format().as_u16() != format.as_u16() => {
+					let source_tag = ExifTag::unknown_from_u16_with_data(hex_tag, &format, &raw_data, endian, group);
+					match integer_components_as_u8_vec(&source_tag.components(), &tag.format(), endian)
+					{
+						Some(converted_data) => converted_data,
+						None                 => invalid_entry!(Other, "Illegal format for known tag!"),
+					}
+				},
+				_ => raw_data,
+			};
+
 			// If this is known tag...
-			if let Ok(tag) = ExifTag::from_u16(hex_tag)
+			if let Some(tag) = &known_tag
 			{
 				// ...for a SubIFD...
 				if let Some(subifd_group) = tag.is_offset_tag()
 				{
 					// ...perform a recursive call
+					if raw_data.len() != 4
+					{
+						invalid_entry!(Other, format!("Offset tag 0x{:04x} does not consist of a single value!", hex_tag));
+					}
 					let offset = from_u8_vec_macro!(u32, &raw_data, endian);
-					let relative_offset = (offset - given_offset) as usize;
 
-					if let Ok(subifd_result) = Self::decode_ifd(
-						&encoded_data[relative_offset..].to_vec(),
+					context.ancestors.push(given_offset);
+					let subifd_result = Self::decode_ifd(
+						tiff_data,
 						&subifd_group,
 						offset,
-						endian
-					)
+						endian,
+						context
+					);
+					context.ancestors.pop();
+
+					if let Ok((subifd_result, _)) = subifd_result
 					{
 						tags.extend(subifd_result);
 						continue;
 					}
 					else
 					{
-						return io_error!(Other, "Could not decode SubIFD!");
+						invalid_entry!(Other, "Could not decode SubIFD!");
 					}
 				}
 			}
 			
-			tags.push(ExifTag::from_u16_with_data(hex_tag, &format, &raw_data, &endian, group).unwrap());
+			if let Some(tag) = &known_tag
+			{
+				// The tag keeps the declared number of components, even if the
+				// specifications predefine another one (e.g. a `BitsPerSample`
+				// with a single value for grayscale images). Except for strings
+				// (whose NUL terminator and padding can't be told apart), the
+				// decoded value has to take up all of the raw data
+				match ExifTag::from_u16_with_data(hex_tag, &tag.format(), &raw_data, &endian, group)
+				{
+					Ok(tag) if !tag.is_string() && tag.value_len_bytes() != raw_data.len() 
+						=> invalid_entry!(InvalidData, format!(
+							"Tag 0x{:04x} in {:?} has {} components, but only {} could be decoded!", 
+							hex_tag, group, hex_component_number, tag.number_of_components()
+						)),
+					Ok(tag)    => tags.push(tag),
+					Err(error) => invalid_entry!(InvalidData, format!("Could not decode tag 0x{:04x} in {:?}: {}", hex_tag, group, error)),
+				}
+			}
+			else
+			{
+				tags.push(ExifTag::unknown_from_u16_with_data(hex_tag, &format, &raw_data, &endian, group));
+			}
 			
 		}
 
-		return Ok(tags);
+		// The link to the next IFD follows after the entries
+		let next_ifd_offset = from_u8_vec_macro!(u32, &tiff_data[ifd_end..(ifd_end+IFD_END.len())].to_vec(), endian);
+
+		return Ok((tags, next_ifd_offset));
+	}
+
+	/// Collects the tags of the IFD of the given group in the order they get
+	/// written by `encode_ifd`, including the computed ones (which replace 
+	/// stored tags with the same hex value)
+	fn
+	ifd_tags<'a>
+	(
+		&'a self,
+		group: ExifTagGroup,
+		computed_tags: &'a [ExifTag],
+		groups: &[ExifTagGroup],
+		tag_filter: &dyn Fn(&ExifTag) -> bool
+	)
+	-> Vec<&'a ExifTag>
+	{
+		// Sort them ascending by their hex value as required by the 
+		// specifications
+		let mut ifd_tags: Vec<&ExifTag> = self.data.iter()
+			.filter(|tag| tag.is_writable() && tag.get_group() == group)    // Skip tags that can't be written or don't belong to the group
+			.filter(|_| groups.contains(&group))                            // Skip tags of groups that are not requested
+			.filter(|tag| tag_filter(tag))                                  // Skip tags the caller does not want to be written
+			.filter(|tag| computed_tags.iter().all(|computed| computed.as_u16() != tag.as_u16()))
+			.chain(computed_tags.iter())                                    // Computed tags, e.g. for SubIFDs (ExifIFD) or the thumbnail
+			.collect();
+		ifd_tags.sort_by_key(|tag| tag.as_u16());
+
+		// Or replay the order of the decoded entries, with tags that were not
+		// decoded (e.g. added afterwards) following after them
+		if self.preserve_source_order
+		{
+			ifd_tags.sort_by_key(|tag| self.source_order.iter()
+				.position(|(source_group, hex, _)| *source_group == group && *hex == tag.as_u16())
+				.unwrap_or(usize::MAX)
+			);
+		}
+
+		return ifd_tags;
+	}
+
+	/// Gets the format and the encoded value of the given tag of the IFD of
+	/// the given group. These are the ones of the tag, unless 
+	/// `set_preserve_source_order` is enabled and the tag was decoded from
+	/// another integer format (e.g. an `ImageWidth` stored as INT16U instead
+	/// of INT32U), which then gets used again as long as the value fits.
+	fn
+	encoded_format_and_value
+	(
+		&self,
+		tag:   &ExifTag,
+		group: ExifTagGroup
+	)
+	-> (ExifTagFormat, Vec<u8>)
+	{
+		let source_format = self.source_order.iter()
+			.find(|(source_group, hex, _)| *source_group == group && *hex == tag.as_u16())
+			.and_then(|(_, _, hex_format)| ExifTagFormat::from_u16(*hex_format))
+			.filter(|format| self.preserve_source_order && tag.is_writable() && *format != tag.format());
+
+		if let Some(format) = source_format
+		{
+			if let Some(value) = integer_components_as_u8_vec(&tag.components(), &format, &self.endian)
+			{
+				return (format, value);
+			}
+		}
+
+		return (tag.format(), tag.value_as_u8_vec(&self.endian));
 	}
 
 	fn
@@ -595,21 +3646,18 @@ Metadata
 		group: ExifTagGroup,                                                    // The group the specific tags need to belong to (e.g. IFD0, ExifIFD, ...)
 		given_offset: u32,                                                      // How much offset already exists
 		next_ifd_link: &[u8; 4],                                                // A link to the next IFD (e.g. IFD1 for IFD0) or 4 bytes of 0x00 to signal "no next IFD"
-		subifd_tag: Option<ExifTag>                                             // An optional ExifTag signaling that a SubIFD will follow
+		computed_tags: &Vec<ExifTag>,                                           // Tags with values computed during encoding (e.g. offsets to SubIFDs), fitting into 4 bytes
+		groups: &[ExifTagGroup],                                                // The groups whose tags may be written, the computed tags are written regardless
+		tag_filter: &dyn Fn(&ExifTag) -> bool                                   // Decides for each stored tag whether it gets written
 	)
 	-> Option<(u32, Vec<u8>)>
 	{
-		// Start Interop IFD with number of entries
+		let ifd_tags = self.ifd_tags(group, computed_tags, groups, tag_filter);
+
+		// Start IFD with number of entries
 		// If there are none, return None
 		let mut ifd_vec: Vec<u8> = Vec::new();
-		let mut count_entries = subifd_tag.is_some() as u16;
-		for tag in &self.data
-		{
-			if tag.is_writable() && tag.get_group() == group
-			{
-				count_entries += 1;
-			}
-		}
+		let count_entries = ifd_tags.len() as u16;
 
 		if count_entries == 0
 		{
@@ -629,19 +3677,16 @@ Metadata
 		let mut ifd_offset_area: Vec<u8> = Vec::new();
 
 		// Write directory entries to the vector
-		for tag in &self.data
+		// Note that the computed tags (e.g. the offsets to SubIFDs) are known
+		// to have a single component with exactly 4 bytes, thus fitting 
+		// perfectly into the directory entry
+		for tag in ifd_tags
 		{
-			// Skip tags that can't be written or don't belong to the group
-			if !tag.is_writable() || tag.get_group() != group
-			{
-				continue;
-			}
-
-			let value = tag.value_as_u8_vec(&self.endian);
+			let (format, value) = self.encoded_format_and_value(tag, group);
 			
 			// Add Tag & Data Format /                                          2 + 2 bytes
 			ifd_vec.extend(to_u8_vec_macro!(u16, &tag.as_u16(), &self.endian).iter());
-			ifd_vec.extend(to_u8_vec_macro!(u16, &tag.format().as_u16(), &self.endian).iter());
+			ifd_vec.extend(to_u8_vec_macro!(u16, &format.as_u16(), &self.endian).iter());
 
 			// Add number of components /                                       4 bytes
 			let number_of_components: u32 = tag.number_of_components();
@@ -660,7 +3705,7 @@ Metadata
 			// Add offset or value /                                            4 bytes
 			// Depending on the amount of data, either put it directly into
 			// next 4 bytes or write an offset where the data can be found 
-			let byte_count: u32 = number_of_components * tag.format().bytes_per_component();
+			let byte_count: u32 = number_of_components * format.bytes_per_component();
 			if byte_count > 4
 			{
 				ifd_vec.extend(to_u8_vec_macro!(u32, &next_offset, &self.endian).iter());
@@ -686,23 +3731,6 @@ Metadata
 			
 		}
 
-		// In case we have to write a SubIFD (e.g. ExifIFD) next
-		// Do NOT mix this up with link to next IFD (like e.g. IFD1)
-		if let Some(tag) = subifd_tag
-		{
-			// Write the offset tag & data format /                             2 + 2 bytes
-			ifd_vec.extend(to_u8_vec_macro!(u16, &tag.as_u16(), &self.endian).iter());
-			ifd_vec.extend(to_u8_vec_macro!(u16, &tag.format().as_u16(), &self.endian).iter());
-
-			// Add number of components /                                       4 bytes
-			ifd_vec.extend(to_u8_vec_macro!(u32, &tag.number_of_components(), &self.endian).iter());
-
-			// Add the offset /                                                 4 bytes
-			// We assume (know) that this is one component which has exactly
-			// 4 bytes, thus fitting perfectly into the directory entry
-			ifd_vec.extend(to_u8_vec_macro!(u32, &next_offset, &self.endian).iter());
-		}
-
 		// Write link and offset data
 		ifd_vec.extend(next_ifd_link.iter());
 		ifd_vec.extend(ifd_offset_area.iter());
@@ -712,44 +3740,515 @@ Metadata
 		return Some((next_offset, ifd_vec));
 	}
 
-	#[allow(unused_assignments)]
+	/// Encodes the IFD of the given group at the given offset, followed by
+	/// the SubIFDs linked from it (e.g. ExifIFD and GPSIFD for IFD0), which 
+	/// are encoded recursively. SubIFDs without any tags are left out.
+	/// Only tags of the given groups are written - an IFD of another group
+	/// consists just of the offset tags linking to its SubIFDs (if needed).
+	/// Returns `None` if neither the IFD nor its SubIFDs contain any tags.
+	fn
+	encode_ifd_and_subifds
+	(
+		&self,
+		group: ExifTagGroup,
+		given_offset: u32,
+		next_ifd_link: &[u8; 4],
+		groups: &[ExifTagGroup],
+		tag_filter: &dyn Fn(&ExifTag) -> bool
+	)
+	-> Option<(u32, Vec<u8>)>
+	{
+		// Find out which SubIFDs need to be written
+		let subifds: Vec<SubIFDLink> = subifds_of(group)
+			.into_iter()
+			.filter(|(subifd_group, _)| self.encode_ifd_and_subifds(*subifd_group, 0, &IFD_END, groups, tag_filter).is_some())
+			.collect();
+
+		// As the length of the IFD does not depend on the offset values, a 
+		// first encoding with placeholder offsets tells where the SubIFDs start
+		let placeholder_tags = subifds.iter()
+			.map(|(_, offset_tag)| offset_tag(vec![0]))
+			.collect::<Vec<ExifTag>>();
+		let (mut current_offset, _) = self.encode_ifd(group, given_offset, next_ifd_link, &placeholder_tags, groups, tag_filter)?;
+
+		// Encode the SubIFDs one after another, remembering their offsets
+		let mut subifd_tags = Vec::new();
+		let mut subifd_vec  = Vec::new();
+		for (subifd_group, offset_tag) in subifds
+		{
+			let (offset_post_subifd, subifd_data) = self.encode_ifd_and_subifds(subifd_group, current_offset, &IFD_END, groups, tag_filter)?;
+			subifd_tags.push(offset_tag(vec![current_offset]));
+			subifd_vec.extend(subifd_data.iter());
+			current_offset = offset_post_subifd;
+		}
+
+		// Now encode the IFD with the actual offsets
+		let (_, mut ifd_vec) = self.encode_ifd(group, given_offset, next_ifd_link, &subifd_tags, groups, tag_filter)?;
+		ifd_vec.extend(subifd_vec.iter());
+
+		return Some((current_offset, ifd_vec));
+	}
+
 	fn
 	encode_metadata_general
 	(
 		&self
 	)
 	-> Vec<u8>
+	{
+		return self.encode_metadata_general_filtered(&ALL_GROUPS, &|_| true);
+	}
+
+	/// Encodes the metadata like `encode_metadata_general`, but only writes
+	/// the tags of the given groups (and the thumbnail if IFD1 is included)
+	/// that pass the given filter
+	fn
+	encode_metadata_general_filtered
+	(
+		&self,
+		groups:     &[ExifTagGroup],
+		tag_filter: &dyn Fn(&ExifTag) -> bool
+	)
+	-> Vec<u8>
 	{
 		// Start construction with TIFF header
 		let mut exif_vec: Vec<u8> = Vec::from(self.endian.header());
-		let mut current_offset: u32 = 8;
+		let current_offset: u32 = 8;
 
-		// IFD0
-		if let Some((offset_post_ifd0, ifd0_data)) = self.encode_ifd(
+		// IFD0 and its SubIFDs, first without a link to IFD1 to find out 
+		// where IFD1 would start
+		let Some((ifd1_offset, ifd0_data)) = self.encode_ifd_and_subifds(
 			ExifTagGroup::IFD0,
 			current_offset,                                                     // For the TIFF header
-			&[0x00, 0x00, 0x00, 0x00],                                          // For now no link to IFD1
-			Some(ExifTag::ExifOffset(vec![0]))
+			&IFD_END,
+			groups,
+			tag_filter
 		)
+		else
+		{
+			// Without any IFD0 tags, an IFD0 without entries is still needed,
+			// as the TIFF header always points to it - either for linking to
+			// IFD1 (e.g. if there is only a thumbnail) or with a zero link
+			let ifd1_offset = current_offset + 2 + IFD_END.len() as u32;
+			exif_vec.extend(to_u8_vec_macro!(u16, &0, &self.endian).iter());
+			if let Some(ifd1_data) = self.encode_ifd1(ifd1_offset, groups, tag_filter)
+			{
+				exif_vec.extend(to_u8_vec_macro!(u32, &ifd1_offset, &self.endian).iter());
+				exif_vec.extend(ifd1_data.iter());
+			}
+			else
+			{
+				exif_vec.extend(IFD_END.iter());
+			}
+			return exif_vec;
+		};
+
+		// IFD1 with the thumbnail data after it
+		if let Some(ifd1_data) = self.encode_ifd1(ifd1_offset, groups, tag_filter)
 		{
-			current_offset = offset_post_ifd0;
+			let ifd1_link: [u8; 4] = to_u8_vec_macro!(u32, &ifd1_offset, &self.endian).try_into().unwrap();
+			let (_, ifd0_data) = self.encode_ifd_and_subifds(
+				ExifTagGroup::IFD0,
+				current_offset,
+				&ifd1_link,
+				groups,
+				tag_filter
+			).unwrap();
+
 			exif_vec.extend(ifd0_data.iter());
+			exif_vec.extend(ifd1_data.iter());
 		}
-
-		// ExifIFD
-		if let Some((offset_post_exififd, exififd_data)) = self.encode_ifd(
-			ExifTagGroup::ExifIFD,
-			current_offset,                                                     // Don't need +8 as already accounted for in this value due to previous function call
-			&[0x00, 0x00, 0x00, 0x00],
-			None
-		)
+		else
 		{
-			current_offset = offset_post_exififd;
-			exif_vec.extend(exififd_data.iter());
+			exif_vec.extend(ifd0_data.iter());
 		}
 
-		// Other directories here... (someday)
-		
 		return exif_vec;
 	}
+
+	/// Encodes IFD1 at the given offset, followed by the thumbnail (if there
+	/// is any). The `ThumbnailOffset` and `ThumbnailLength` tags get computed
+	/// accordingly. Returns `None` if there are neither IFD1 tags nor a 
+	/// thumbnail or IFD1 is not among the given groups.
+	fn
+	encode_ifd1
+	(
+		&self,
+		given_offset: u32,
+		groups: &[ExifTagGroup],
+		tag_filter: &dyn Fn(&ExifTag) -> bool
+	)
+	-> Option<Vec<u8>>
+	{
+		if !groups.contains(&ExifTagGroup::IFD1)
+		{
+			return None;
+		}
+
+		let Some(thumbnail) = &self.thumbnail
+		else
+		{
+			return self.encode_ifd(ExifTagGroup::IFD1, given_offset, &IFD_END, &Vec::new(), groups, tag_filter)
+				.map(|(_, ifd1_data)| ifd1_data);
+		};
+
+		// The thumbnail follows after IFD1 and the data its tags point to
+		let mut placeholder_tags = vec![
+			ExifTag::ThumbnailOffset(vec![0]),
+			ExifTag::ThumbnailLength(vec![0]),
+		];
+		let mut thumbnail_tags = Vec::new();
+
+		if self.needs_thumbnail_compression(thumbnail)
+		{
+			placeholder_tags.push(ExifTag::UnknownINT16U(vec![JPEG_THUMBNAIL_COMPRESSION], 0x0103, ExifTagGroup::IFD1));
+			thumbnail_tags.push(ExifTag::UnknownINT16U(vec![JPEG_THUMBNAIL_COMPRESSION], 0x0103, ExifTagGroup::IFD1));
+		}
+
+		let (thumbnail_offset, _) = self.encode_ifd(ExifTagGroup::IFD1, given_offset, &IFD_END, &placeholder_tags, groups, tag_filter)?;
+
+		thumbnail_tags.push(ExifTag::ThumbnailOffset(vec![thumbnail_offset]));
+		thumbnail_tags.push(ExifTag::ThumbnailLength(vec![thumbnail.len() as u32]));
+		let (_, mut ifd1_data) = self.encode_ifd(ExifTagGroup::IFD1, given_offset, &IFD_END, &thumbnail_tags, groups, tag_filter)?;
+		ifd1_data.extend(thumbnail.iter());
+
+		return Some(ifd1_data);
+	}
+
+	/// A JPEG thumbnail requires a Compression of 6, otherwise some viewers
+	/// don't find it. Checks if the value is missing or inconsistent and
+	/// therefore needs to be corrected when writing IFD1
+	fn
+	needs_thumbnail_compression
+	(
+		&self,
+		thumbnail: &[u8]
+	)
+	-> bool
+	{
+		if !is_jpeg(thumbnail)
+		{
+			return false;
+		}
+
+		let compression = self.data.iter().find_map(|tag| match tag
+		{
+			ExifTag::UnknownINT16U(value, 0x0103, ExifTagGroup::IFD1) => Some(value),
+			_ => None
+		});
+
+		return compression != Some(&vec![JPEG_THUMBNAIL_COMPRESSION]);
+	}
+
+	/// Appends the IFD entries of the given group and its SubIFDs to the 
+	/// plan in the order `encode_ifd_and_subifds` writes them. Returns 
+	/// `false` if neither the IFD nor its SubIFDs contain any tags.
+	fn
+	write_plan_of_ifd_and_subifds
+	(
+		&self,
+		group: ExifTagGroup,
+		plan: &mut Vec<(ExifTagGroup, u16)>
+	)
+	-> bool
+	{
+		let subifds: Vec<SubIFDLink> = subifds_of(group)
+			.into_iter()
+			.filter(|(subifd_group, _)| self.encode_ifd_and_subifds(*subifd_group, 0, &IFD_END, &ALL_GROUPS, &|_| true).is_some())
+			.collect();
+
+		let offset_tags = subifds.iter()
+			.map(|(_, offset_tag)| offset_tag(vec![0]))
+			.collect::<Vec<ExifTag>>();
+		let ifd_tags = self.ifd_tags(group, &offset_tags, &ALL_GROUPS, &|_| true);
+
+		if ifd_tags.is_empty()
+		{
+			return false;
+		}
+
+		plan.extend(ifd_tags.iter().map(|tag| (group, tag.as_u16())));
+		for (subifd_group, _) in subifds
+		{
+			self.write_plan_of_ifd_and_subifds(subifd_group, plan);
+		}
+
+		return true;
+	}
+}
+
+/// Interprets the value of a GPS direction tag (e.g. `GPSImgDirection`) in
+/// degrees and its reference tag, which defaults to true north if missing
+fn
+gps_direction
+(
+	value:     &[uR64],
+	reference: Option<&str>
+)
+-> Option<(f64, NorthReference)>
+{
+	if value.len() != 1 || value[0].denominator == 0
+	{
+		return None;
+	}
+
+	let reference = match reference.map(trim_string_padding).unwrap_or("T")
+	{
+		"T" => NorthReference::True,
+		"M" => NorthReference::Magnetic,
+		_   => return None,
+	};
+
+	return Some((value[0].clone().into(), reference));
+}
+
+/// Finds the standard value closest to the given value if their binary 
+/// logarithms differ by at most the given tolerance, e.g. to round a shutter
+/// speed computed from APEX units to one marked on cameras
+fn
+round_to_standard_value
+(
+	value:     f64,
+	standard:  &[f64],
+	tolerance: f64
+)
+-> Option<f64>
+{
+	return standard.iter()
+		.map(|candidate| (*candidate, (value.log2() - candidate.log2()).abs()))
+		.filter(|(_, distance)| *distance <= tolerance)
+		.min_by(|(_, a), (_, b)| a.total_cmp(b))
+		.map(|(candidate, _)| candidate);
+}
+
+/// Checks if the given data starts with the JPEG SOI marker, e.g. to find out
+/// if a thumbnail is a JPEG
+fn
+is_jpeg
+(
+	data: &[u8]
+)
+-> bool
+{
+	return data.starts_with(&[0xff, 0xd8]);
+}
+
+/// Gets the given date without padding, or `None` if it is not set, i.e. it
+/// only consists of blanks, colons and zeros (e.g. `"0000:00:00 00:00:00"`
+/// or `"    :  :     :  :  "`, which the specifications allow for unknown 
+/// dates)
+fn
+date_if_set
+(
+	date: &str
+)
+-> Option<&str>
+{
+	let trimmed = trim_string_padding(date);
+	if trimmed.chars().all(|character| matches!(character, ' ' | ':' | '0'))
+	{
+		return None;
+	}
+	return Some(trimmed);
+}
+
+/// Encodes the given integer values using another integer format, or `None`
+/// if the values aren't integers or don't fit into the new format
+fn
+integer_components_as_u8_vec
+(
+	value:  &TagValue,
+	format: &ExifTagFormat,
+	endian: &Endian
+)
+-> Option<Vec<u8>>
+{
+	let integers: Vec<i64> = match value
+	{
+		TagValue::Byte(   values) => values.iter().map(|value| *value as i64).collect(),
+		TagValue::Short(  values) => values.iter().map(|value| *value as i64).collect(),
+		TagValue::Long(   values) => values.iter().map(|value| *value as i64).collect(),
+		TagValue::SByte(  values) => values.iter().map(|value| *value as i64).collect(),
+		TagValue::SShort( values) => values.iter().map(|value| *value as i64).collect(),
+		TagValue::SLong(  values) => values.iter().map(|value| *value as i64).collect(),
+		_                         => return None,
+	};
+
+	return match format
+	{
+		ExifTagFormat::INT8U  => Some(integers.iter().map(|value| u8::try_from(*value)).collect::<Result<Vec<u8>, _>>().ok()?.to_u8_vec(endian)),
+		ExifTagFormat::INT16U => Some(integers.iter().map(|value| u16::try_from(*value)).collect::<Result<Vec<u16>, _>>().ok()?.to_u8_vec(endian)),
+		ExifTagFormat::INT32U => Some(integers.iter().map(|value| u32::try_from(*value)).collect::<Result<Vec<u32>, _>>().ok()?.to_u8_vec(endian)),
+		ExifTagFormat::INT8S  => Some(integers.iter().map(|value| i8::try_from(*value)).collect::<Result<Vec<i8>, _>>().ok()?.to_u8_vec(endian)),
+		ExifTagFormat::INT16S => Some(integers.iter().map(|value| i16::try_from(*value)).collect::<Result<Vec<i16>, _>>().ok()?.to_u8_vec(endian)),
+		ExifTagFormat::INT32S => Some(integers.iter().map(|value| i32::try_from(*value)).collect::<Result<Vec<i32>, _>>().ok()?.to_u8_vec(endian)),
+		_                     => None,
+	};
+}
+
+/// A SubIFD group together with the variant of the offset tag linking to it
+type SubIFDLink = (ExifTagGroup, fn(Vec<u32>) -> ExifTag);
+
+/// The SubIFDs that may be linked from the IFD of the given group
+fn
+subifds_of
+(
+	group: ExifTagGroup
+)
+-> Vec<SubIFDLink>
+{
+	match group
+	{
+		ExifTagGroup::IFD0    => vec![
+			(ExifTagGroup::ExifIFD,    ExifTag::ExifOffset),
+			(ExifTagGroup::GPSIFD,     ExifTag::GPSInfo),
+		],
+		ExifTagGroup::ExifIFD => vec![
+			(ExifTagGroup::InteropIFD, ExifTag::InteropOffset),
+		],
+		_                     => Vec::new(),
+	}
+}
+
+/// Removes trailing whitespace and NUL characters that are used as padding in
+/// string values by some cameras
+fn
+trim_string_padding
+(
+	value: &str
+)
+-> &str
+{
+	return value.trim_end_matches(|character: char| character.is_whitespace() || character == '\0');
+}
+
+/// Reads only the `Make` and `Model` tags of IFD0 from the image at the 
+/// specified path, which is a lot cheaper than decoding all of the metadata
+/// (including SubIFDs) via `Metadata::new_from_path`. The entries of IFD0
+/// are only scanned until both tags are found.
+/// - If unable to read the file (e.g. does not exist, unsupported file type,
+///   etc.), an error gets returned.
+/// - If the file has no (decodable) EXIF data or one of the two tags is 
+///   missing, `None` gets returned.
+///
+/// # Examples
+/// ```no_run
+/// if let Some((make, model)) = revolt_little_exif::read_camera(std::path::Path::new("image.jpg")).unwrap()
+/// {
+///     println!("Taken with a {} {}", make, model);
+/// }
+/// ```
+pub fn
+read_camera
+(
+	path: &Path
+)
+-> Result<Option<(String, String)>, std::io::Error>
+{
+	let file_type   = Metadata::file_type_from_path(path)?;
+	let exif_header = Metadata::exif_header_of(&file_type);
+
+	let pre_decode_general = match Metadata::read_raw_from_path(path, file_type, false)
+	{
+		Ok(data) => data,
+		Err(_)   => return Ok(None),
+	};
+
+	let (endian, tiff_data) = match Metadata::decode_tiff_header(&pre_decode_general, exif_header)
+	{
+		Ok(result) => result,
+		Err(_)     => return Ok(None),
+	};
+
+	// Scan the entries of IFD0 for the two tags
+	let ifd_start = match Metadata::ifd0_offset_of(&tiff_data, &endian)
+	{
+		Ok(offset) => offset as usize,
+		Err(_)     => return Ok(None),
+	};
+	if tiff_data.len() < ifd_start + 2
+	{
+		return Ok(None);
+	}
+	let number_of_entries = from_u8_vec_macro!(u16, &tiff_data[ifd_start..(ifd_start+2)].to_vec(), &endian) as usize;
+
+	let mut make  = None;
+	let mut model = None;
+	for i in 0..number_of_entries
+	{
+		let ifd_entry_index = ifd_start + 2 + i * IFD_ENTRY_LENGTH as usize;
+		if tiff_data.len() < ifd_entry_index + IFD_ENTRY_LENGTH as usize
+		{
+			break;
+		}
+
+		let hex_tag    = from_u8_vec_macro!(u16, &tiff_data[(ifd_entry_index)..(ifd_entry_index+2)].to_vec(), &endian);
+		let hex_format = from_u8_vec_macro!(u16, &tiff_data[(ifd_entry_index+2)..(ifd_entry_index+4)].to_vec(), &endian);
+
+		let is_make_or_model = hex_tag == ExifTag::Make(String::new()).as_u16() || hex_tag == ExifTag::Model(String::new()).as_u16();
+		if !is_make_or_model || hex_format != ExifTagFormat::STRING.as_u16()
+		{
+			continue;
+		}
+
+		// Strings longer than 4 bytes are stored at an offset
+		let byte_count = from_u8_vec_macro!(u32, &tiff_data[(ifd_entry_index+4)..(ifd_entry_index+8)].to_vec(), &endian) as usize;
+		let data_start = match byte_count > 4
+		{
+			true  => from_u8_vec_macro!(u32, &tiff_data[(ifd_entry_index+8)..(ifd_entry_index+12)].to_vec(), &endian) as usize,
+			false => ifd_entry_index + 8,
+		};
+		if tiff_data.len() < data_start + byte_count
+		{
+			continue;
+		}
+
+		let raw_data = tiff_data[data_start..(data_start+byte_count)].to_vec();
+		match ExifTag::from_u16_with_data(hex_tag, &ExifTagFormat::STRING, &raw_data, &endian, &ExifTagGroup::IFD0)
+		{
+			Ok(ExifTag::Make(value))  => make  = Some(value),
+			Ok(ExifTag::Model(value)) => model = Some(value),
+			_                         => (),
+		}
+
+		if make.is_some() && model.is_some()
+		{
+			break;
+		}
+	}
+
+	return Ok(make.zip(model));
+}
+
+/// Copies the EXIF metadata of the image at `src` into the image at `dst`,
+/// which may be of another file type (e.g. from a JPEG to a WebP). The 
+/// container specific wrapping (APP1 segment, PNG chunk, WebP chunk) is 
+/// handled automatically, the tags, the thumbnail and the endianness are 
+/// taken over as they are. Any EXIF metadata previously stored in the
+/// destination gets replaced.
+/// In contrast to `Metadata::new_from_path`, an error is returned if the
+/// source does not contain any decodable EXIF metadata, leaving the 
+/// destination untouched.
+///
+/// # Examples
+/// ```no_run
+/// revolt_little_exif::transcode_metadata(
+///     std::path::Path::new("photo.jpg"),
+///     std::path::Path::new("photo.webp")
+/// ).unwrap();
+/// ```
+pub fn
+transcode_metadata
+(
+	src: &Path,
+	dst: &Path
+)
+-> Result<(), std::io::Error>
+{
+	let file_type          = Metadata::file_type_from_path(src)?;
+	let exif_header        = Metadata::exif_header_of(&file_type);
+	let pre_decode_general = Metadata::read_raw_from_path(src, file_type, false)?;
+	let metadata           = Metadata::decode_metadata_general(&pre_decode_general, exif_header, &DecodeOptions::new())?;
+
+	return metadata.write_to_file(dst);
 }
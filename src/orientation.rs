@@ -0,0 +1,139 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Applying the EXIF orientation to decoded pixel data, so that the image
+//! gets displayed the way it was intended (e.g. upright for photos taken
+//! with a rotated camera). Requires the `orientation` feature.
+
+use crate::general_file_io::*;
+
+/// Transforms the given pixel buffer according to the value of the EXIF 
+/// `Orientation` tag, in place. The buffer is expected to store the pixels
+/// row by row (starting at the top left), each consisting of `channels` 
+/// bytes (e.g. 3 for RGB or 4 for RGBA data).
+/// Returns the width and height of the transformed image, which are swapped
+/// for the orientations 5 to 8 (i.e. rotations by 90 degrees).
+///
+/// | Orientation | Transformation                          |
+/// |-------------|-----------------------------------------|
+/// | 1           | None                                    |
+/// | 2           | Mirror horizontally                     |
+/// | 3           | Rotate by 180°                          |
+/// | 4           | Mirror vertically                       |
+/// | 5           | Transpose (mirror along main diagonal)  |
+/// | 6           | Rotate by 90° clockwise                 |
+/// | 7           | Transverse (mirror along anti-diagonal) |
+/// | 8           | Rotate by 90° counterclockwise          |
+///
+/// Returns an error if the orientation is not one of these values or if the
+/// size of the buffer does not match the given dimensions.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::metadata::Metadata;
+/// use revolt_little_exif::exif_tag::ExifTag;
+/// use revolt_little_exif::orientation;
+///
+/// let metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+///
+/// // RGB data as given by some image decoder
+/// let (mut pixels, width, height) = (vec![0u8; 640 * 480 * 3], 640, 480);
+///
+/// if let Some(ExifTag::Orientation(value)) = metadata.get_tag(&ExifTag::Orientation(vec![]))
+/// {
+///     let (width, height) = orientation::apply(&mut pixels, width, height, 3, value[0]).unwrap();
+/// }
+/// ```
+pub fn
+apply
+(
+	pixels:      &mut [u8],
+	width:       u32,
+	height:      u32,
+	channels:    u32,
+	orientation: u16
+)
+-> Result<(u32, u32), std::io::Error>
+{
+	let (w, h, c) = (width as usize, height as usize, channels as usize);
+
+	if w.checked_mul(h).and_then(|area| area.checked_mul(c)) != Some(pixels.len())
+	{
+		return io_error!(InvalidInput, format!(
+			"Pixel buffer of {} bytes does not match {}x{} pixels with {} channels!",
+			pixels.len(), width, height, channels
+		));
+	}
+
+	// Maps a pixel of the transformed image to the pixel of the original one
+	// it gets its data from, given the width of the transformed image
+	let source: fn(usize, usize, usize, usize) -> (usize, usize) = match orientation
+	{
+		1 => return Ok((width, height)),
+		2 => |x, y, w, _| (w - 1 - x, y        ),
+		3 => |x, y, w, h| (w - 1 - x, h - 1 - y),
+		4 => |x, y, _, h| (x,         h - 1 - y),
+		5 => |x, y, _, _| (y,         x        ),
+		6 => |x, y, _, h| (y,         h - 1 - x),
+		7 => |x, y, w, h| (w - 1 - y, h - 1 - x),
+		8 => |x, y, w, _| (w - 1 - y, x        ),
+		_ => return io_error!(InvalidInput, format!("Invalid orientation {}!", orientation)),
+	};
+
+	let (new_width, new_height) = if orientation >= 5 { (height, width) } else { (width, height) };
+
+	let original = pixels.to_vec();
+	for y in 0..(new_height as usize)
+	{
+		for x in 0..(new_width as usize)
+		{
+			let (source_x, source_y) = source(x, y, w, h);
+			let target_index = (y        * new_width as usize + x       ) * c;
+			let source_index = (source_y * w                  + source_x) * c;
+			pixels[target_index..(target_index+c)].copy_from_slice(&original[source_index..(source_index+c)]);
+		}
+	}
+
+	return Ok((new_width, new_height));
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::apply;
+
+	#[test]
+	fn
+	all_orientations()
+	{
+		// 3x2 image with a single channel:
+		// 1 2 3
+		// 4 5 6
+		let expected: [(u16, Vec<u8>, (u32, u32)); 8] = [
+			(1, vec![1, 2, 3, 4, 5, 6], (3, 2)),
+			(2, vec![3, 2, 1, 6, 5, 4], (3, 2)),
+			(3, vec![6, 5, 4, 3, 2, 1], (3, 2)),
+			(4, vec![4, 5, 6, 1, 2, 3], (3, 2)),
+			(5, vec![1, 4, 2, 5, 3, 6], (2, 3)),
+			(6, vec![4, 1, 5, 2, 6, 3], (2, 3)),
+			(7, vec![6, 3, 5, 2, 4, 1], (2, 3)),
+			(8, vec![3, 6, 2, 5, 1, 4], (2, 3)),
+		];
+
+		for (orientation, pixels, dimensions) in expected
+		{
+			let mut buffer = vec![1, 2, 3, 4, 5, 6];
+			assert_eq!(apply(&mut buffer, 3, 2, 1, orientation).unwrap(), dimensions);
+			assert_eq!(buffer, pixels, "Orientation {}", orientation);
+		}
+
+		// Channels are moved as a whole
+		let mut buffer = vec![1, 1, 2, 2];
+		assert_eq!(apply(&mut buffer, 2, 1, 2, 6).unwrap(), (1, 2));
+		assert_eq!(buffer, vec![1, 1, 2, 2]);
+
+		let mut buffer = vec![1, 2, 3];
+		assert!(apply(&mut buffer, 2, 2, 1, 1).is_err());
+		assert!(apply(&mut buffer, 3, 1, 1, 9).is_err());
+	}
+}
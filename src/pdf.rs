@@ -0,0 +1,125 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Reading EXIF data from JPEG images embedded in PDF documents. These are
+//! stored as streams using the `DCTDecode` filter, whose data is a complete
+//! JPEG file that is read using the JPEG reader. Streams with further
+//! filters (e.g. `[/FlateDecode /DCTDecode]`) are compressed and therefore
+//! skipped, as well as streams of objects stored in compressed object
+//! streams. Writing is not supported.
+
+use std::path::Path;
+
+use crate::general_file_io::*;
+
+use crate::jpg;
+
+pub(crate) const PDF_SIGNATURE: &[u8] = b"%PDF-";
+
+const OBJ_KEYWORD:       &[u8] = b"obj";
+const STREAM_KEYWORD:    &[u8] = b"stream";
+const ENDSTREAM_KEYWORD: &[u8] = b"endstream";
+const DCT_DECODE_FILTER: &[u8] = b"/DCTDecode";
+
+/// Reads the raw EXIF data of all JPEG images embedded in the PDF document
+/// at the specified path that have EXIF data
+pub(crate) fn
+read_all_metadata
+(
+	path: &Path
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	return read_all_metadata_from_bytes(&file_buffer);
+}
+
+/// Reads the raw EXIF data of all JPEG images embedded in the given PDF data
+/// that have EXIF data, in the order their streams occur in the data
+pub(crate) fn
+read_all_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<Vec<u8>>, std::io::Error>
+{
+	if !file_buffer.starts_with(PDF_SIGNATURE)
+	{
+		return io_error!(InvalidData, "Can't open PDF file - Wrong signature!");
+	}
+
+	let mut raw_exif_data = Vec::new();
+	let mut position      = 0;
+
+	while let Some(stream_keyword_start) = find(file_buffer, position, STREAM_KEYWORD)
+	{
+		let stream_keyword_end = stream_keyword_start + STREAM_KEYWORD.len();
+
+		// The "stream" of an "endstream" keyword without a preceding stream
+		if file_buffer[..stream_keyword_start].ends_with(b"end")
+		{
+			position = stream_keyword_end;
+			continue;
+		}
+
+		// The dictionary of the stream is located between the start of the
+		// object and the keyword
+		let dictionary_start = rfind(&file_buffer[position..stream_keyword_start], OBJ_KEYWORD)
+			.map_or(position, |offset| position + offset);
+		let dictionary = &file_buffer[dictionary_start..stream_keyword_start];
+
+		// The keyword is followed by an end-of-line marker (CRLF or LF)
+		let mut data_start = stream_keyword_end;
+		if file_buffer[data_start..].starts_with(b"\r\n")
+		{
+			data_start += 2;
+		}
+		else if file_buffer[data_start..].starts_with(b"\n")
+		{
+			data_start += 1;
+		}
+
+		let data_end = find(file_buffer, data_start, ENDSTREAM_KEYWORD).unwrap_or(file_buffer.len());
+
+		if find(dictionary, 0, DCT_DECODE_FILTER).is_some()
+		{
+			if let Ok(raw_exif) = jpg::read_metadata_from_bytes(&file_buffer[data_start..data_end])
+			{
+				raw_exif_data.push(raw_exif);
+			}
+		}
+
+		position = data_end;
+	}
+
+	return Ok(raw_exif_data);
+}
+
+/// Finds the first occurrence of the needle in the haystack at or after the
+/// given position
+fn
+find
+(
+	haystack: &[u8],
+	position: usize,
+	needle:   &[u8]
+)
+-> Option<usize>
+{
+	return haystack.get(position..)?
+		.windows(needle.len())
+		.position(|window| window == needle)
+		.map(|offset| position + offset);
+}
+
+/// Finds the last occurrence of the needle in the haystack
+fn
+rfind
+(
+	haystack: &[u8],
+	needle:   &[u8]
+)
+-> Option<usize>
+{
+	return haystack.windows(needle.len()).rposition(|window| window == needle);
+}
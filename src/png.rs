@@ -2,12 +2,6 @@
 // See https://github.com/TechnikTobi/little_exif#license for licensing details
 
 use std::path::Path;
-use std::io::Read;
-use std::io::Write;
-use std::io::Seek;
-use std::io::SeekFrom;
-use std::fs::File;
-use std::fs::OpenOptions;
 use std::collections::VecDeque;
 
 use crc::Crc;
@@ -19,6 +13,12 @@ use crate::png_chunk::PngChunk;
 use crate::general_file_io::*;
 
 pub(crate) const PNG_SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+// The hex encoded EXIF data within the zTXt chunk starts with the EXIF header
+pub(crate) const PNG_EXIF_HEADER: &[u8] = &EXIF_HEADER;
+
+// The maximum length of the data of a chunk according to the specification
+const PNG_MAX_CHUNK_LENGTH: usize = 0x7fff_ffff;
+
 pub(crate) const RAW_PROFILE_TYPE_EXIF: [u8; 23] = [
 	0x52, 0x61, 0x77, 0x20,                             // Raw
 	0x70, 0x72, 0x6F, 0x66, 0x69, 0x6C, 0x65, 0x20,     // profile
@@ -183,133 +183,125 @@ decode_metadata_png
 	return Ok(Vec::from(exif_all));
 }
 
-fn
-check_signature
-(
-	path: &Path
-)
--> Result<File, std::io::Error>
-{
-	if !path.exists()
-	{
-		return io_error!(NotFound, "Can't open PNG file - File does not exist!");
-	}
+/// A chunk of PNG data: Its descriptor (name and data length) and its complete
+/// bytes, i.e. length, name, data and CRC
+type RawChunk<'a> = (PngChunk, &'a [u8]);
 
-	let mut file = OpenOptions::new()
-		.read(true)
-		.open(path)
-		.expect("Could not open file");
-	
-	// Check the signature
-	let mut signature_buffer = [0u8; 8];
-	file.read(&mut signature_buffer).unwrap();
-	let signature_is_valid = signature_buffer.iter()
-		.zip(PNG_SIGNATURE.iter())
-		.filter(|&(read, constant)| read == constant)
-		.count() == PNG_SIGNATURE.len();
-
-	if !signature_is_valid
-	{
-		return io_error!(InvalidData, "Can't open PNG file - Wrong signature!");
-	}
-
-	// Signature is valid - can proceed using the file as PNG file
-	return Ok(file);
-}
-
-// TODO: Check if this is also affected by endianness
-// Edit: Should... not? I guess?
+/// Reads the descriptor of the chunk starting at the given position of the
+/// PNG data in the given buffer, i.e. its name and the length of its data.
+/// If the buffer ends before, an error of kind `UnexpectedEof` states how
+/// many bytes are required in total.
 fn
-get_next_chunk_descriptor
+read_chunk_descriptor
 (
-	file: &mut File
+	file_buffer: &[u8],
+	position:    usize
 )
 -> Result<PngChunk, std::io::Error>
 {
-	// Read the start of the chunk
-	let mut chunk_start = [0u8; 8];
-	let mut bytes_read = file.read(&mut chunk_start).unwrap();
-
-	// Check that indeed 8 bytes were read
-	if bytes_read != 8
+	if file_buffer.len() < position + 8
 	{
-		return io_error!(Other, "Could not read start of chunk");
+		return incomplete_data_error!(position + 8);
 	}
 
 	// Construct name of chunk and its length
-	let chunk_name = String::from_utf8((&chunk_start[4..8]).to_vec());
+	let chunk_name = String::from_utf8_lossy(&file_buffer[(position+4)..(position+8)]).to_string();
 	let mut chunk_length = 0u32;
-	for byte in &chunk_start[0..4]
+	for byte in &file_buffer[position..(position+4)]
 	{
 		chunk_length = chunk_length * 256 + *byte as u32;
 	}
 
-	// Read chunk data ...
-	let mut chunk_data_buffer = vec![0u8; chunk_length as usize];
-	bytes_read = file.read(&mut chunk_data_buffer).unwrap();
-	if bytes_read != chunk_length as usize
+	// Note: chunk_length does NOT include the +4 for the CRC area!
+	if let Ok(png_chunk) = PngChunk::from_string(&chunk_name, chunk_length)
 	{
-		return io_error!(Other, "Could not read chunk data");
+		return Ok(png_chunk);
 	}
+	return io_error!(InvalidData, "Invalid PNG chunk name");
+}
+
+/// Reads the complete bytes of the chunk with the given descriptor that 
+/// starts at the given position of the PNG data in the given buffer. 
+/// Optionally, the CRC of the chunk gets verified, failing with an error that
+/// names the chunk in case of a mismatch. 
+/// If the buffer ends before the chunk, an error of kind `UnexpectedEof`
+/// states how many bytes are required in total.
+fn
+read_chunk<'a>
+(
+	file_buffer: &'a [u8],
+	position:    usize,
+	chunk:       &PngChunk,
+	verify_crc:  bool
+)
+-> Result<&'a [u8], std::io::Error>
+{
+	// Length & name (4+4 bytes), data and CRC (4 bytes)
+	let data_end  = position + 8 + chunk.length() as usize;
+	let chunk_end = data_end + 4;
 
-	// ... and CRC values
-	let mut chunk_crc_buffer = [0u8; 4];
-	bytes_read = file.read(&mut chunk_crc_buffer).unwrap();
-	if bytes_read != 4
+	if file_buffer.len() < chunk_end
 	{
-		return io_error!(Other, "Could not read chunk CRC");
+		return incomplete_data_error!(chunk_end);
 	}
 
-	// Compute CRC on chunk
-	let mut crc_input = Vec::new();
-	crc_input.extend(chunk_start[4..8].iter());
-	crc_input.extend(chunk_data_buffer.iter());
-
-	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-	let checksum = crc_struct.checksum(&crc_input) as u32;
-
-	for i in 0..4
+	// Compute CRC on chunk name and data
+	if verify_crc
 	{
-		if ((checksum >> (8 * (3-i))) as u8) != chunk_crc_buffer[i]
+		let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+		let checksum = crc_struct.checksum(&file_buffer[(position+4)..data_end]);
+
+		if checksum.to_be_bytes() != file_buffer[data_end..chunk_end]
 		{
-			return io_error!(InvalidData, "Checksum check failed while reading PNG!");
+			return io_error!(InvalidData, format!("Checksum check failed for {} chunk while reading PNG!", chunk.as_string()));
 		}
 	}
 
-	// If validating the chunk using the CRC was successful, return its descriptor
-	// Note: chunk_length does NOT include the +4 for the CRC area!
-	if let Ok(png_chunk) = PngChunk::from_string(
-		&chunk_name.unwrap(),
-		chunk_length
-	)
-	{
-		return Ok(png_chunk);
-	}
-	else
-	{
-		return io_error!(Other, "Invalid PNG chunk name");
-	}
+	return Ok(&file_buffer[position..chunk_end]);
+}
+
+/// The data of the given complete chunk bytes, without length, name and CRC
+fn
+chunk_data
+(
+	raw_chunk: &[u8]
+)
+-> &[u8]
+{
+	return &raw_chunk[8..(raw_chunk.len()-4)];
 }
 
-/// "Parses" the PNG by checking various properties:
-/// - Can the file be opened and is the signature valid?
-/// - Are the various chunks OK or not? For this, the local subroutine `get_next_chunk_descriptor` is used
+/// "Parses" the PNG data in the given buffer by checking various properties:
+/// - Is the signature valid?
+/// - Are the various chunks up to the IEND chunk complete and do they have
+///   valid names? For this, the local subroutines `read_chunk_descriptor` and
+///   `read_chunk` are used
+/// - Optionally, do the CRCs of the chunks match their contents?
 pub(crate) fn
 parse_png
 (
-	path: &Path
+	file_buffer: &[u8],
+	verify_crc:  bool
 )
--> Result<Vec<PngChunk>, std::io::Error>
+-> Result<Vec<RawChunk<'_>>, std::io::Error>
 {
-	let mut file = check_signature(path)?;
-	let mut chunks = Vec::new();
+	if !file_buffer.starts_with(&PNG_SIGNATURE)
+	{
+		return io_error!(InvalidData, "Can't open PNG file - Wrong signature!");
+	}
+
+	let mut chunks: Vec<RawChunk> = Vec::new();
+	let mut position = PNG_SIGNATURE.len();
 
 	loop
 	{
-		let chunk_descriptor = get_next_chunk_descriptor(&mut file)?;
-		chunks.push(chunk_descriptor);
+		let chunk     = read_chunk_descriptor(file_buffer, position)?;
+		let raw_chunk = read_chunk(file_buffer, position, &chunk, verify_crc)?;
+
+		position += raw_chunk.len();
+		chunks.push((chunk, raw_chunk));
 
-		if chunks.last().unwrap().as_string() == "IEND".to_string()
+		if chunks.last().unwrap().0.as_string() == "IEND"
 		{
 			break;
 		}
@@ -318,9 +310,37 @@ parse_png
 	return Ok(chunks);
 }
 
-// Clears existing metadata chunk from a png file
-// Gets called before writing any new metadata
-#[allow(non_snake_case)]
+/// Checks if the given chunk is the zTXt chunk containing the EXIF data
+fn
+is_exif_chunk
+(
+	chunk:      &PngChunk,
+	chunk_data: &[u8]
+)
+-> bool
+{
+	return chunk.as_string() == "zTXt" && chunk_data.starts_with(&RAW_PROFILE_TYPE_EXIF);
+}
+
+/// Decodes the raw EXIF data from the data of the EXIF zTXt chunk
+fn
+decode_exif_chunk
+(
+	chunk_data: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	// Decode zlib data...
+	if let Ok(decompressed_data) = decompress_to_vec_zlib(&chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
+	{
+		// ...and perform PNG-specific decoding & return the result
+		return decode_metadata_png(&decompressed_data);
+	}
+	return io_error!(Other, "Could not inflate compressed chunk data!");
+}
+
+/// Removes the EXIF zTXt chunk(s) from the PNG image file at the specified
+/// path, see `clear_metadata_from_bytes`
 pub(crate) fn
 clear_metadata
 (
@@ -328,149 +348,195 @@ clear_metadata
 )
 -> Result<(), std::io::Error>
 {
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = clear_metadata_from_bytes(&file_buffer)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
+	return Ok(());
+}
 
-	// Parse the PNG - if this fails, the clear operation fails as well
-	let parse_png_result = parse_png(path)?;
-
-	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	let mut file = OpenOptions::new()
-		.write(true)
-		.read(true)
-		.open(path)
-		.expect("Could not open file");
-	let mut seek_counter = 8u64;
+/// Reads the raw EXIF data from the PNG image file at the specified path, 
+/// going through all of its chunks. Unless `verify_crc` is set, the CRCs of
+/// the chunks are not verified, so that files with stale CRCs (e.g. left 
+/// behind by buggy tools) can still be read.
+pub(crate) fn
+read_metadata
+(
+	path:       &Path,
+	verify_crc: bool
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
 
-	for chunk in &parse_png_result
+	// Parse the PNG - if this fails, the read fails as well
+	for (chunk, raw_chunk) in parse_png(&file_buffer, verify_crc)?
 	{
-		// If this is not a zTXt chunk, jump to the next chunk
-		if chunk.as_string() != String::from("zTXt")
+		if is_exif_chunk(&chunk, chunk_data(raw_chunk))
 		{
-			seek_counter += chunk.length() as u64 + 12;
-			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
-			continue;
+			return decode_exif_chunk(chunk_data(raw_chunk));
 		}
+	}
+
+	return io_error!(Other, "No metadata found!");
+}
+
+/// Reads the raw EXIF data from the leading bytes of a PNG file by going
+/// through the chunks up to the first IDAT chunk. Metadata that is stored after
+/// the image data is therefore not found by this function. If `verify_crc` is
+/// set, the CRCs of these chunks get verified as well.
+/// If the given bytes end before the EXIF data could be read, an error of kind
+/// `UnexpectedEof` states how many bytes are required in total.
+pub(crate) fn
+read_metadata_from_bytes
+(
+	file_buffer: &[u8],
+	verify_crc:  bool
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	if file_buffer.len() < PNG_SIGNATURE.len()
+	{
+		return incomplete_data_error!(PNG_SIGNATURE.len());
+	}
+
+	if file_buffer[0..PNG_SIGNATURE.len()] != PNG_SIGNATURE
+	{
+		return io_error!(InvalidData, "Can't open PNG file - Wrong signature!");
+	}
 
-		// Skip chunk length and type (4+4 Bytes)
-		perform_file_action!(file.seek(SeekFrom::Current(8)));
+	let mut position = PNG_SIGNATURE.len();
 
-		// Read chunk data into buffer for checking that this is the 
-		// correct chunk to delete
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
+	loop
+	{
+		let chunk = read_chunk_descriptor(file_buffer, position)?;
+
+		if chunk.as_string() == "IDAT" || chunk.as_string() == "IEND"
 		{
-			return io_error!(Other, "Could not read chunk data");
+			break;
 		}
 
-		// Compare to the "Raw profile type exif" string constant
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
+		// Other chunks than zTXt only need to be complete for verifying them
+		if chunk.as_string() == "zTXt" || verify_crc
 		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
+			let raw_chunk = read_chunk(file_buffer, position, &chunk, verify_crc)?;
+			if is_exif_chunk(&chunk, chunk_data(raw_chunk))
 			{
-				correct_zTXt_chunk = false;
-				break;
+				return decode_exif_chunk(chunk_data(raw_chunk));
 			}
 		}
 
-		// Skip the CRC as it is not important at this point
-		perform_file_action!(file.seek(SeekFrom::Current(4)));
+		position += 8 + chunk.length() as usize + 4;
+	}
 
-		// If this is not the correct zTXt chunk, ignore current
-		// (wrong) zTXt chunk and continue with next chunk
-		if !correct_zTXt_chunk
-		{	
-			continue;
-		}
-		
-		// We have now established that this is the correct chunk to delete
-		// Therefore: Copy data from here (after CRC) onwards into a buffer...
-		let mut buffer = Vec::new();
-		perform_file_action!(file.read_to_end(&mut buffer));
+	return io_error!(Other, "No metadata found!");
+}
 
-		// ...compute the new file length while we are at it...
-		let new_file_length = seek_counter + buffer.len() as u64;
+/// Decodes the keyword and text of a tEXt, zTXt or iTXt chunk.
+/// Returns `None` if the chunk data is malformed.
+fn
+decode_text_chunk
+(
+	chunk_name: &str,
+	chunk_data: &[u8]
+)
+-> Option<(String, String)>
+{
+	// All textual chunks start with a NUL terminated, Latin-1 encoded keyword
+	let keyword_end = chunk_data.iter().position(|byte| *byte == 0x00)?;
+	let keyword     = chunk_data[..keyword_end].iter().map(|byte| *byte as char).collect::<String>();
+	let rest        = &chunk_data[(keyword_end+1)..];
 
-		// ...go back to the chunk to be removed...
-		perform_file_action!(file.seek(SeekFrom::Start(seek_counter)));
+	match chunk_name
+	{
+		// Latin-1 encoded text without any further fields
+		"tEXt" => {
+			return Some((keyword, rest.iter().map(|byte| *byte as char).collect()));
+		},
+
+		// Compression method (only 0 = zlib is defined) and compressed text
+		"zTXt" => {
+			if rest.first() != Some(&0x00)
+			{
+				return None;
+			}
+			let text = decompress_to_vec_zlib(&rest[1..]).ok()?;
+			return Some((keyword, text.iter().map(|byte| *byte as char).collect()));
+		},
+
+		// Compression flag & method, NUL terminated language tag and
+		// translated keyword, followed by UTF-8 encoded (compressed) text
+		"iTXt" => {
+			if rest.len() < 2
+			{
+				return None;
+			}
+			let compressed = rest[0] == 0x01;
+			let mut fields = rest[2..].splitn(3, |byte| *byte == 0x00);
+			let _language_tag       = fields.next()?;
+			let _translated_keyword = fields.next()?;
+			let text_data           = fields.next()?;
 
-		// ...and overwrite it using the data from the buffer
-		perform_file_action!(file.write_all(&buffer));
-		perform_file_action!(file.seek(SeekFrom::Start(seek_counter)));		
+			let text = if compressed
+			{
+				decompress_to_vec_zlib(text_data).ok()?
+			}
+			else
+			{
+				text_data.to_vec()
+			};
+			return Some((keyword, String::from_utf8(text).ok()?));
+		},
 
-		// Update the size of the file - otherwise there will be
-		// duplicate bytes at the end!
-		perform_file_action!(file.set_len(new_file_length));
+		_ => None
 	}
-
-	return Ok(());
 }
 
-#[allow(non_snake_case)]
-pub(crate) fn
-read_metadata
+/// Reads the keyword/value pairs of all textual chunks (tEXt, zTXt & iTXt) of
+/// the PNG file at the given path, in the order they appear in the file.
+/// The zTXt chunk containing the EXIF data is not included, as its content
+/// is available via the `Metadata` struct. Chunks with malformed data are
+/// skipped.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::png::read_text_chunks;
+///
+/// for (keyword, text) in read_text_chunks(std::path::Path::new("image.png")).unwrap()
+/// {
+///     println!("{}: {}", keyword, text);
+/// }
+/// ```
+pub fn
+read_text_chunks
 (
 	path: &Path
 )
--> Result<Vec<u8>, std::io::Error>
+-> Result<Vec<(String, String)>, std::io::Error>
 {
-	// Parse the PNG - if this fails, the read fails as well
-	let parse_png_result = parse_png(path)?;
+	let file_buffer = std::fs::read(path)?;
+	let mut text_chunks = Vec::new();
 
-	// Parsed PNG is Ok to use - Open the file and go through the chunks
-	let mut file = check_signature(path).unwrap();
-	for chunk in &parse_png_result
+	// Parse the PNG - if this fails, the read fails as well
+	for (chunk, raw_chunk) in parse_png(&file_buffer, true)?
 	{
-		// Wrong chunk? Seek to the next one
-		if chunk.as_string() != String::from("zTXt")
+		let chunk_name = chunk.as_string();
+		if chunk_name != "tEXt" && chunk_name != "zTXt" && chunk_name != "iTXt"
 		{
-			perform_file_action!(file.seek(SeekFrom::Current(chunk.length() as i64 + 12)));
 			continue;
 		}
 
-		// We now have a zTXt chunk:
-		// Skip chunk length and type (4+4 Bytes)
-		perform_file_action!(file.seek(SeekFrom::Current(8)));
-
-		// Read chunk data into buffer
-		// No need to verify this using CRC as already done by parse_png(path)
-		let mut zTXt_chunk_data = vec![0u8; chunk.length() as usize];
-		if file.read(&mut zTXt_chunk_data).unwrap() != chunk.length() as usize
-		{
-			return io_error!(Other, "Could not read chunk data");
-		}
-
-		// Check that this is the correct zTXt chunk...
-		let mut correct_zTXt_chunk = true;
-		for i in 0..RAW_PROFILE_TYPE_EXIF.len()
-		{
-			if zTXt_chunk_data[i] != RAW_PROFILE_TYPE_EXIF[i]
-			{
-				correct_zTXt_chunk = false;
-				break;
-			}
-		}
-
-		if !correct_zTXt_chunk
+		if is_exif_chunk(&chunk, chunk_data(raw_chunk))
 		{
-			// Skip CRC from current (wrong) zTXt chunk and continue
-			perform_file_action!(file.seek(SeekFrom::Current(4)));
 			continue;
 		}
 
-		// Decode zlib data...
-		if let Ok(decompressed_data) = decompress_to_vec_zlib(&zTXt_chunk_data[RAW_PROFILE_TYPE_EXIF.len()..])
-		{
-			// ...and perform PNG-specific decoding & return the result
-			return Ok(decode_metadata_png(&decompressed_data).unwrap());
-		}
-		else
+		if let Some(text_chunk) = decode_text_chunk(&chunk_name, chunk_data(raw_chunk))
 		{
-			return io_error!(Other, "Could not inflate compressed chunk data!");
+			text_chunks.push(text_chunk);
 		}
 	}
 
-	return io_error!(Other, "No metadata found!");
-
+	return Ok(text_chunks);
 }
 
 /// Provides the WebP specific encoding result as vector of bytes to be used
@@ -499,50 +565,46 @@ as_u8_vec
 	return zTXt_chunk_data;
 }
 
-#[allow(non_snake_case)]
-pub(crate) fn
-write_metadata
+/// Checks that a chunk with data of the given length can be stored in a PNG
+/// file, i.e. does not exceed the maximum length of 2^31-1 bytes
+fn
+check_chunk_length
 (
-	path: &Path,
-	general_encoded_metadata: &Vec<u8>
+	chunk_data_length: usize
 )
 -> Result<(), std::io::Error>
 {
-
-	// First clear the existing metadata
-	// This also parses the PNG and checks its validity, so it is safe to
-	// assume that is, in fact, a usable PNG file
-	let _ = clear_metadata(path)?;
-
-	let mut IHDR_length = 0u32;
-	if let Ok(chunks) = parse_png(path)
+	if chunk_data_length > PNG_MAX_CHUNK_LENGTH
 	{
-		IHDR_length = chunks[0].length();
+		return io_error!(InvalidInput, format!(
+			"Can't write PNG chunk with {} bytes of data - Exceeds the maximum of {} bytes!",
+			chunk_data_length, PNG_MAX_CHUNK_LENGTH
+		));
 	}
+	return Ok(());
+}
 
-	// Encode the data specifically for PNG and open the image file
+/// Builds the complete zTXt chunk (length, type, data and CRC) containing
+/// the given generally encoded metadata. Returns an error if the chunk is
+/// too large for a PNG file.
+#[allow(non_snake_case)]
+fn
+encode_zTXt_chunk
+(
+	general_encoded_metadata: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
 	let encoded_metadata = encode_metadata_png(general_encoded_metadata);
-	let mut file = OpenOptions::new()
-		.write(true)
-		.read(true)
-		.open(path)
-		.expect("Could not open file");
-	let seek_start = 0u64         // Skip ...
-	+ PNG_SIGNATURE.len() as u64  // PNG Signature
-	+ IHDR_length         as u64  // IHDR data section
-	+ 12                  as u64; // rest of IHDR chunk (length, type, CRC)
-
-	// Get to first chunk after IHDR, copy all the data starting from there
-	let mut buffer = Vec::new();
-	perform_file_action!(file.seek(SeekFrom::Start(seek_start)));
-	perform_file_action!(file.read_to_end(&mut buffer));
-	perform_file_action!(file.seek(SeekFrom::Start(seek_start)));
 
 	// Build data of new chunk using zlib compression (level=8 -> default)
 	let mut zTXt_chunk_data: Vec<u8> = vec![0x7a, 0x54, 0x58, 0x74];
 	zTXt_chunk_data.extend(RAW_PROFILE_TYPE_EXIF.iter());
 	zTXt_chunk_data.extend(compress_to_vec_zlib(&encoded_metadata, 8).iter());
 
+	// Excluding the chunk type
+	check_chunk_length(zTXt_chunk_data.len() - 4)?;
+
 	// Compute CRC and append it to the chunk data
 	let crc_struct = Crc::<u32>::new(&CRC_32_ISO_HDLC);
 	let checksum = crc_struct.checksum(&zTXt_chunk_data) as u32;
@@ -551,21 +613,86 @@ write_metadata
 		zTXt_chunk_data.push( (checksum >> (8 * (3-i))) as u8);		
 	}
 
-	// Write new data to PNG file
 	// Start with length of the new chunk (subtracting 8 for type and CRC)
 	let chunk_data_len = zTXt_chunk_data.len() as u32 - 8;
+	let mut zTXt_chunk: Vec<u8> = Vec::new();
 	for i in 0..4
 	{
-		perform_file_action!(file.write( &[(chunk_data_len >> (8 * (3-i))) as u8] ));
+		zTXt_chunk.push((chunk_data_len >> (8 * (3-i))) as u8);
 	}
+	zTXt_chunk.extend(zTXt_chunk_data.iter());
 
-	// Write data of new chunk and rest of PNG file
-	perform_file_action!(file.write_all(&zTXt_chunk_data));
-	perform_file_action!(file.write_all(&buffer));
+	return Ok(zTXt_chunk);
+}
 
+/// Writes the given generally encoded metadata to the PNG image file at the
+/// specified path, see `write_metadata_to_bytes`. The file stays unchanged if
+/// the metadata can't be stored in a PNG.
+pub(crate) fn
+write_metadata
+(
+	path: &Path,
+	general_encoded_metadata: &Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = write_metadata_to_bytes(&file_buffer, general_encoded_metadata)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
 	return Ok(());
 }
 
+/// Removes the EXIF zTXt chunk(s) from the PNG data in the given buffer,
+/// returning the resulting file contents
+pub(crate) fn
+clear_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let mut new_file_buffer = PNG_SIGNATURE.to_vec();
+
+	// Skip the EXIF zTXt chunk, keep everything else
+	for (chunk, raw_chunk) in parse_png(file_buffer, false)?
+	{
+		if !is_exif_chunk(&chunk, chunk_data(raw_chunk))
+		{
+			new_file_buffer.extend(raw_chunk.iter());
+		}
+	}
+
+	return Ok(new_file_buffer);
+}
+
+/// Writes the given generally encoded metadata to the PNG data in the given
+/// buffer, returning the resulting file contents. Like for `write_metadata`,
+/// an existing EXIF zTXt chunk gets removed and the new one is placed 
+/// directly after the IHDR chunk. For an animated PNG, this is before the 
+/// acTL chunk, which only has to precede the first IDAT chunk.
+#[allow(non_snake_case)]
+pub(crate) fn
+write_metadata_to_bytes
+(
+	file_buffer:              &[u8],
+	general_encoded_metadata: &Vec<u8>
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let zTXt_chunk = encode_zTXt_chunk(general_encoded_metadata)?;
+
+	let mut new_file_buffer = clear_metadata_from_bytes(file_buffer)?;
+
+	// The new chunk follows directly after IHDR, which is the first chunk 
+	// (length & name, data and CRC). Its length got checked while clearing 
+	let ihdr = read_chunk_descriptor(&new_file_buffer, PNG_SIGNATURE.len())?;
+	let ihdr_end = PNG_SIGNATURE.len() + 8 + ihdr.length() as usize + 4;
+
+	new_file_buffer.splice(ihdr_end..ihdr_end, zTXt_chunk);
+
+	return Ok(new_file_buffer);
+}
+
 #[cfg(test)]
 mod tests 
 {
@@ -574,10 +701,18 @@ mod tests
 	fn
 	parsing_test() 
 	{
-		let chunks = crate::png::parse_png(
-			std::path::Path::new("tests/png_parse_test_image.png")
-		).unwrap();
+		let file_buffer = std::fs::read("tests/png_parse_test_image.png").unwrap();
+		let chunks = crate::png::parse_png(&file_buffer, true).unwrap();
 		assert_eq!(chunks.len(), 3);
 	}
+
+	#[test]
+	fn
+	check_chunk_length()
+	{
+		assert!(crate::png::check_chunk_length(0).is_ok());
+		assert!(crate::png::check_chunk_length(0x7fff_ffff).is_ok());
+		assert!(crate::png::check_chunk_length(0x8000_0000).is_err());
+	}
 	
 }
@@ -27,6 +27,8 @@ macro_rules! build_png_chunk_type_enum {
 	{
 		/// These are the different PNG chunk types currently known to
 		/// little_exif. These might be expanded in the future if necessary.
+		/// Any other chunk (e.g. a private one) is represented by `Unknown`
+		/// so that it can be skipped and preserved when writing metadata.
 		#[allow(non_camel_case_types)]
 		pub(crate) enum
 		PngChunk
@@ -34,6 +36,7 @@ macro_rules! build_png_chunk_type_enum {
 			$(
 				$tag(u32),
 			)*
+			Unknown(String, u32),
 		}
 
 		impl PngChunk
@@ -50,6 +53,7 @@ macro_rules! build_png_chunk_type_enum {
 					$(
 						PngChunk::$tag(length) => length,
 					)*
+					PngChunk::Unknown(_, length) => length,
 				}
 			}
 
@@ -60,11 +64,12 @@ macro_rules! build_png_chunk_type_enum {
 			)
 			-> String
 			{
-				match *self
+				match self
 				{
 					$(
 						PngChunk::$tag(_) => String::from(stringify!($tag)),
 					)*
+					PngChunk::Unknown(name, _) => name.clone(),
 				}
 			}
 
@@ -81,7 +86,17 @@ macro_rules! build_png_chunk_type_enum {
 					$(
 						stringify!($tag) => Ok(PngChunk::$tag(length)),
 					)*
-					_ => Err("Invalid chunk name".to_string()),
+					_ => {
+						// Chunk names consist of exactly 4 ASCII letters
+						if string_name.len() == 4 && string_name.chars().all(|c| c.is_ascii_alphabetic())
+						{
+							Ok(PngChunk::Unknown(string_name.clone(), length))
+						}
+						else
+						{
+							Err("Invalid chunk name".to_string())
+						}
+					},
 				}
 			}
 		}
@@ -94,5 +109,24 @@ build_png_chunk_type_enum![
 	(PLTE,  true,       false,      BEFORE_IDAT),
 	(IDAT,  true,       true,       NONE),
 	(IEND,  true,       false,      LAST),
-	(zTXt,  false,      true,       NONE)
+	(cHRM,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(gAMA,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(iCCP,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(sBIT,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(sRGB,  false,      false,      BEFORE_PLTE_AND_IDAT),
+	(bKGD,  false,      false,      AFTER_PLTE_BEFORE_IDAT),
+	(tRNS,  false,      false,      AFTER_PLTE_BEFORE_IDAT),
+	(pHYs,  false,      false,      BEFORE_IDAT),
+	(tIME,  false,      false,      NONE),
+	(eXIf,  false,      false,      BEFORE_IDAT),
+	(zTXt,  false,      true,       NONE),
+	(tEXt,  false,      true,       NONE),
+	(iTXt,  false,      true,       NONE),
+
+	// Animated PNG (APNG), see https://wiki.mozilla.org/APNG_Specification
+	// The sequence numbers of fcTL and fdAT are not affected by inserting
+	// or removing other chunks, so these can be preserved as they are
+	(acTL,  false,      false,      BEFORE_IDAT),
+	(fcTL,  false,      true,       NONE),
+	(fdAT,  false,      true,       NONE)
 ];
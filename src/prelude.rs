@@ -0,0 +1,24 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Re-exports the types that are needed for most interactions with the 
+//! crate, so that they can be imported at once.
+//!
+//! # Examples
+//! ```no_run
+//! use revolt_little_exif::prelude::*;
+//!
+//! let mut metadata = Metadata::new_from_path(std::path::Path::new("image.jpg")).unwrap();
+//! metadata.set_tag(ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 250 }]));
+//! metadata.write_to_file(std::path::Path::new("image.jpg")).unwrap();
+//! ```
+
+pub use crate::decode_options::DecodeOptions;
+pub use crate::endian::Endian;
+pub use crate::exif_tag::ExifTag;
+pub use crate::exif_tag::ExifTagGroup;
+pub use crate::exif_tag_format::TagValue;
+pub use crate::filetype::FileExtension;
+pub use crate::metadata::Metadata;
+pub use crate::rational::iR64;
+pub use crate::rational::uR64;
@@ -0,0 +1,286 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Preview images embedded in the EXIF data, see `Metadata::previews`.
+//! Besides the thumbnail of IFD1, previews are searched for in the IFDs
+//! following IFD1 in the chain (IFD2, IFD3, ...) and in the SubIFDs linked
+//! via the `SubIFDs` tag (0x014a) of IFD0 and these IFDs, as e.g. written
+//! by cameras for RAW files. An IFD contains a preview if it has a
+//! `JPEGInterchangeFormat` (0x0201) and `JPEGInterchangeFormatLength`
+//! (0x0202) tag, giving the offset and length of the image data.
+
+use std::io::Cursor;
+
+use crate::endian::Endian;
+use crate::u8conversion::*;
+use crate::jpg::SegmentIter;
+
+const IFD_ENTRY_LENGTH: usize = 12;
+
+const IMAGE_WIDTH_TAG:    u16 = 0x0100;
+const IMAGE_HEIGHT_TAG:   u16 = 0x0101;
+const SUBIFDS_TAG:        u16 = 0x014a;
+const PREVIEW_START_TAG:  u16 = 0x0201;
+const PREVIEW_LENGTH_TAG: u16 = 0x0202;
+
+// The hex value of an IFD entry and its integer values
+type IfdEntry = (u16, Vec<u32>);
+
+/// The format of the data of a preview image
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum
+PreviewFormat
+{
+	/// A complete JPEG file, starting with the SOI marker
+	JPEG,
+
+	/// Any other data, e.g. uncompressed thumbnails
+	Unknown,
+}
+
+/// A preview image embedded in the EXIF data
+#[derive(Clone, Debug, PartialEq)]
+pub struct
+Preview
+{
+	/// The width of the image in pixels, if known
+	pub width:  Option<u32>,
+
+	/// The height of the image in pixels, if known
+	pub height: Option<u32>,
+
+	/// The format of the data
+	pub format: PreviewFormat,
+
+	/// The data of the image as stored in the file
+	pub data:   Vec<u8>,
+}
+
+impl
+Preview
+{
+	/// Constructs a preview from its data, with the given dimensions as
+	/// fallback in case they can't be determined from the data itself (e.g.
+	/// using the SOF segment of a JPEG)
+	pub(crate) fn
+	new
+	(
+		data:   Vec<u8>,
+		width:  Option<u32>,
+		height: Option<u32>
+	)
+	-> Preview
+	{
+		if !data.starts_with(&[0xff, 0xd8])
+		{
+			return Preview { width, height, format: PreviewFormat::Unknown, data };
+		}
+
+		let (width, height) = match jpeg_dimensions(&data)
+		{
+			Some((jpeg_width, jpeg_height)) => (Some(jpeg_width), Some(jpeg_height)),
+			None                            => (width, height),
+		};
+
+		return Preview { width, height, format: PreviewFormat::JPEG, data };
+	}
+}
+
+/// Gets the dimensions (width and height) of a JPEG from its first SOF
+/// segment, which has to precede the image data
+fn
+jpeg_dimensions
+(
+	data: &[u8]
+)
+-> Option<(u32, u32)>
+{
+	let mut segments = SegmentIter::new(Cursor::new(data)).ok()?;
+	while let Some(Ok(segment)) = segments.next()
+	{
+		match segment.marker()
+		{
+			0xda => return None,                                                // SOS marker, the image data starts
+			0xc4 | 0xc8 | 0xcc => (),                                           // DHT, JPG and DAC markers, which share the range of the SOF markers
+			0xc0..=0xcf => {
+				// Sample precision (1 byte), height and width (2 bytes each)
+				let sof = segments.read_data(&segment).ok()?;
+				if sof.len() < 5
+				{
+					return None;
+				}
+				let height = u16::from_be_bytes([sof[1], sof[2]]) as u32;
+				let width  = u16::from_be_bytes([sof[3], sof[4]]) as u32;
+				return Some((width, height));
+			},
+			_ => (),
+		}
+	}
+	return None;
+}
+
+/// Decodes the previews found in the IFDs starting with the one at the given
+/// offset (i.e. the one IFD1 links to, or 0 if there is none) as well as
+/// their SubIFDs, followed by the ones in the SubIFDs of IFD0.
+/// Invalid IFDs are skipped, and at most `max_ifd_depth` IFDs are followed
+/// in a chain or nested SubIFDs, just like when decoding the tags.
+pub(crate) fn
+decode_previews
+(
+	tiff_data:     &[u8],
+	ifd0_offset:   u32,
+	ifd2_offset:   u32,
+	endian:        &Endian,
+	max_ifd_depth: usize
+)
+-> Vec<Preview>
+{
+	let mut previews = Vec::new();
+	let mut visited  = vec![ifd0_offset];
+
+	decode_previews_of_chain(tiff_data, ifd2_offset, endian, max_ifd_depth, 0, &mut visited, &mut previews);
+
+	if let Some((ifd0_entries, _)) = read_ifd(tiff_data, ifd0_offset, endian)
+	{
+		for subifd_offset in entry_values(&ifd0_entries, SUBIFDS_TAG)
+		{
+			decode_previews_of_chain(tiff_data, subifd_offset, endian, max_ifd_depth, 1, &mut visited, &mut previews);
+		}
+	}
+
+	return previews;
+}
+
+fn
+decode_previews_of_chain
+(
+	tiff_data:     &[u8],
+	offset:        u32,
+	endian:        &Endian,
+	max_ifd_depth: usize,
+	depth:         usize,
+	visited:       &mut Vec<u32>,
+	previews:      &mut Vec<Preview>
+)
+{
+	let mut current_offset = offset;
+	let mut chain_length   = 0;
+
+	while current_offset != 0 && chain_length < max_ifd_depth && depth < max_ifd_depth
+	{
+		// Offsets that were already visited indicate a cycle
+		if visited.contains(&current_offset)
+		{
+			return;
+		}
+		visited.push(current_offset);
+
+		let Some((entries, next_offset)) = read_ifd(tiff_data, current_offset, endian)
+		else
+		{
+			return;
+		};
+
+		if let (Some(start), Some(length)) = (
+			entry_values(&entries, PREVIEW_START_TAG).first(),
+			entry_values(&entries, PREVIEW_LENGTH_TAG).first()
+		)
+		{
+			let start = *start as usize;
+			let end   = start + *length as usize;
+			if *length > 0 && end <= tiff_data.len()
+			{
+				previews.push(Preview::new(
+					tiff_data[start..end].to_vec(),
+					entry_values(&entries, IMAGE_WIDTH_TAG).first().copied(),
+					entry_values(&entries, IMAGE_HEIGHT_TAG).first().copied()
+				));
+			}
+		}
+
+		for subifd_offset in entry_values(&entries, SUBIFDS_TAG)
+		{
+			decode_previews_of_chain(tiff_data, subifd_offset, endian, max_ifd_depth, depth + 1, visited, previews);
+		}
+
+		current_offset = next_offset;
+		chain_length  += 1;
+	}
+}
+
+/// Gets the values of the entry with the given hex value
+fn
+entry_values
+(
+	entries:   &[IfdEntry],
+	hex_value: u16
+)
+-> Vec<u32>
+{
+	return entries.iter()
+		.find(|(hex, _)| *hex == hex_value)
+		.map(|(_, values)| values.clone())
+		.unwrap_or_default();
+}
+
+/// Reads the entries of the IFD at the given offset with integer values
+/// (`SHORT`, `LONG` or `IFD` format, the latter being used by some files for
+/// the `SubIFDs` tag) and the offset of the next IFD.
+/// Returns `None` if the IFD exceeds the TIFF data.
+fn
+read_ifd
+(
+	tiff_data: &[u8],
+	offset:    u32,
+	endian:    &Endian
+)
+-> Option<(Vec<IfdEntry>, u32)>
+{
+	let ifd_start = offset as usize;
+	let number_of_entries = from_u8_vec_macro!(u16, &tiff_data.get(ifd_start..(ifd_start+2))?.to_vec(), endian) as usize;
+
+	let ifd_end = ifd_start + 2 + IFD_ENTRY_LENGTH * number_of_entries;
+	let next_offset = from_u8_vec_macro!(u32, &tiff_data.get(ifd_end..(ifd_end+4))?.to_vec(), endian);
+
+	let mut entries = Vec::new();
+	for entry in tiff_data[(ifd_start+2)..ifd_end].chunks(IFD_ENTRY_LENGTH)
+	{
+		let hex_tag    = from_u8_vec_macro!(u16, &entry[0..2].to_vec(), endian);
+		let hex_format = from_u8_vec_macro!(u16, &entry[2..4].to_vec(), endian);
+		let count      = from_u8_vec_macro!(u32, &entry[4..8].to_vec(), endian) as usize;
+
+		let component_size = match hex_format
+		{
+			3      => 2,
+			4 | 13 => 4,
+			_      => continue,
+		};
+
+		// The values are either stored in the entry or at the given offset
+		let byte_count = component_size * count;
+		let raw_values = match byte_count > 4
+		{
+			true  => {
+				let data_start = from_u8_vec_macro!(u32, &entry[8..12].to_vec(), endian) as usize;
+				let Some(raw_values) = tiff_data.get(data_start..(data_start+byte_count))
+				else
+				{
+					continue;
+				};
+				raw_values
+			},
+			false => &entry[8..(8+byte_count)],
+		};
+
+		let values = raw_values.chunks(component_size)
+			.map(|component| match component_size
+			{
+				2 => from_u8_vec_macro!(u16, &component.to_vec(), endian) as u32,
+				_ => from_u8_vec_macro!(u32, &component.to_vec(), endian),
+			})
+			.collect();
+		entries.push((hex_tag, values));
+	}
+
+	return Some((entries, next_offset));
+}
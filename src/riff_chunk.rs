@@ -49,52 +49,3 @@ RiffChunkDescriptor
 		self.fourCC.clone()
 	}
 }
-
-
-pub(crate) struct
-RiffChunk
-{
-	descriptor: RiffChunkDescriptor,
-	payload:    Vec<u8>
-}
-
-impl
-RiffChunk
-{
-	#[allow(non_snake_case)]
-	pub fn
-	new
-	(
-		fourCC:  String,
-		size:    usize,
-		payload: Vec<u8>
-	)
-	-> RiffChunk
-	{
-		RiffChunk
-		{
-			descriptor: RiffChunkDescriptor::new(fourCC, size),
-			payload:    payload
-		}
-	}
-
-	pub fn
-	descriptor
-	(
-		&self
-	)
-	-> RiffChunkDescriptor
-	{
-		self.descriptor.clone()
-	}
-
-	pub fn
-	payload
-	(
-		&self
-	)
-	-> &Vec<u8>
-	{
-		&self.payload
-	}
-}
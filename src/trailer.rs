@@ -0,0 +1,37 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+use crate::endian::Endian;
+use crate::general_file_io::*;
+
+// The number of bytes at the end of a file that get searched for EXIF data
+const TRAILER_SCAN_LENGTH: usize = 0x0010_0000;
+
+/// Searches the tail of the given buffer (the last `TRAILER_SCAN_LENGTH`
+/// bytes) for EXIF data appended at the end of a file (e.g. by some TGA
+/// writers), which is recognized by the EXIF header followed by a TIFF
+/// header.
+/// Returns the candidates starting with the EXIF header and reaching until
+/// the end of the buffer, with the candidate closest to the end first. As
+/// these may also be false positives (e.g. the bytes appearing by chance in
+/// the image data), the caller has to check that they can be decoded.
+pub(crate) fn
+exif_candidates
+(
+	file_buffer: &[u8]
+)
+-> impl Iterator<Item = &[u8]>
+{
+	let scan_start = file_buffer.len().saturating_sub(TRAILER_SCAN_LENGTH);
+
+	return (scan_start..file_buffer.len())
+		.rev()
+		.map(move |position| &file_buffer[position..])
+		.filter(|candidate|
+			candidate.starts_with(&EXIF_HEADER)
+			&& (
+				candidate[EXIF_HEADER.len()..].starts_with(&Endian::Little.header()[0..4])
+				|| candidate[EXIF_HEADER.len()..].starts_with(&Endian::Big.header()[0..4])
+			)
+		);
+}
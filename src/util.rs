@@ -0,0 +1,131 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Helpers for getting image data into a form little_exif can read, e.g.
+//! when the image is not stored as plain file.
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Decodes the content of a MIME part (e.g. an image attached to an email)
+/// according to its `Content-Transfer-Encoding`, so that it can be passed to
+/// `Metadata::new_from_vec_autodetect`.
+/// Supported encodings are `base64` and `quoted-printable` (compared case
+/// insensitively). As specified by RFC 2045, characters outside of the
+/// base64 alphabet (e.g. line breaks) are ignored and decoding stops at the
+/// padding. Any other encoding (like `7bit`, `8bit` or `binary`) is assumed
+/// not to alter the data, which therefore gets returned unchanged.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::metadata::Metadata;
+/// use revolt_little_exif::util::decode_mime_image;
+///
+/// let part = std::fs::read("attachment.txt").unwrap();
+/// let image = decode_mime_image(&part, "base64");
+/// let metadata = Metadata::new_from_vec_autodetect(&image).unwrap();
+/// ```
+pub fn
+decode_mime_image
+(
+	part:     &[u8],
+	encoding: &str
+)
+-> Vec<u8>
+{
+	match encoding.trim().to_lowercase().as_str()
+	{
+		"base64"           => decode_base64(part),
+		"quoted-printable" => decode_quoted_printable(part),
+		_                  => part.to_vec(),
+	}
+}
+
+fn
+decode_base64
+(
+	data: &[u8]
+)
+-> Vec<u8>
+{
+	let mut decoded = Vec::new();
+	let mut buffer  = 0u32;
+	let mut bits    = 0u32;
+
+	for byte in data
+	{
+		if *byte == b'='
+		{
+			break;
+		}
+
+		let Some(value) = BASE64_ALPHABET.iter().position(|character| character == byte)
+		else
+		{
+			continue;
+		};
+
+		// Each character contributes 6 bits, complete bytes are emitted
+		buffer = (buffer << 6) | value as u32;
+		bits  += 6;
+		if bits >= 8
+		{
+			bits -= 8;
+			decoded.push((buffer >> bits) as u8);
+			buffer &= (1 << bits) - 1;
+		}
+	}
+
+	return decoded;
+}
+
+fn
+decode_quoted_printable
+(
+	data: &[u8]
+)
+-> Vec<u8>
+{
+	let mut decoded  = Vec::new();
+	let mut position = 0;
+
+	while position < data.len()
+	{
+		if data[position] != b'='
+		{
+			decoded.push(data[position]);
+			position += 1;
+			continue;
+		}
+
+		let escaped = &data[(position+1)..data.len().min(position+3)];
+
+		// A soft line break, which just gets removed
+		if escaped.starts_with(b"\r\n")
+		{
+			position += 3;
+			continue;
+		}
+		if escaped.starts_with(b"\n")
+		{
+			position += 2;
+			continue;
+		}
+
+		// An encoded byte given by two hex digits, otherwise the equal sign
+		// is kept as it is
+		let is_encoded_byte = escaped.len() == 2 && escaped.iter().all(u8::is_ascii_hexdigit);
+		match std::str::from_utf8(escaped).ok().filter(|_| is_encoded_byte).and_then(|digits| u8::from_str_radix(digits, 16).ok())
+		{
+			Some(byte) => {
+				decoded.push(byte);
+				position += 3;
+			},
+			None => {
+				decoded.push(b'=');
+				position += 1;
+			},
+		}
+	}
+
+	return decoded;
+}
@@ -4,7 +4,6 @@
 use std::fs::File;
 use std::fs::OpenOptions;
 use std::io::Read;
-use std::io::Write;
 use std::io::Seek;
 use std::io::SeekFrom;
 use std::path::Path;
@@ -12,13 +11,50 @@ use std::path::Path;
 use crate::endian::*;
 use crate::u8conversion::*;
 use crate::general_file_io::*;
-use crate::riff_chunk::RiffChunk;
 use crate::riff_chunk::RiffChunkDescriptor;
 
 pub(crate) const RIFF_SIGNATURE:       [u8; 4] = [0x52, 0x49, 0x46, 0x46];
 pub(crate) const WEBP_SIGNATURE:       [u8; 4] = [0x57, 0x45, 0x42, 0x50];
 pub(crate) const VP8X_HEADER:          &str    = "VP8X";
 pub(crate) const EXIF_CHUNK_HEADER:    &str    = "EXIF";
+pub(crate) const XMP_CHUNK_HEADER:     &str    = "XMP ";
+
+// The EXIF chunk directly starts with the TIFF data
+pub(crate) const WEBP_EXIF_HEADER: &[u8] = &[];
+
+// The flags of the VP8X chunk indicating an EXIF and an XMP chunk
+const EXIF_FLAG: u8 = 0x08;
+const XMP_FLAG:  u8 = 0x04;
+
+// Known chunks that should come before the EXIF chunk
+const PRE_EXIF_CHUNKS: [&str; 7] = [
+	"VP8X",
+	"VP8 ",
+	"VP8L",
+	"ICCP",
+	"ANIM",
+	"ANMF",
+	"ALPH"
+];
+
+// Known chunks that should come before the XMP chunk, i.e. the EXIF chunk and
+// the ones preceding it
+const PRE_XMP_CHUNKS: [&str; 8] = [
+	"VP8X",
+	"VP8 ",
+	"VP8L",
+	"ICCP",
+	"ANIM",
+	"ANMF",
+	"ALPH",
+	EXIF_CHUNK_HEADER
+];
+
+// The size information of the file and its chunks is stored as u32
+const RIFF_MAX_SIZE: u64 = u32::MAX as u64;
+
+// The type of a chunk and its raw bytes, see `split_into_chunks`
+type RawChunk<'a> = (String, &'a [u8]);
 
 /// A WebP file starts as follows
 /// - The RIFF signature: ASCII characters "R", "I", "F", "F"  -> 4 bytes
@@ -61,7 +97,7 @@ check_signature
 
 	// Read the file size in byte and validate it using the file metadata
 	let mut size_buffer = [0u8; 4];
-	file.read(&mut size_buffer).unwrap();
+	perform_file_action!(file.read_exact(&mut size_buffer));
 	let byte_count = from_u8_vec_macro!(u32, &size_buffer.to_vec(), &Endian::Little);
 	if file.metadata().unwrap().len() != (byte_count + 8) as u64
 	{
@@ -70,7 +106,7 @@ check_signature
 
 	// Check the WEBP signature
 	let mut webp_signature_buffer = [0u8; 4];
-	file.read(&mut webp_signature_buffer).unwrap();
+	perform_file_action!(file.read_exact(&mut webp_signature_buffer));
 	if !webp_signature_buffer.iter()
 		.zip(WEBP_SIGNATURE.iter())
 		.filter(|&(read, constant)| read == constant)
@@ -88,18 +124,18 @@ check_signature
 
 
 
-/// Gets the next RIFF chunk, starting at the current file cursor
-/// Advances the cursor to the start of the next chunk
+/// Gets a descriptor of the next RIFF chunk, starting at the current file
+/// cursor position. Advances the cursor to the start of the next chunk
 fn
-get_next_chunk
+get_next_chunk_descriptor
 (
 	file: &mut File
 )
--> Result<RiffChunk, std::io::Error>
+-> Result<RiffChunkDescriptor, std::io::Error>
 {
 	// Read the start of the chunk
 	let mut chunk_start = [0u8; 8];
-	let mut bytes_read = file.read(&mut chunk_start).unwrap();
+	let bytes_read = file.read(&mut chunk_start).unwrap();
 
 	// Check that indeed 8 bytes were read
 	if bytes_read != 8
@@ -114,24 +150,21 @@ get_next_chunk
 	// Account for the possible padding byte
 	chunk_length += chunk_length % 2;
 
-	// Read RIFF chunk data
-	let mut chunk_data_buffer = vec![0u8; chunk_length as usize];
-	bytes_read = file.read(&mut chunk_data_buffer).unwrap();
-	if bytes_read != chunk_length as usize
+	// Skip the RIFF chunk data, making sure that it is actually there
+	let chunk_start_position = file.stream_position()?;
+	let file_length          = file.metadata()?.len();
+	if file_length < chunk_start_position + chunk_length as u64
 	{
 		return io_error!(
 			Other, 
-			format!("Could not read RIFF chunk data! Expected {chunk_length} bytes but read {bytes_read}")
+			format!("Could not read RIFF chunk data! Expected {} bytes but read {}", chunk_length, file_length - chunk_start_position)
 		);
 	}
+	perform_file_action!(file.seek(SeekFrom::Current(chunk_length as i64)));
 
 	if let Ok(parsed_chunk_name) = chunk_name
 	{
-		return Ok(RiffChunk::new(
-			parsed_chunk_name as String, 
-			chunk_length      as usize,
-			chunk_data_buffer as Vec<u8>
-		));
+		return Ok(RiffChunkDescriptor::new(parsed_chunk_name, chunk_length as usize));
 	}
 	else
 	{
@@ -141,23 +174,6 @@ get_next_chunk
 
 
 
-/// Gets a descriptor of the next RIFF chunk, starting at the current file
-/// cursor position. Advances the cursor to the start of the next chunk
-/// Relies on `get_next_chunk` by basically calling that function and throwing
-/// away the actual payload
-fn
-get_next_chunk_descriptor
-(
-	file: &mut File
-)
--> Result<RiffChunkDescriptor, std::io::Error>
-{
-	let next_chunk_result = get_next_chunk(file)?;
-	return Ok(next_chunk_result.descriptor());
-}
-
-
-
 /// "Parses" the WebP file by checking various properties:
 /// - Can the file be opened and is the signature valid, including the file size?
 /// - Are the chunks and their size descriptions OK? Relies on the local subroutine `get_next_chunk_descriptor`
@@ -283,7 +299,7 @@ check_exif_in_file
 	// Check the 5th bit of the 32 bit flag_buffer. 
 	// For further details see the Extended File Format section at
 	// https://developers.google.com/speed/webp/docs/riff_container#extended_file_format
-	if flag_buffer[0] & 0x08 != 0x08
+	if flag_buffer[0] & EXIF_FLAG != EXIF_FLAG
 	{
 		return io_error!(Other, "No EXIF chunk according to VP8X flags!");
 	}
@@ -304,7 +320,7 @@ read_metadata
 {
 	// Check the file signature, parse it, check that it has a VP8X chunk and
 	// the EXIF flag is set there
-	let (mut file, parse_webp_result) = check_exif_in_file(path).unwrap();
+	let (mut file, parse_webp_result) = check_exif_in_file(path)?;
 
 	// At this point we have established that the file has to contain an EXIF
 	// chunk at some point. So, now we need to find & return it
@@ -342,19 +358,12 @@ read_metadata
 
 		if chunk_type.to_lowercase() == EXIF_CHUNK_HEADER.to_lowercase()
 		{
-			// Read the EXIF chunk's data into a buffer
+			// Read the EXIF chunk's data into a buffer, which directly starts
+			// with the TIFF header (see `WEBP_EXIF_HEADER`)
 			let mut payload_buffer = vec![0u8; chunk_size];
 			perform_file_action!(file.read(&mut payload_buffer));
 
-			// Add the 6 bytes of the EXIF_HEADER as Prefix for the generic EXIF
-			// data parser that is called on the result of this read function
-			// Otherwise the result would directly start with the Endianness
-			// information, leading to a failed EXIF header signature check in 
-			// the function `decode_metadata_general`
-			let mut raw_exif_data = EXIF_HEADER.to_vec();
-			raw_exif_data.append(&mut payload_buffer);
-
-			return Ok(raw_exif_data);
+			return Ok(payload_buffer);
 		}
 		else
 		{
@@ -376,106 +385,87 @@ read_metadata
 
 
 
-fn
-update_file_size_information
+/// Reads the raw EXIF data from the leading bytes of a WebP file. As the EXIF
+/// chunk is usually located after the image data, this may require most of the
+/// file - however, as the size of each chunk is known in advance, it is not 
+/// necessary to read in the data of the chunks in between.
+/// If the given bytes end before the EXIF data could be read, an error of kind
+/// `UnexpectedEof` states how many bytes are required in total.
+pub(crate) fn
+read_metadata_from_bytes
 (
-	file:  &mut File,
-	delta: i32
+	file_buffer: &[u8]
 )
--> Result<(), std::io::Error>
+-> Result<Vec<u8>, std::io::Error>
 {
-	// Note from the documentation:
-	// As the size of any chunk is even, the size given by the RIFF header is also even.
-
-	// Update the file size information, first by reading in the current value...
-	perform_file_action!(file.seek(SeekFrom::Start(4)));
-	let mut file_size_buffer = [0u8; 4];
-
-	// ...converting it to u32 representation...
-	perform_file_action!(file.read(&mut file_size_buffer));
-	let old_file_size = from_u8_vec_macro!(u32, &file_size_buffer.to_vec(), &Endian::Little);
-
-	// ...adding the delta byte count (and performing some checks)...
-	if delta < 0
+	// RIFF signature, file size and WEBP signature
+	if file_buffer.len() < 12
 	{
-		assert!(old_file_size as i32 > delta);
+		return incomplete_data_error!(12);
 	}
-	let new_file_size = (old_file_size as i32 + delta) as u32;
-
-	assert!(old_file_size % 2 == 0);
-	assert!(new_file_size % 2 == 0);
 
-	// ...and writing back to file...
-	perform_file_action!(file.seek(SeekFrom::Start(4)));
-	perform_file_action!(file.write_all(&to_u8_vec_macro!(u32, &new_file_size, &Endian::Little)));
-
-	Ok(())
-}
-
-
-
-fn
-convert_to_extended_format
-(
-	file: &mut File
-)
--> Result<(), std::io::Error>
-{
-	// Start by getting the first chunk of the WebP file
-	perform_file_action!(file.seek(SeekFrom::Start(12)));
-	let first_chunk_result = get_next_chunk(file);
-
-	// Check that this get operation was successful
-	if first_chunk_result.is_err()
+	if file_buffer[0..4] != RIFF_SIGNATURE || file_buffer[8..12] != WEBP_SIGNATURE
 	{
-		return Err(first_chunk_result.err().unwrap());
+		return io_error!(InvalidData, "Can't open WebP file - Wrong signature!");
 	}
 
-	let first_chunk = first_chunk_result.unwrap();
+	// The size information does not include the RIFF signature and itself
+	let file_size = from_u8_vec_macro!(u32, &file_buffer[4..8].to_vec(), &Endian::Little) as usize + 8;
 
-	// Find out what simple type of WebP file we are dealing with
-	let (width, height) = match first_chunk.descriptor().header().as_str()
+	let mut position = 12usize;
+
+	loop
 	{
-		"VP8" 
-			=> {println!("VP8 !"); todo!()},
-		"VP8L"
-			=> get_dimension_info_from_vp8l_chunk(first_chunk.payload()),
-		_ 
-			=> io_error!(Other, "Expected either 'VP8 ' or 'VP8L' chunk for conversion!")
-	}?;
+		if position >= file_size
+		{
+			break;
+		}
 
-	println!("{} {}", width, height);
+		// Read the chunk type and its size
+		if file_buffer.len() < position + 8
+		{
+			return incomplete_data_error!(position + 8);
+		}
 
-	let width_vec  = to_u8_vec_macro!(u32, &width,  &Endian::Little);
-	let height_vec = to_u8_vec_macro!(u32, &height, &Endian::Little);
+		let chunk_type = String::from_u8_vec(&file_buffer[position..(position+4)].to_vec(), &Endian::Little);
+		let chunk_size = from_u8_vec_macro!(u32, &file_buffer[(position+4)..(position+8)].to_vec(), &Endian::Little) as usize;
 
-	let mut vp8x_chunk = vec![
-		0x56, 0x50, 0x38, 0x58, // ASCII chars "V", "P", "8", "X"                  -> 4 byte
-		0x0A, 0x00, 0x00, 0x00, // size of this chunk (32 + 24 + 24 bit = 10 byte) -> 4 byte
-		0x00, 0x00, 0x00, 0x00, // Flags and reserved area                         -> 4 byte
-	];
+		// A simple file format WebP can't contain any EXIF metadata and the
+		// flags of the VP8X chunk tell whether there is EXIF data at all
+		if position == 12
+		{
+			if chunk_type.to_lowercase() != VP8X_HEADER.to_lowercase()
+			{
+				break;
+			}
 
-	// Add the two 24 bits for width and height information
-	for i in 0..3 { vp8x_chunk.push(width_vec[i]); }
-	for i in 0..3 { vp8x_chunk.push(height_vec[i]); }
+			if file_buffer.len() < position + 9
+			{
+				return incomplete_data_error!(position + 9);
+			}
 
-	// Write the VP8X chunk, first by reading the file (except for the header)
-	// into a buffer...
-	let mut buffer = Vec::new();
-	perform_file_action!(file.seek(SeekFrom::Start(12u64)));
-	perform_file_action!(file.read_to_end(&mut buffer));
+			if file_buffer[position + 8] & EXIF_FLAG != EXIF_FLAG
+			{
+				break;
+			}
+		}
 
-	// ...actually writing the VP8X chunk data...
-	perform_file_action!(file.seek(SeekFrom::Start(12u64)));
-	perform_file_action!(file.write(&vp8x_chunk));
+		if chunk_type.to_lowercase() == EXIF_CHUNK_HEADER.to_lowercase()
+		{
+			if file_buffer.len() < position + 8 + chunk_size
+			{
+				return incomplete_data_error!(position + 8 + chunk_size);
+			}
 
-	// ...and writing back the file contents
-	perform_file_action!(file.write(&buffer));
+			// No EXIF header to skip, see `read_metadata`
+			return Ok(file_buffer[(position+8)..(position+8+chunk_size)].to_vec());
+		}
 
-	// Finally, update the file size information
-	update_file_size_information(file, 18)?;
+		// Skip the entire chunk, including the possible padding byte
+		position += 8 + chunk_size + chunk_size % 2;
+	}
 
-	Ok(())
+	return io_error!(Other, "No EXIF data found!");
 }
 
 
@@ -516,297 +506,462 @@ get_dimension_info_from_vp8l_chunk
 
 
 
-fn
-set_exif_flag
+/// Removes all EXIF chunks from the WebP image file at the specified path and
+/// clears the EXIF flag of its VP8X chunk, see `clear_metadata_from_bytes`
+pub(crate) fn
+clear_metadata
 (
-	path:  &Path,
-	exif_flag_value: bool
+	path: &Path
 )
 -> Result<(), std::io::Error>
 {
-	// Parse the WebP file - if this fails, we surely can't read any metadata
-	let parsed_webp_result = parse_webp(path);
-	if let Err(error) = parsed_webp_result
-	{
-		return Err(error);
-	}
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = clear_metadata_from_bytes(&file_buffer)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
+	return Ok(());
+}
 
-	// Open the file for further processing
-	let mut file = check_signature(path).unwrap();
 
-	// Next, check if this is an Extended File Format WebP file
-	// In this case, the first Chunk SHOULD have the type "VP8X"
-	// Otherwise we have to create the VP8X chunk!
-	if let Some(first_chunk) = parsed_webp_result.as_ref().unwrap().first()
-	{
-		// Compare the chunk descriptor header and call chunk creator if required
-		if first_chunk.header().to_lowercase() != VP8X_HEADER.to_lowercase()
-		{
-			convert_to_extended_format(&mut file)?;
-		}
-	}
-	else
-	{
-		return io_error!(Other, "Could not read first chunk descriptor of WebP file!");
-	}	
 
-	// At this point we know that we have a VP8X chunk at the expected location
-	// So, read in the flags and set the EXIF flag accoring to the given bool
-	let mut flag_buffer = vec![0u8; 4usize];
-	perform_file_action!(file.seek(SeekFrom::Start(12u64 + 4u64 + 4u64)));
-	if file.read(&mut flag_buffer).unwrap() != 4
-	{
-		return io_error!(Other, "Could not read flags of VP8X chunk!");
-	}
+/// Encodes a chunk of the given type containing the given data, consisting
+/// of its fourCC, its size information, the data and a possible padding byte
+fn
+encode_chunk
+(
+	chunk_header: &str,
+	chunk_data:   &[u8]
+)
+-> Vec<u8>
+{
+	// Vector storing the data that will be returned
+	let mut chunk: Vec<u8> = Vec::new();
 
-	// Mask the old flag by either or-ing with 1 at the EXIF flag position for
-	// setting it to true, or and-ing with 1 everywhere but the EXIF flag pos
-	// to set it to false
-	flag_buffer[0] = if exif_flag_value
+	// Compute the length of the chunk data
+	// This does NOT include the fourCC and size information of that chunk 
+	// Also does NOT include the padding byte, i.e. this value may be odd!
+	let length = chunk_data.len() as u32;
+
+	// Start with the fourCC chunk head and the size information.
+	// Then copy the given data 
+	chunk.extend(chunk_header.as_bytes());
+	chunk.extend(to_u8_vec_macro!(u32, &length, &Endian::Little));
+	chunk.extend(chunk_data.iter());
+
+	// Add the padding byte if required
+	if length % 2 != 0
 	{
-		flag_buffer[0] | 0x08
+		chunk.extend([0x00]);
 	}
-	else
-	{
-		flag_buffer[0] & 0b11110111
-	};
 
-	// Write flag buffer back to the file
-	perform_file_action!(file.seek(SeekFrom::Start(12u64 + 4u64 + 4u64)));
-	perform_file_action!(file.write_all(&flag_buffer));
-
-	Ok(())
+	return chunk;
 }
 
 
 
+/// Provides the WebP specific encoding result as vector of bytes to be used
+/// by the user (e.g. in combination with another library)
 pub(crate) fn
-clear_metadata
+as_u8_vec
 (
-	path: &Path
+	general_encoded_metadata: &Vec<u8>
+)
+-> Vec<u8>
+{
+	encode_chunk(EXIF_CHUNK_HEADER, general_encoded_metadata)
+}
+
+
+
+/// Checks that a chunk of the given type with the given length of its data
+/// fits into a WebP file, whose size information (i.e. the size of the file
+/// without the RIFF signature and the size information itself) would be the
+/// given one without any chunks of that type.
+fn
+check_chunk_size
+(
+	chunk_header:            &str,
+	chunk_length:            usize,
+	riff_size_without_chunk: u64
 )
 -> Result<(), std::io::Error>
 {
-	// Check the file signature, parse it, check that it has a VP8X chunk and
-	// the EXIF flag is set there
-	let exif_check_result = check_exif_in_file(path);
-	if exif_check_result.is_err()
+	// fourCC, size information, data and possible padding byte
+	let chunk_size = 8 + chunk_length as u64 + chunk_length as u64 % 2;
+
+	if chunk_length as u64 > RIFF_MAX_SIZE || riff_size_without_chunk + chunk_size > RIFF_MAX_SIZE
 	{
-		match exif_check_result.as_ref().err().unwrap().to_string().as_str()
-		{
-			"No EXIF chunk according to VP8X flags!"
-				=> return Ok(()),
-			"Expected first chunk of WebP file to be of type 'VP8X' but instead got VP8L!"
-				=> return Ok(()),
-			_
-				=> return Err(exif_check_result.err().unwrap())
-		}
+		return io_error!(InvalidInput, format!(
+			"Can't write WebP {} chunk with {} bytes of data - The file would exceed the maximum RIFF size of {} bytes!",
+			chunk_header.trim_end(), chunk_length, RIFF_MAX_SIZE
+		));
 	}
+	return Ok(());
+}
+
+/// Writes the given generally encoded metadata to the WebP image file at 
+/// the specified path. 
+/// Note that *all* previously stored EXIF metadata gets removed first before
+/// writing the "new" metadata. 
+pub(crate) fn
+write_metadata
+(
+	path:                     &Path,
+	general_encoded_metadata: &Vec<u8>
+)
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = write_metadata_to_bytes(&file_buffer, general_encoded_metadata)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
+	return Ok(());
+}
 
-	let (mut file, parse_webp_result) = exif_check_result.unwrap();
 
-	// Compute a delta of how much the file size information has to change
-	let mut delta = 0i32;
 
-	// Skip the WEBP signature
-	perform_file_action!(file.seek(SeekFrom::Current(4i64)));
 
-	for parsed_chunk in parse_webp_result
+
+/// Splits the WebP data in the given buffer into its chunks, each with its
+/// type and raw bytes (including the type, size information and possible
+/// padding byte). Checks the signature and the size information first.
+fn
+split_into_chunks
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<RawChunk<'_>>, std::io::Error>
+{
+	if file_buffer.len() < 12 || file_buffer[0..4] != RIFF_SIGNATURE || file_buffer[8..12] != WEBP_SIGNATURE
 	{
-		// At the start of each iteration, the file cursor is at the start of
-		// the fourCC section of a chunk
+		return io_error!(InvalidData, "Can't open WebP file - Wrong signature!");
+	}
 
-		// Compute how many bytes this chunk has
-		let parsed_chunk_byte_count = 
-			4u64                            // fourCC section of EXIF chunk
-			+ 4u64                          // size information of EXIF chunk
-			+ parsed_chunk.len() as u64     // actual size of EXIF chunk data
-			+ parsed_chunk.len() as u64 % 2 // accounting for possible padding byte
-		;
+	let file_size = from_u8_vec_macro!(u32, &file_buffer[4..8].to_vec(), &Endian::Little) as usize + 8;
+	if file_size != file_buffer.len()
+	{
+		return io_error!(InvalidData, "Can't open WebP file - Promised byte count does not correspond with file size!");
+	}
 
-		// Not an EXIF chunk, seek to next one and continue
-		if parsed_chunk.header().to_lowercase() != EXIF_CHUNK_HEADER.to_lowercase()
+	let mut chunks: Vec<RawChunk> = Vec::new();
+	let mut position = 12usize;
+	while position < file_size
+	{
+		if file_size < position + 8
 		{
-			perform_file_action!(file.seek(SeekFrom::Current(parsed_chunk_byte_count as i64)));
-			continue;
+			return io_error!(InvalidData, "Could not read start of chunk");
 		}
 
-		// Get the current size of the file in bytes
-		let old_file_byte_count = file.metadata().unwrap().len();
+		let chunk_type = String::from_u8_vec(&file_buffer[position..(position+4)].to_vec(), &Endian::Little);
+		let chunk_size = from_u8_vec_macro!(u32, &file_buffer[(position+4)..(position+8)].to_vec(), &Endian::Little) as usize;
+		let chunk_end  = position + 8 + chunk_size + chunk_size % 2;
+		if file_size < chunk_end
+		{
+			return io_error!(InvalidData, "Could not read RIFF chunk data!");
+		}
 
-		// Get a backup of the current cursor position
-		let exif_chunk_start_cursor_position = SeekFrom::Start(file.seek(SeekFrom::Current(0)).unwrap());
+		chunks.push((chunk_type, &file_buffer[position..chunk_end]));
+		position = chunk_end;
+	}
 
-		// Skip the EXIF chunk ...
-		perform_file_action!(file.seek(SeekFrom::Current(parsed_chunk_byte_count as i64)));
+	return Ok(chunks);
+}
 
-		// ...and copy everything afterwards into a buffer...
-		let mut buffer = Vec::new();
-		perform_file_action!(file.read_to_end(&mut buffer));
+/// Removes all EXIF chunks from the WebP data in the given buffer and clears
+/// the EXIF flag of its VP8X chunk (if there is one), returning the resulting
+/// file contents.
+pub(crate) fn
+clear_metadata_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return remove_chunks_from_bytes(file_buffer, EXIF_CHUNK_HEADER, EXIF_FLAG);
+}
 
-		// ...and seek back to where the EXIF chunk is located...
-		perform_file_action!(file.seek(exif_chunk_start_cursor_position));
+/// Removes all chunks of the given type from the WebP data in the given buffer
+/// and clears the given flag of its VP8X chunk (if there is one), returning 
+/// the resulting file contents.
+fn
+remove_chunks_from_bytes
+(
+	file_buffer:  &[u8],
+	chunk_header: &str,
+	vp8x_flag:    u8
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	let chunks = split_into_chunks(file_buffer)?;
 
-		// ...and overwrite the EXIF chunk...
-		perform_file_action!(file.write_all(&buffer));
+	let mut new_file_buffer: Vec<u8> = Vec::new();
+	new_file_buffer.extend(RIFF_SIGNATURE.iter());
+	new_file_buffer.extend([0x00, 0x00, 0x00, 0x00]);                           // File size, gets updated later on
+	new_file_buffer.extend(WEBP_SIGNATURE.iter());
 
-		// ...and finally update the size of the file
-		perform_file_action!(file.set_len(old_file_byte_count - parsed_chunk_byte_count));
+	for (chunk_type, chunk) in chunks
+	{
+		if chunk_type.to_lowercase() == chunk_header.to_lowercase()
+		{
+			continue;
+		}
+
+		let chunk_start = new_file_buffer.len();
+		new_file_buffer.extend(chunk.iter());
 
-		// Additionally, update the size information that gets written to the 
-		// file header after this loop
-		delta -= parsed_chunk_byte_count as i32;
+		if chunk_type.to_lowercase() == VP8X_HEADER.to_lowercase() && chunk.len() >= 9
+		{
+			new_file_buffer[chunk_start + 8] &= !vp8x_flag;
+		}
 	}
 
-	// Update file size information
-	update_file_size_information(&mut file, delta)?;
-	
-	// Set the flags in the VP8X chunk. First, read in the current flags
-	perform_file_action!(set_exif_flag(path, false));
+	// The size information does not include the RIFF signature and itself
+	let new_file_size = new_file_buffer.len() as u32 - 8;
+	new_file_buffer[4..8].copy_from_slice(&to_u8_vec_macro!(u32, &new_file_size, &Endian::Little));
 
-	return Ok(());
+	return Ok(new_file_buffer);
 }
 
-
-
+/// Gets the VP8X chunk of the given chunks and the chunks following it. A
+/// simple file format WebP needs a new VP8X chunk in front of its VP8L chunk,
+/// which gets constructed with all flags cleared and is followed by all of
+/// the given chunks.
 fn
-encode_metadata_webp
+split_off_vp8x_chunk<'a>
 (
-	exif_vec: &Vec<u8>
+	chunks: &'a [RawChunk<'a>]
 )
--> Vec<u8>
+-> Result<(Vec<u8>, &'a [RawChunk<'a>]), std::io::Error>
 {
-	// Vector storing the data that will be returned
-	let mut webp_exif: Vec<u8> = Vec::new();
-
-	// Compute the length of the exif data chunk 
-	// This does NOT include the fourCC and size information of that chunk 
-	// Also does NOT include the padding byte, i.e. this value may be odd!
-	let length = exif_vec.len() as u32;
-
-	// Start with the fourCC chunk head and the size information.
-	// Then copy the previously encoded EXIF data 
-	webp_exif.extend([0x45, 0x58, 0x49, 0x46]);
-	webp_exif.extend(to_u8_vec_macro!(u32, &length, &Endian::Little));
-	webp_exif.extend(exif_vec.iter());
+	let (vp8x_chunk, other_chunks) = match chunks.first()
+	{
+		Some((chunk_type, chunk)) if chunk_type.to_lowercase() == VP8X_HEADER.to_lowercase()
+			=> (chunk.to_vec(), &chunks[1..]),
+		Some((chunk_type, chunk)) if chunk_type == "VP8L"
+			=> {
+				let (width, height) = get_dimension_info_from_vp8l_chunk(&chunk[8..].to_vec())?;
+				let width_vec  = to_u8_vec_macro!(u32, &width,  &Endian::Little);
+				let height_vec = to_u8_vec_macro!(u32, &height, &Endian::Little);
+
+				let mut vp8x_chunk = vec![
+					0x56, 0x50, 0x38, 0x58, // ASCII chars "V", "P", "8", "X"                  -> 4 byte
+					0x0A, 0x00, 0x00, 0x00, // size of this chunk (32 + 24 + 24 bit = 10 byte) -> 4 byte
+					0x00, 0x00, 0x00, 0x00, // Flags and reserved area                         -> 4 byte
+				];
+				vp8x_chunk.extend(width_vec[0..3].iter());
+				vp8x_chunk.extend(height_vec[0..3].iter());
+
+				(vp8x_chunk, chunks)
+			},
+		_
+			=> return io_error!(Unsupported, "Expected either 'VP8X' or 'VP8L' chunk for writing!"),
+	};
 
-	// Add the padding byte if required
-	if length % 2 != 0
+	if vp8x_chunk.len() < 9
 	{
-		webp_exif.extend([0x00]);
+		return io_error!(InvalidData, "Could not read flags of VP8X chunk!");
 	}
 
-	return webp_exif;
+	return Ok((vp8x_chunk, other_chunks));
 }
 
-
-
-/// Provides the WebP specific encoding result as vector of bytes to be used
-/// by the user (e.g. in combination with another library)
+/// Writes the given generally encoded metadata to the WebP data in the given
+/// buffer, returning the resulting file contents. Like for `write_metadata`,
+/// all previously stored EXIF chunks get removed, a simple file format WebP 
+/// gets converted to the extended file format and the new EXIF chunk is
+/// placed after the chunks that have to precede it.
 pub(crate) fn
-as_u8_vec
+write_metadata_to_bytes
 (
+	file_buffer:              &[u8],
 	general_encoded_metadata: &Vec<u8>
 )
--> Vec<u8>
+-> Result<Vec<u8>, std::io::Error>
 {
-	encode_metadata_webp(general_encoded_metadata)
+	return insert_chunk_into_bytes(file_buffer, general_encoded_metadata, EXIF_CHUNK_HEADER, EXIF_FLAG, &PRE_EXIF_CHUNKS);
 }
 
-
-
-/// Writes the given generally encoded metadata to the WebP image file at 
-/// the specified path. 
-/// Note that *all* previously stored EXIF metadata gets removed first before
-/// writing the "new" metadata. 
-pub(crate) fn
-write_metadata
+/// Inserts a chunk of the given type with the given data into the WebP data
+/// in the given buffer, returning the resulting file contents. All chunks of
+/// that type get removed first, a simple file format WebP gets converted to
+/// the extended file format and the given flag of its VP8X chunk is set. 
+/// The new chunk is placed after the chunks of the given types that have to
+/// precede it.
+fn
+insert_chunk_into_bytes
 (
-	path:                     &Path,
-	general_encoded_metadata: &Vec<u8>
+	file_buffer:  &[u8],
+	chunk_data:   &[u8],
+	chunk_header: &str,
+	vp8x_flag:    u8,
+	pre_chunks:   &[&str]
 )
--> Result<(), std::io::Error>
+-> Result<Vec<u8>, std::io::Error>
 {
-	// Clear the metadata from the file and return if this results in an error
-	clear_metadata(path)?;
-
-	// Encode the general metadata format to WebP specifications
-	let encoded_metadata = encode_metadata_webp(general_encoded_metadata);
-
-	// Open the file...
-	let mut file = check_signature(path)?;
-
-	// ...and find a location where to put the EXIF chunk
-	// This is done by requesting a chunk descriptor as long as we find a chunk
-	// that is both known and should be located *before* the EXIF chunk
-	let pre_exif_chunks = [
-		"VP8X",
-		"VP8",
-		"VP8L",
-		"ICCP",
-		"ANIM"
-	];
+	let chunks = split_into_chunks(file_buffer)?;
 
-	loop
-	{
-		// Request a chunk descriptor. If this fails, check the error 
-		// Depending on its type, either continue normally or return it
-		let chunk_descriptor_result = get_next_chunk_descriptor(&mut file);
+	// Make sure that the first chunk is a VP8X chunk, with the flag set
+	let (mut vp8x_chunk, other_chunks) = split_off_vp8x_chunk(&chunks)?;
+	vp8x_chunk[8] |= vp8x_flag;
 
-		if let Ok(chunk_descriptor) = chunk_descriptor_result
-		{
-			let mut chunk_type_found_in_pre_exif_chunks = false;
+	let riff_size_without_chunk = other_chunks.iter()
+		.filter(|(chunk_type, _)| chunk_type.to_lowercase() != chunk_header.to_lowercase())
+		.map(|(_, chunk)| chunk.len() as u64)
+		.sum::<u64>() + (WEBP_SIGNATURE.len() + vp8x_chunk.len()) as u64;
+	check_chunk_size(chunk_header, chunk_data.len(), riff_size_without_chunk)?;
 
-			// Check header of chunk descriptor against any of the known chunks
-			// that should come before the EXIF chunk
-			for pre_exif_chunk in &pre_exif_chunks
-			{
-				chunk_type_found_in_pre_exif_chunks |= pre_exif_chunk.to_lowercase() == chunk_descriptor.header().to_lowercase();
-			}
+	let new_chunk = encode_chunk(chunk_header, chunk_data);
 
-			if !chunk_type_found_in_pre_exif_chunks
-			{
-				break;
-			}
+	let mut new_file_buffer: Vec<u8> = Vec::new();
+	new_file_buffer.extend(RIFF_SIGNATURE.iter());
+	new_file_buffer.extend([0x00, 0x00, 0x00, 0x00]);                           // File size, gets updated later on
+	new_file_buffer.extend(WEBP_SIGNATURE.iter());
+	new_file_buffer.extend(vp8x_chunk.iter());
+
+	let mut new_chunk_written = false;
+	for (chunk_type, chunk) in other_chunks
+	{
+		// Remove all previously stored chunks of that type
+		if chunk_type.to_lowercase() == chunk_header.to_lowercase()
+		{
+			continue;
 		}
-		else
+
+		let is_pre_chunk = pre_chunks.iter().any(|pre_chunk| pre_chunk.to_lowercase() == chunk_type.to_lowercase());
+		if !is_pre_chunk && !new_chunk_written
 		{
-			match chunk_descriptor_result.as_ref().err().unwrap().kind()
-			{
-				std::io::ErrorKind::UnexpectedEof
-					=> break, // No further chunks, place EXIF chunk here
-				_
-					=> return Err(chunk_descriptor_result.err().unwrap())
-			}
+			new_file_buffer.extend(new_chunk.iter());
+			new_chunk_written = true;
 		}
+
+		new_file_buffer.extend(chunk.iter());
 	}
 
-	// Next, read remaining file into a buffer...
-	let current_file_cursor = SeekFrom::Start(file.seek(SeekFrom::Current(0)).unwrap());
-	let mut read_buffer = Vec::new();
-	perform_file_action!(file.read_to_end(&mut read_buffer));
+	if !new_chunk_written
+	{
+		new_file_buffer.extend(new_chunk.iter());
+	}
 
-	// ...and write the EXIF chunk at the previously found location...
-	perform_file_action!(file.seek(current_file_cursor));
-	perform_file_action!(file.write_all(&encoded_metadata));
+	// The size information does not include the RIFF signature and itself
+	let new_file_size = new_file_buffer.len() as u32 - 8;
+	new_file_buffer[4..8].copy_from_slice(&to_u8_vec_macro!(u32, &new_file_size, &Endian::Little));
 
-	// ...and writing back the remaining file content
-	perform_file_action!(file.write_all(&read_buffer));
+	return Ok(new_file_buffer);
+}
 
-	// Update the file size information by adding the byte count of the EXIF chunk
-	// (Note: Due to  the WebP specific encoding function, this vector already
-	// contains the EXIF header characters and size information, as well as the
-	// possible padding byte. Therefore, simply taking the length of this
-	// vector takes their byte count also into account and no further values
-	// need to be added)
-	update_file_size_information(&mut file, encoded_metadata.len() as i32)?;
 
-	// Finally, set the EXIF flag
-	perform_file_action!(set_exif_flag(path, true));
+/// Reads the XMP data of the WebP file at the given path, i.e. the payload of
+/// its `XMP ` chunk. Returns `None` if the file has no such chunk. Like for
+/// the EXIF data, only the first chunk is considered if there are several.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::webp::read_xmp;
+///
+/// if let Some(xmp) = read_xmp(std::path::Path::new("image.webp")).unwrap()
+/// {
+///     println!("{}", String::from_utf8_lossy(&xmp));
+/// }
+/// ```
+pub fn
+read_xmp
+(
+	path: &Path
+)
+-> Result<Option<Vec<u8>>, std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	return read_xmp_from_bytes(&file_buffer);
+}
 
+/// Writes the given XMP data to the WebP file at the given path, replacing all
+/// previously stored XMP chunks. A simple file format WebP gets converted to
+/// the extended file format and the XMP flag of its VP8X chunk is set. Other
+/// chunks, including the EXIF chunk and its flag, are left untouched.
+///
+/// # Examples
+/// ```no_run
+/// use revolt_little_exif::webp::write_xmp;
+///
+/// let xmp = std::fs::read("image.xmp").unwrap();
+/// write_xmp(std::path::Path::new("image.webp"), &xmp).unwrap();
+/// ```
+pub fn
+write_xmp
+(
+	path: &Path,
+	xmp:  &[u8]
+)
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = write_xmp_to_bytes(&file_buffer, xmp)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
 	return Ok(());
 }
 
+/// Removes all XMP chunks from the WebP file at the given path and clears the
+/// XMP flag of its VP8X chunk (if there is one). Other chunks, including the
+/// EXIF chunk and its flag, are left untouched.
+pub fn
+clear_xmp
+(
+	path: &Path
+)
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read(path)?;
+	let new_file_buffer = clear_xmp_from_bytes(&file_buffer)?;
+	perform_file_action!(std::fs::write(path, new_file_buffer));
+	return Ok(());
+}
+
+/// Reads the payload of the first `XMP ` chunk of the WebP data in the given
+/// buffer, without the possible padding byte
+pub(crate) fn
+read_xmp_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Option<Vec<u8>>, std::io::Error>
+{
+	for (chunk_type, chunk) in split_into_chunks(file_buffer)?
+	{
+		if chunk_type.to_lowercase() == XMP_CHUNK_HEADER.to_lowercase()
+		{
+			let chunk_size = from_u8_vec_macro!(u32, &chunk[4..8].to_vec(), &Endian::Little) as usize;
+			return Ok(Some(chunk[8..(8+chunk_size)].to_vec()));
+		}
+	}
+
+	return Ok(None);
+}
+
+/// Writes the given XMP data to the WebP data in the given buffer, returning
+/// the resulting file contents. The XMP chunk is placed after the EXIF chunk
+/// and the other chunks that have to precede it, see `write_metadata`.
+pub(crate) fn
+write_xmp_to_bytes
+(
+	file_buffer: &[u8],
+	xmp:         &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return insert_chunk_into_bytes(file_buffer, xmp, XMP_CHUNK_HEADER, XMP_FLAG, &PRE_XMP_CHUNKS);
+}
+
+/// Removes all XMP chunks from the WebP data in the given buffer and clears
+/// the XMP flag of its VP8X chunk (if there is one), returning the resulting
+/// file contents.
+pub(crate) fn
+clear_xmp_from_bytes
+(
+	file_buffer: &[u8]
+)
+-> Result<Vec<u8>, std::io::Error>
+{
+	return remove_chunks_from_bytes(file_buffer, XMP_CHUNK_HEADER, XMP_FLAG);
+}
+
 
 
 
@@ -835,4 +990,14 @@ mod tests
 
 		Ok(())
 	}
+
+	#[test]
+	fn
+	check_chunk_size()
+	{
+		assert!(crate::webp::check_chunk_size("EXIF", 100, 1000).is_ok());
+		assert!(crate::webp::check_chunk_size("EXIF", 0xffff_ffff - 1000 - 8, 1000).is_err());
+		assert!(crate::webp::check_chunk_size("EXIF", 0xffff_ffff - 1000 - 9, 1000).is_ok());
+		assert!(crate::webp::check_chunk_size("XMP ", 0x1_0000_0000, 0).is_err());
+	}
 }
\ No newline at end of file
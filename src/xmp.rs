@@ -0,0 +1,337 @@
+// Copyright © 2024 Tobias J. Prisching <tobias.prisching@icloud.com> and CONTRIBUTORS
+// See https://github.com/TechnikTobi/little_exif#license for licensing details
+
+//! Editing the XMP properties that are used for tagging photos but have no
+//! EXIF counterpart: the rating (`xmp:Rating`), the keywords (`dc:subject`)
+//! and the creator (`dc:creator`). Currently, these can only be stored in
+//! WebP files, see `webp::read_xmp` and `webp::write_xmp`.
+//!
+//! This is not a general XMP implementation: Only these three properties are
+//! read from an existing XMP packet, and writing replaces the whole packet.
+//!
+//! # Examples
+//! ```no_run
+//! use revolt_little_exif::metadata::Metadata;
+//! use revolt_little_exif::exif_tag::ExifTag;
+//! use revolt_little_exif::xmp::Xmp;
+//!
+//! let path = std::path::Path::new("image.webp");
+//! let mut metadata = Metadata::new_from_path(path).unwrap();
+//! metadata.set_tag(ExifTag::Artist("Jane Doe".to_string()));
+//!
+//! let mut xmp = Xmp::new_from_path(path).unwrap();
+//! xmp.set_rating(4).unwrap();
+//! xmp.add_keyword("Vacation");
+//! xmp.set_creator("Jane Doe");
+//!
+//! metadata.write_to_file_with_xmp(path, &xmp).unwrap();
+//! ```
+
+use std::path::Path;
+
+use crate::general_file_io::*;
+use crate::webp;
+
+// The highest rating, whereas 0 stands for an unrated image
+const MAX_RATING: u8 = 5;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct
+Xmp
+{
+	rating:   Option<u8>,
+	keywords: Vec<String>,
+	creator:  Option<String>,
+}
+
+impl
+Xmp
+{
+	/// Constructs a new `Xmp` object without any properties
+	pub fn
+	new
+	()
+	-> Xmp
+	{
+		Xmp::default()
+	}
+
+	/// Constructs a new `Xmp` object with the properties of the XMP packet of
+	/// the WebP file at the specified path. If the file has no XMP packet,
+	/// the object is empty.
+	pub fn
+	new_from_path
+	(
+		path: &Path
+	)
+	-> Result<Xmp, std::io::Error>
+	{
+		return Ok(match webp::read_xmp(path)?
+		{
+			Some(xmp) => Self::from_u8_vec(&xmp),
+			None      => Self::new(),
+		});
+	}
+
+	/// Constructs a new `Xmp` object with the properties found in the given
+	/// XMP packet. The rating may be given as attribute or as element of its
+	/// `rdf:Description`, the keywords and the creator as items of the list
+	/// of their element. Values that are malformed are left out.
+	pub fn
+	from_u8_vec
+	(
+		xmp: &[u8]
+	)
+	-> Xmp
+	{
+		let packet = String::from_utf8_lossy(xmp);
+
+		let rating = find_property_value(&packet, "xmp:Rating")
+			.and_then(|rating| rating.trim().parse::<u8>().ok())
+			.filter(|rating| *rating <= MAX_RATING);
+
+		return Xmp {
+			rating,
+			keywords: find_list_items(&packet, "dc:subject"),
+			creator:  find_list_items(&packet, "dc:creator").into_iter().next(),
+		};
+	}
+
+	/// Encodes the properties as XMP packet, e.g. for `webp::write_xmp`
+	pub fn
+	as_u8_vec
+	(
+		&self
+	)
+	-> Vec<u8>
+	{
+		let mut properties = String::new();
+
+		if let Some(rating) = self.rating
+		{
+			properties += &format!("   <xmp:Rating>{}</xmp:Rating>\n", rating);
+		}
+
+		if let Some(creator) = &self.creator
+		{
+			properties += &format!("   <dc:creator><rdf:Seq><rdf:li>{}</rdf:li></rdf:Seq></dc:creator>\n", escape(creator));
+		}
+
+		if !self.keywords.is_empty()
+		{
+			let items = self.keywords.iter()
+				.map(|keyword| format!("<rdf:li>{}</rdf:li>", escape(keyword)))
+				.collect::<String>();
+			properties += &format!("   <dc:subject><rdf:Bag>{}</rdf:Bag></dc:subject>\n", items);
+		}
+
+		return format!(concat!(
+			"<?xpacket begin=\"\u{feff}\" id=\"W5M0MpCehiHzreSzNTczkc9d\"?>\n",
+			"<x:xmpmeta xmlns:x=\"adobe:ns:meta/\">\n",
+			" <rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\">\n",
+			"  <rdf:Description rdf:about=\"\"\n",
+			"    xmlns:xmp=\"http://ns.adobe.com/xap/1.0/\"\n",
+			"    xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n",
+			"{}",
+			"  </rdf:Description>\n",
+			" </rdf:RDF>\n",
+			"</x:xmpmeta>\n",
+			"<?xpacket end=\"w\"?>"
+		), properties).into_bytes();
+	}
+
+	/// Gets the rating, from 0 (unrated) to 5
+	pub fn
+	rating
+	(
+		&self
+	)
+	-> Option<u8>
+	{
+		self.rating
+	}
+
+	/// Sets the rating, from 0 (unrated) to 5. Returns an error for any
+	/// other value.
+	pub fn
+	set_rating
+	(
+		&mut self,
+		rating: u8
+	)
+	-> Result<(), std::io::Error>
+	{
+		if rating > MAX_RATING
+		{
+			return io_error!(InvalidInput, format!("Can't set rating {} - Must be between 0 and {}!", rating, MAX_RATING));
+		}
+		self.rating = Some(rating);
+		return Ok(());
+	}
+
+	/// Gets the keywords in the order they were added
+	pub fn
+	keywords
+	(
+		&self
+	)
+	-> &[String]
+	{
+		&self.keywords
+	}
+
+	/// Adds the given keyword, unless it is already present
+	pub fn
+	add_keyword
+	(
+		&mut self,
+		keyword: &str
+	)
+	{
+		if !self.keywords.iter().any(|existing| existing == keyword)
+		{
+			self.keywords.push(keyword.to_string());
+		}
+	}
+
+	/// Gets the creator
+	pub fn
+	creator
+	(
+		&self
+	)
+	-> Option<&str>
+	{
+		self.creator.as_deref()
+	}
+
+	/// Sets the creator, replacing the previous one(s)
+	pub fn
+	set_creator
+	(
+		&mut self,
+		creator: &str
+	)
+	{
+		self.creator = Some(creator.to_string());
+	}
+
+	/// Writes the properties as XMP packet to the WebP file at the specified
+	/// path, replacing its previous XMP packet. The EXIF data is left
+	/// untouched, see `Metadata::write_to_file_with_xmp` for writing both.
+	pub fn
+	write_to_file
+	(
+		&self,
+		path: &Path
+	)
+	-> Result<(), std::io::Error>
+	{
+		return webp::write_xmp(path, &self.as_u8_vec());
+	}
+}
+
+/// Replaces the characters that can't be part of XML text by their entities
+fn
+escape
+(
+	text: &str
+)
+-> String
+{
+	return text
+		.replace('&',  "&amp;")
+		.replace('<',  "&lt;")
+		.replace('>',  "&gt;")
+		.replace('"',  "&quot;")
+		.replace('\'', "&apos;");
+}
+
+/// Reverses `escape`
+fn
+unescape
+(
+	text: &str
+)
+-> String
+{
+	return text
+		.replace("&lt;",   "<")
+		.replace("&gt;",   ">")
+		.replace("&quot;", "\"")
+		.replace("&apos;", "'")
+		.replace("&amp;",  "&");
+}
+
+/// Finds the value of the simple property with the given name, which is
+/// given either as attribute (`name="value"`) or as element
+/// (`<name>value</name>`)
+fn
+find_property_value
+(
+	packet: &str,
+	name:   &str
+)
+-> Option<String>
+{
+	let attribute = format!("{}=\"", name);
+	if let Some(start) = packet.find(&attribute).map(|position| position + attribute.len())
+	{
+		let end = packet[start..].find('"')?;
+		return Some(unescape(&packet[start..(start+end)]));
+	}
+
+	let opening_tag = format!("<{}>", name);
+	let closing_tag = format!("</{}>", name);
+	let start = packet.find(&opening_tag)? + opening_tag.len();
+	let end   = packet[start..].find(&closing_tag)?;
+	return Some(unescape(&packet[start..(start+end)]));
+}
+
+/// Finds the items (`rdf:li`) of the list that is the value of the property
+/// with the given name
+fn
+find_list_items
+(
+	packet: &str,
+	name:   &str
+)
+-> Vec<String>
+{
+	let opening_tag = format!("<{}>", name);
+	let closing_tag = format!("</{}>", name);
+
+	let Some(start) = packet.find(&opening_tag).map(|position| position + opening_tag.len())
+	else
+	{
+		return Vec::new();
+	};
+	let Some(end) = packet[start..].find(&closing_tag)
+	else
+	{
+		return Vec::new();
+	};
+
+	// Items may have attributes, e.g. `xml:lang`
+	let mut items = Vec::new();
+	let mut list  = &packet[start..(start+end)];
+	while let Some(item_start) = list.find("<rdf:li")
+	{
+		list = &list[item_start..];
+		let Some(text_start) = list.find('>').map(|position| position + 1)
+		else
+		{
+			break;
+		};
+		let Some(text_end) = list[text_start..].find("</rdf:li>")
+		else
+		{
+			break;
+		};
+
+		items.push(unescape(&list[text_start..(text_start+text_end)]));
+		list = &list[(text_start+text_end)..];
+	}
+
+	return items;
+}
@@ -5,9 +5,12 @@ use std::fs::copy;
 use std::fs::remove_file;
 use std::path::Path;
 
-extern crate little_exif;
+extern crate revolt_little_exif as little_exif;
 use little_exif::metadata::Metadata;
 use little_exif::exif_tag::ExifTag;
+use little_exif::filetype::FileExtension;
+use little_exif::decode_options::DecodeOptions;
+use little_exif::xmp::Xmp;
 
 #[test]
 fn
@@ -115,6 +118,24 @@ get_test_metadata()
 	return Ok(metadata);
 }
 
+fn
+minimal_jpg
+(
+	tiff_data: &[u8]
+)
+-> Vec<u8>
+{
+	// Just the SOI marker, the APP1 segment with the EXIF data and EOI
+	let length = (2 + 6 + tiff_data.len()) as u16;
+	return [
+		vec![0xff, 0xd8, 0xff, 0xe1],
+		length.to_be_bytes().to_vec(),
+		b"Exif\0\0".to_vec(),
+		tiff_data.to_vec(),
+		vec![0xff, 0xd9]
+	].concat();
+}
+
 #[test]
 fn 
 as_u8_vec_png()
@@ -126,7 +147,6 @@ as_u8_vec_png()
 			.as_u8_vec(little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: false })
 			.iter()
 			.map(|char_value| *char_value as char)
-			.into_iter()
 			.collect::<String>()
 	);
 }
@@ -143,7 +163,6 @@ as_u8_vec_png_zTXt()
 			.as_u8_vec(little_exif::filetype::FileExtension::PNG { as_zTXt_chunk: true })
 			.iter()
 			.map(|char_value| *char_value as char)
-			.into_iter()
 			.collect::<String>()
 	);
 }
@@ -172,6 +191,288 @@ clear_metadata_jpg()
 	Ok(())
 }
 
+#[test]
+fn
+clear_metadata_from_bytes()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+
+	for path in ["tests/sample2.jpg", "tests/sample2.png", "tests/sample2_extended.webp"]
+	{
+		let file_type = || path.rsplit('.').next().unwrap().parse::<FileExtension>().unwrap();
+		let original  = std::fs::read(path)?;
+
+		let mut with_metadata = Vec::new();
+		metadata.write_to_writer(&original, &mut with_metadata, file_type())?;
+		let stripped = Metadata::clear_metadata_from_bytes(&with_metadata, file_type())?;
+
+		// The result equals the stripped original
+		assert_eq!(stripped, Metadata::clear_metadata_from_bytes(&original, file_type())?);
+		assert!(!stripped.windows(4).any(|window| window == b"Exif" || window == b"EXIF"));
+	}
+
+	assert!(Metadata::clear_metadata_from_bytes(&[0x00; 16], FileExtension::JPEG).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+new_from_vec_autodetect()
+-> Result<(), std::io::Error>
+{
+	// Structured readers
+	let jpg_metadata = Metadata::new_from_vec_autodetect(&std::fs::read("tests/sample2.jpg")?)?;
+	assert_eq!(jpg_metadata.data(), Metadata::new_from_path(Path::new("tests/sample2.jpg"))?.data());
+
+	// A TGA file with EXIF data (following the EXIF header) appended after
+	// the image data and some bytes mimicking EXIF data that can't be decoded
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Model("Trailer".to_string()));
+	let app1 = metadata.as_u8_vec(FileExtension::JPEG);
+
+	let mut tga = vec![0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x18, 0x00];
+	tga.extend([0x12, 0x34, 0x56]);
+	tga.extend(&app1[4..]);
+	tga.extend(b"Exif\0\0MM\0\x2a\0\0\0\x08\xff\xff");
+
+	let tga_metadata = Metadata::new_from_vec_autodetect(&tga)?;
+	assert_eq!(tga_metadata.data(), &vec![ExifTag::Model("Trailer".to_string())]);
+
+	assert!(Metadata::new_from_vec_autodetect(&tga[..30])?.data().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+new_from_reader_autodetect()
+-> Result<(), std::io::Error>
+{
+	use little_exif::filetype::FileExtension;
+
+	// A non-seekable reader counting the bytes read from it
+	struct CountingReader<'a>
+	{
+		data:     &'a [u8],
+		position: usize,
+	}
+
+	impl std::io::Read for CountingReader<'_>
+	{
+		fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize>
+		{
+			let length = std::cmp::min(buffer.len(), self.data.len() - self.position);
+			buffer[..length].copy_from_slice(&self.data[self.position..(self.position+length)]);
+			self.position += length;
+			return Ok(length);
+		}
+	}
+
+	for path in ["tests/sample2.jpg", "tests/sample2.png", "tests/sample2_extended.webp"]
+	{
+		let file_buffer = std::fs::read(path)?;
+		let mut reader = CountingReader { data: &file_buffer, position: 0 };
+
+		let metadata = Metadata::new_from_reader_autodetect(&mut reader)?;
+		assert_eq!(metadata.data(), Metadata::new_from_path(Path::new(path))?.data());
+
+		// JPEGs and PNGs are only read up to their EXIF data
+		if path.ends_with(".jpg")
+		{
+			assert!(reader.position < file_buffer.len());
+		}
+	}
+	assert!(!Metadata::new_from_reader_autodetect(std::fs::File::open("tests/sample2.jpg")?)?.data().is_empty());
+
+	// Truncated or unknown data
+	let file_buffer = std::fs::read("tests/sample2.jpg")?;
+	assert!(Metadata::new_from_reader_autodetect(&file_buffer[..64])?.data().is_empty());
+	assert!(Metadata::new_from_reader_autodetect(&[0x00; 4][..])?.data().is_empty());
+
+	assert_eq!(FileExtension::detect_from_bytes(&file_buffer),              Some(FileExtension::JPEG));
+	assert_eq!(FileExtension::detect_from_bytes(&std::fs::read("tests/read_sample.webp")?), Some(FileExtension::WEBP));
+	assert_eq!(FileExtension::detect_from_bytes(b"GIF89a"),                 None);
+
+	Ok(())
+}
+
+#[test]
+fn
+decode_mime_image()
+-> Result<(), std::io::Error>
+{
+	use little_exif::util::decode_mime_image;
+
+	// Base64 with CRLF line breaks, as found in emails, of a JPEG that only
+	// consists of an APP1 segment with the orientation
+	let part = b"/9j/4QAiRXhpZgAASUkqAAgAAAABABIBAwABAA\r\nAAAQAAAAAAAAD/2Q==\r\n";
+	let image = decode_mime_image(part, "Base64");
+	assert_eq!(image, minimal_jpg(&[
+		0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x01, 0x00,
+		0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00,
+	]));
+
+	let metadata = Metadata::new_from_vec_autodetect(&image)?;
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])), Some(&ExifTag::Orientation(vec![1])));
+
+	assert_eq!(decode_mime_image(b"TWE=", "base64"), b"Ma");
+	assert_eq!(decode_mime_image(b"TW\r\nFu", "base64"), b"Man");
+
+	// Quoted-printable with encoded bytes, a soft line break and an equal
+	// sign that does not start an escape sequence
+	assert_eq!(decode_mime_image(b"=FF=d8ab=\r\ncd=\nef=g", "quoted-printable"), b"\xff\xd8abcdef=g");
+
+	// Only two hex digits form an encoded byte, not e.g. a signed number
+	assert_eq!(decode_mime_image(b"=+1=-1", "quoted-printable"), b"=+1=-1");
+
+	// Other encodings leave the data untouched
+	assert_eq!(decode_mime_image(b"TWFu", "binary"), b"TWFu");
+
+	Ok(())
+}
+
+#[test]
+fn
+read_metadata_ico()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Software("Favicon Generator".to_string()));
+
+	let mut png = Vec::new();
+	metadata.write_to_writer(&std::fs::read("tests/png_parse_test_image.png")?, &mut png, FileExtension::PNG { as_zTXt_chunk: true })?;
+
+	// ICONDIR with a BMP encoded image (without metadata) followed by a PNG
+	let bmp = vec![0x28, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00];
+	let mut ico = vec![0x00, 0x00, 0x01, 0x00, 0x02, 0x00];
+	ico.extend([0x10, 0x10, 0x00, 0x00, 0x01, 0x00, 0x20, 0x00]);
+	ico.extend((bmp.len() as u32).to_le_bytes());
+	ico.extend((6u32 + 2 * 16).to_le_bytes());
+	ico.extend([0x20, 0x20, 0x00, 0x00, 0x01, 0x00, 0x20, 0x00]);
+	ico.extend((png.len() as u32).to_le_bytes());
+	ico.extend((6u32 + 2 * 16 + bmp.len() as u32).to_le_bytes());
+	ico.extend(bmp);
+	ico.extend(png);
+	std::fs::write("tests/sample_ico_copy.ico", &ico)?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample_ico_copy.ico"))?;
+	assert_eq!(read_metadata.get_software_trimmed(), Some("Favicon Generator"));
+	assert_eq!(Metadata::new_from_vec_autodetect(&ico)?.get_software_trimmed(), Some("Favicon Generator"));
+
+	// Read-only
+	assert!(!FileExtension::ICO.supports_write());
+	assert_eq!(
+		read_metadata.write_to_file(Path::new("tests/sample_ico_copy.ico")).unwrap_err().kind(),
+		std::io::ErrorKind::Unsupported
+	);
+
+	// Image data beyond the end of the file
+	let truncated_path = Path::new("tests/sample_ico_truncated_copy.cur");
+	std::fs::write(truncated_path, &ico[..(ico.len() - 1)])?;
+	assert!(Metadata::new_from_path(truncated_path)?.data().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_all_metadata_ico()
+-> Result<(), std::io::Error>
+{
+	let png_with_software = |software: &str| -> Result<Vec<u8>, std::io::Error> {
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Software(software.to_string()));
+
+		let mut png = Vec::new();
+		metadata.write_to_writer(&std::fs::read("tests/png_parse_test_image.png")?, &mut png, FileExtension::PNG { as_zTXt_chunk: true })?;
+		return Ok(png);
+	};
+
+	// ICONDIR with a PNG, a BMP encoded image (without metadata), a PNG 
+	// without metadata and another PNG
+	let images = vec![
+		png_with_software("Small")?,
+		vec![0x28, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00, 0x00],
+		std::fs::read("tests/png_parse_test_image.png")?,
+		png_with_software("Large")?,
+	];
+	let mut ico = vec![0x00, 0x00, 0x01, 0x00, images.len() as u8, 0x00];
+	let mut offset = 6 + 16 * images.len() as u32;
+	for image in &images
+	{
+		ico.extend([0x10, 0x10, 0x00, 0x00, 0x01, 0x00, 0x20, 0x00]);
+		ico.extend((image.len() as u32).to_le_bytes());
+		ico.extend(offset.to_le_bytes());
+		offset += image.len() as u32;
+	}
+	ico.extend(images.concat());
+	std::fs::write("tests/sample_ico_all_copy.ico", &ico)?;
+
+	let all_metadata = Metadata::new_all_from_ico_path(Path::new("tests/sample_ico_all_copy.ico"))?;
+	assert_eq!(
+		all_metadata.iter().map(|metadata| metadata.get_software_trimmed()).collect::<Vec<_>>(), 
+		vec![Some("Small"), Some("Large")]
+	);
+
+	// Only for ICO and CUR files
+	assert_eq!(
+		Metadata::new_all_from_ico_path(Path::new("tests/sample2.jpg")).err().map(|error| error.kind()),
+		Some(std::io::ErrorKind::Unsupported)
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_all_metadata_pdf()
+-> Result<(), std::io::Error>
+{
+	let jpeg_with_model = |model: &str| -> Result<Vec<u8>, std::io::Error> {
+		let mut metadata = Metadata::new();
+		metadata.set_tag(ExifTag::Model(model.to_string()));
+
+		let mut jpeg = Vec::new();
+		metadata.write_to_writer(&std::fs::read("tests/sample2.jpg")?, &mut jpeg, FileExtension::JPEG)?;
+		return Ok(jpeg);
+	};
+
+	let stream_object = |number: u32, filter: &str, data: &[u8]| -> Vec<u8> {
+		let mut object = format!(
+			"{} 0 obj\n<< /Type /XObject /Subtype /Image /Filter {} /Length {} >>\nstream\r\n", 
+			number, filter, data.len()
+		).into_bytes();
+		object.extend(data);
+		object.extend(b"\r\nendstream\nendobj\n");
+		return object;
+	};
+
+	// Two JPEG images, a compressed JPEG image (which can't be read) and a
+	// content stream mentioning the filter
+	let mut pdf = b"%PDF-1.7\n1 0 obj\n<< /Type /Catalog >>\nendobj\n".to_vec();
+	pdf.extend(stream_object(2, "/DCTDecode", &jpeg_with_model("First")?));
+	pdf.extend(stream_object(3, "[/FlateDecode /DCTDecode]", &[0x78, 0x9c, 0x03, 0x00]));
+	pdf.extend(stream_object(4, "/FlateDecode", b"BT (stream /DCTDecode) Tj ET"));
+	pdf.extend(stream_object(5, "/DCTDecode", &jpeg_with_model("Second")?));
+	pdf.extend(b"trailer\n<< /Root 1 0 R >>\n%%EOF\n");
+	std::fs::write("tests/sample_pdf_copy.pdf", &pdf)?;
+
+	let all_metadata = Metadata::new_all_from_pdf_path(Path::new("tests/sample_pdf_copy.pdf"))?;
+	assert_eq!(
+		all_metadata.iter().map(|metadata| metadata.get_model_trimmed()).collect::<Vec<_>>(), 
+		vec![Some("First"), Some("Second")]
+	);
+
+	// Not a PDF document
+	assert!(Metadata::new_all_from_pdf_path(Path::new("tests/sample2.jpg")).is_err());
+
+	Ok(())
+}
+
 #[test]
 fn 
 write_to_file_jpg() 
@@ -281,4 +582,3357 @@ write_to_file_webp_extended()
 	metadata.write_to_file(Path::new("tests/sample2_extended_copy.webp"))?;
 
 	Ok(())
-}
\ No newline at end of file
+}
+
+
+fn
+required_length_of
+(
+	error: &std::io::Error
+)
+-> usize
+{
+	assert_eq!(error.kind(), std::io::ErrorKind::UnexpectedEof);
+	error.get_ref()
+		.and_then(|inner| inner.downcast_ref::<little_exif::metadata::IncompleteData>())
+		.expect("Expected IncompleteData as inner error!")
+		.required_length()
+}
+
+#[test]
+fn
+from_header_bytes_jpg()
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read("tests/sample2.jpg")?;
+
+	// The full file (and thus its header) contains the metadata
+	let metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+	assert!(metadata.get_tag(&ExifTag::ExifImageWidth(vec![])).is_some());
+
+	// Only the signature and the start of the first segment
+	let error = Metadata::from_header_bytes(&file_buffer[..3], FileExtension::JPEG).err().unwrap();
+	assert_eq!(required_length_of(&error), 4);
+
+	// Providing the requested amount of data eventually leads to the metadata
+	let mut available = 3;
+	loop
+	{
+		match Metadata::from_header_bytes(&file_buffer[..available], FileExtension::JPEG)
+		{
+			Ok(partial_metadata) => {
+				assert_eq!(partial_metadata.data(), metadata.data());
+				break;
+			},
+			Err(error) => {
+				let required = required_length_of(&error);
+				assert!(required > available);
+				available = required;
+			}
+		}
+	}
+	assert!(available < file_buffer.len());
+
+	Ok(())
+}
+
+#[test]
+fn
+from_header_bytes_png()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_header_bytes_copy.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.png", "tests/sample2_header_bytes_copy.png")?;
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_header_bytes_copy.png"))?;
+
+	let file_buffer = std::fs::read("tests/sample2_header_bytes_copy.png")?;
+
+	let metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::PNG { as_zTXt_chunk: true })?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	// Cut off in the middle of the zTXt chunk
+	let error = Metadata::from_header_bytes(&file_buffer[..50], FileExtension::PNG { as_zTXt_chunk: true }).err().unwrap();
+	assert!(required_length_of(&error) > 50);
+
+	Ok(())
+}
+
+#[test]
+fn
+from_header_bytes_webp()
+-> Result<(), std::io::Error>
+{
+	let file_buffer = std::fs::read("tests/read_sample.webp")?;
+
+	let metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::WEBP)?;
+	assert!(metadata.get_tag(&ExifTag::ISO(vec![])).is_some());
+
+	let error = Metadata::from_header_bytes(&file_buffer[..8], FileExtension::WEBP).err().unwrap();
+	assert_eq!(required_length_of(&error), 12);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_gps_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_gps_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_gps_copy.jpg")?;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(
+		ExifTag::GPSDifferential(vec![1])
+	);
+	metadata.set_tag(
+		ExifTag::GPSHPositioningError(vec![little_exif::rational::uR64 { nominator: 3, denominator: 2 }])
+	);
+	metadata.set_tag(
+		ExifTag::GPSStatus("A".to_string())
+	);
+	metadata.set_tag(
+		ExifTag::GPSMapDatum("WGS-84".to_string())
+	);
+	metadata.write_to_file(Path::new("tests/sample2_gps_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_gps_copy.jpg"))?;
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::GPSDifferential(vec![])), 
+		Some(&ExifTag::GPSDifferential(vec![1]))
+	);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::GPSHPositioningError(vec![])), 
+		Some(&ExifTag::GPSHPositioningError(vec![little_exif::rational::uR64 { nominator: 3, denominator: 2 }]))
+	);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::GPSStatus(String::new())), 
+		Some(&ExifTag::GPSStatus("A".to_string()))
+	);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::GPSMapDatum(String::new())), 
+		Some(&ExifTag::GPSMapDatum("WGS-84".to_string()))
+	);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::ISO(vec![])), 
+		Some(&ExifTag::ISO(vec![2706]))
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_cfa_pattern_and_oecf_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_cfa_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_cfa_copy.jpg")?;
+
+	// A 2x2 RGGB pattern, with the repeat dimensions stored as big endian
+	// regardless of the byte order of the EXIF data
+	let cfa_pattern = vec![0x00, 0x02, 0x00, 0x02, 0, 1, 1, 2];
+
+	// 1 column, 2 rows, column name "ISO 100", followed by 2 SRATIONALs
+	let mut oecf = vec![0x00, 0x01, 0x00, 0x02];
+	oecf.extend(b"ISO 100\0");
+	oecf.extend([0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4]);
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::CFAPattern(cfa_pattern.clone()));
+	metadata.set_tag(ExifTag::OECF(oecf.clone()));
+	metadata.write_to_file(Path::new("tests/sample2_cfa_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_cfa_copy.jpg"))?;
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::CFAPattern(vec![])),
+		Some(&ExifTag::CFAPattern(cfa_pattern))
+	);
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::OECF(vec![])),
+		Some(&ExifTag::OECF(oecf))
+	);
+	assert_eq!(read_metadata.get_cfa_pattern(), Some((2, 2, &[0u8, 1, 1, 2][..])));
+
+	let mut malformed = Metadata::new();
+	malformed.set_tag(ExifTag::CFAPattern(vec![0x00, 0x02, 0x00, 0x02, 0]));
+	assert_eq!(malformed.get_cfa_pattern(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_nikon_maker_note_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::maker_note::MakerNote;
+
+	if let Err(error) = remove_file("tests/sample2_maker_note_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_maker_note_copy.jpg")?;
+
+	// Type 3 header with a big endian TIFF header, followed by an IFD with a
+	// single ASCII entry whose value is located relative to the MakerNote's
+	// TIFF header ("Nikon" at offset 26)
+	let mut maker_note = b"Nikon\0\x02\x10\0\0MM\0\x2a\0\0\0\x08".to_vec();
+	maker_note.extend([0x00, 0x01, 0x00, 0x03, 0x00, 0x02, 0x00, 0x00, 0x00, 0x06, 0x00, 0x00, 0x00, 0x1a]);
+	maker_note.extend([0x00, 0x00, 0x00, 0x00]);
+	maker_note.extend(b"Nikon\0");
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::MakerNote(maker_note.clone()));
+	metadata.write_to_file(Path::new("tests/sample2_maker_note_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_maker_note_copy.jpg"))?;
+	assert_eq!(read_metadata.get_tag(&ExifTag::MakerNote(vec![])), Some(&ExifTag::MakerNote(maker_note)));
+	assert_eq!(read_metadata.get_maker_note_type(), Some(MakerNote::NikonType3 { endian: Endian::Big }));
+
+	assert_eq!(MakerNote::from_bytes(b"Nikon\0\x01\x00\0\0"), MakerNote::NikonType1);
+	assert_eq!(MakerNote::from_bytes(b"Nikon\0\x02\x00\0\0II\x2a\0"), MakerNote::NikonType3 { endian: Endian::Little });
+	assert_eq!(MakerNote::from_bytes(b"OLYMP\0"), MakerNote::Unknown);
+	assert_eq!(Metadata::new().get_maker_note_type(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+maker_note_keeps_its_own_endian_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::maker_note::MakerNote;
+
+	// Little endian Nikon type 3 MakerNote with a single INT16U entry whose 
+	// value of 0x0102 would read as 0x0201 using big endian
+	let mut maker_note = b"Nikon\0\x02\x10\0\0II\x2a\0\x08\0\0\0".to_vec();
+	maker_note.extend([0x01, 0x00, 0x01, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0x00]);
+	maker_note.extend([0x00, 0x00, 0x00, 0x00]);
+
+	// Little endian EXIF data with just the orientation
+	let little_endian_jpg = minimal_jpg(&[
+		0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x01, 0x00,
+		0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00,
+	]);
+
+	// Written into big endian (sample2.jpg) and little endian EXIF data, the
+	// MakerNote's bytes must not be swapped
+	for (source, copy_path, host_endian) in [
+		(std::fs::read("tests/sample2.jpg")?, "tests/sample2_maker_note_endian_copy.jpg",        Endian::Big),
+		(little_endian_jpg,                   "tests/sample2_little_maker_note_endian_copy.jpg", Endian::Little),
+	]
+	{
+		std::fs::write(copy_path, source)?;
+		let path = Path::new(copy_path);
+
+		let mut metadata = Metadata::new_from_path(path)?;
+		metadata.set_tag(ExifTag::MakerNote(maker_note.clone()));
+		metadata.write_to_file(path)?;
+		Metadata::new_from_path(path)?.write_to_file(path)?;
+
+		let read_metadata = Metadata::new_from_path(path)?;
+		assert_eq!(read_metadata.get_endian(), &host_endian);
+		assert_eq!(read_metadata.get_tag(&ExifTag::MakerNote(vec![])), Some(&ExifTag::MakerNote(maker_note.clone())));
+
+		let maker_note_type = read_metadata.get_maker_note_type().unwrap();
+		assert_eq!(maker_note_type.endian(), Some(&Endian::Little));
+	}
+
+	assert_eq!(MakerNote::NikonType1.endian(), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_owner_name_and_serial_number_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_owner_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_owner_copy.jpg")?;
+
+	// CameraOwnerName (0xa430) and BodySerialNumber (0xa431) of EXIF 2.3
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::OwnerName("Jane Doe".to_string()));
+	metadata.set_tag(ExifTag::SerialNumber("0123456789".to_string()));
+	metadata.write_to_file(Path::new("tests/sample2_owner_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_owner_copy.jpg"))?;
+	assert_eq!(read_metadata.get_tag_by_hex(0xa430), Some(&ExifTag::OwnerName("Jane Doe".to_string())));
+	assert_eq!(read_metadata.get_tag_by_hex(0xa431), Some(&ExifTag::SerialNumber("0123456789".to_string())));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_exif_color_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::rational::uR64;
+
+	if let Err(error) = remove_file("tests/sample2_exif_color_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_exif_color_copy.jpg")?;
+	let path = Path::new("tests/sample2_exif_color_copy.jpg");
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ComponentsConfiguration(vec![1, 2, 3, 0]));           // YCbCr
+	metadata.set_tag(ExifTag::ColorSpace(vec![0xffff]));                            // Uncalibrated
+	metadata.set_tag(ExifTag::Gamma(vec![uR64 { nominator: 22, denominator: 10 }]));
+	metadata.write_to_file(path)?;
+
+	let read_metadata = Metadata::new_from_path(path)?;
+	assert_eq!(read_metadata.data(), metadata.data());
+	assert!(read_metadata.data().iter().all(|tag| !tag.is_unknown()));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_file_preserving_times_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_times_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_times_copy.jpg")?;
+
+	// Move the modification time into the past so that a change is detectable
+	let past = std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+	std::fs::OpenOptions::new()
+		.write(true)
+		.open("tests/sample2_times_copy.jpg")?
+		.set_modified(past)?;
+
+	get_test_metadata()?.write_to_file_preserving_times(Path::new("tests/sample2_times_copy.jpg"))?;
+
+	assert_eq!(std::fs::metadata("tests/sample2_times_copy.jpg")?.modified()?, past);
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_times_copy.jpg"))?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+get_dimensions()
+{
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_dimensions(), None);
+
+	metadata.set_tag(ExifTag::ImageWidth(vec![800]));
+	assert_eq!(metadata.get_dimensions(), None);
+
+	metadata.set_tag(ExifTag::ImageHeight(vec![600]));
+	assert_eq!(metadata.get_dimensions(), Some((800, 600)));
+
+	// The pixel dimensions of the ExifIFD take precedence
+	metadata.set_tag(ExifTag::ExifImageWidth(vec![400]));
+	metadata.set_tag(ExifTag::ExifImageHeight(vec![300]));
+	assert_eq!(metadata.get_dimensions(), Some((400, 300)));
+}
+
+#[test]
+fn
+set_tag_checked()
+{
+	let mut metadata = Metadata::new();
+
+	assert!(metadata.set_tag_checked(ExifTag::Orientation(vec![1])).is_ok());
+	assert!(metadata.set_tag_checked(ExifTag::Orientation(vec![1, 2])).is_err());
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])), Some(&ExifTag::Orientation(vec![1])));
+
+	// Strings with a predefined length get checked including the NUL terminator
+	assert!(metadata.set_tag_checked(ExifTag::DateTimeOriginal("2024:01:01 12:00:00".to_string())).is_ok());
+	assert!(metadata.set_tag_checked(ExifTag::DateTimeOriginal("2024:01:01".to_string())).is_err());
+
+	// Tags without a predefined number of components are always accepted
+	assert!(metadata.set_tag_checked(ExifTag::ImageDescription("Hello World!".to_string())).is_ok());
+	assert_eq!(metadata.data().len(), 3);
+}
+
+#[test]
+fn
+read_text_chunks_png()
+-> Result<(), std::io::Error>
+{
+	let text_chunks = little_exif::png::read_text_chunks(Path::new("tests/sample2_text_chunks.png"))?;
+
+	assert_eq!(text_chunks, vec![
+		("Source".to_string(),  "https://example.com/page".to_string()),
+		("Comment".to_string(), "Café screenshot".to_string()),
+		("Title".to_string(),   "Grüße".to_string()),
+	]);
+
+	// The EXIF data is not among the text chunks
+	if let Err(error) = remove_file("tests/sample2_text_chunks_copy.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_text_chunks.png", "tests/sample2_text_chunks_copy.png")?;
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_text_chunks_copy.png"))?;
+	assert_eq!(little_exif::png::read_text_chunks(Path::new("tests/sample2_text_chunks_copy.png"))?, text_chunks);
+
+	Ok(())
+}
+
+fn
+find_subslice
+(
+	haystack: &[u8],
+	needle:   &[u8]
+)
+-> Option<usize>
+{
+	return haystack.windows(needle.len()).position(|window| window == needle);
+}
+
+#[test]
+fn
+write_preserves_xmp_chunk_webp()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_extended_xmp_copy.webp")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_extended_xmp.webp", "tests/sample2_extended_xmp_copy.webp")?;
+
+	let original = std::fs::read("tests/sample2_extended_xmp.webp")?;
+	let xmp_chunk_start = find_subslice(&original, "XMP ".as_bytes()).unwrap();
+	let xmp_chunk = original[xmp_chunk_start..].to_vec();
+
+	// Write twice to also cover replacing an existing EXIF chunk
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_extended_xmp_copy.webp"))?;
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_extended_xmp_copy.webp"))?;
+
+	let written = std::fs::read("tests/sample2_extended_xmp_copy.webp")?;
+	let exif_chunk_position = find_subslice(&written, "EXIF".as_bytes()).unwrap();
+	let xmp_chunk_position = find_subslice(&written, &xmp_chunk).unwrap();
+	assert!(exif_chunk_position < xmp_chunk_position);
+	assert_eq!(find_subslice(&written[(exif_chunk_position+1)..], "EXIF".as_bytes()), None);
+
+	// Both the EXIF and the XMP flag are set in the VP8X chunk
+	assert_eq!(written[20] & 0x0c, 0x0c);
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_extended_xmp_copy.webp"))?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_xmp_webp()
+-> Result<(), std::io::Error>
+{
+	let path = Path::new("tests/sample2_extended_xmp_write_copy.webp");
+	if let Err(error) = remove_file(path)
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_extended.webp", path)?;
+
+	// An odd length, so that the chunk needs a padding byte
+	let xmp = b"<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF/></x:xmpmeta>!".to_vec();
+	assert_eq!(xmp.len() % 2, 1);
+
+	get_test_metadata()?.write_to_file(path)?;
+	little_exif::webp::write_xmp(path, &xmp)?;
+
+	let written = std::fs::read(path)?;
+	assert_eq!(written[20] & 0x0c, 0x0c);
+	assert_eq!(little_exif::webp::read_xmp(path)?, Some(xmp.clone()));
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	// Replacing the EXIF data keeps the XMP chunk and vice versa
+	get_test_metadata()?.write_to_file(path)?;
+	assert_eq!(little_exif::webp::read_xmp(path)?, Some(xmp.clone()));
+
+	let other_xmp = b"<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"/>".to_vec();
+	little_exif::webp::write_xmp(path, &other_xmp)?;
+	assert_eq!(little_exif::webp::read_xmp(path)?, Some(other_xmp));
+	assert_eq!(Metadata::new_from_path(path)?.get_tag(&ExifTag::Model(String::new())), metadata.get_tag(&ExifTag::Model(String::new())));
+
+	let written = std::fs::read(path)?;
+	assert_eq!(written[20] & 0x0c, 0x0c);
+	assert!(find_subslice(&written, "EXIF".as_bytes()) < find_subslice(&written, "XMP ".as_bytes()));
+	assert_eq!(find_subslice(&written, "XMP ".as_bytes()), written.windows(4).rposition(|window| window == "XMP ".as_bytes()));
+
+	// Clearing the XMP data only clears its flag
+	little_exif::webp::clear_xmp(path)?;
+	assert_eq!(little_exif::webp::read_xmp(path)?, None);
+	assert_eq!(std::fs::read(path)?[20] & 0x0c, 0x08);
+	assert_eq!(Metadata::new_from_path(path)?.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_xmp_properties_webp()
+-> Result<(), std::io::Error>
+{
+	let path = Path::new("tests/sample2_extended_xmp_properties_copy.webp");
+	if let Err(error) = remove_file(path)
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_extended.webp", path)?;
+
+	let mut xmp = Xmp::new_from_path(path)?;
+	assert_eq!(xmp, Xmp::new());
+
+	assert!(xmp.set_rating(6).is_err());
+	xmp.set_rating(4)?;
+	xmp.add_keyword("Vacation");
+	xmp.add_keyword("Rock & Roll");
+	xmp.add_keyword("Vacation");
+	xmp.set_creator("Jane <Doe>");
+
+	// EXIF and XMP data are written at once
+	get_test_metadata()?.write_to_file_with_xmp(path, &xmp)?;
+
+	let read_xmp = Xmp::new_from_path(path)?;
+	assert_eq!(read_xmp, xmp);
+	assert_eq!(read_xmp.rating(), Some(4));
+	assert_eq!(read_xmp.keywords(), ["Vacation".to_string(), "Rock & Roll".to_string()]);
+	assert_eq!(read_xmp.creator(), Some("Jane <Doe>"));
+	assert_eq!(Metadata::new_from_path(path)?.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	// Packets of other tools may give the rating as attribute and the items
+	// with attributes
+	let packet = concat!(
+		"<x:xmpmeta xmlns:x=\"adobe:ns:meta/\"><rdf:RDF><rdf:Description xmp:Rating=\"2\">",
+		"<dc:creator><rdf:Seq><rdf:li xml:lang=\"x-default\">John</rdf:li></rdf:Seq></dc:creator>",
+		"</rdf:Description></rdf:RDF></x:xmpmeta>"
+	);
+	let other_xmp = Xmp::from_u8_vec(packet.as_bytes());
+	assert_eq!(other_xmp.rating(), Some(2));
+	assert_eq!(other_xmp.creator(), Some("John"));
+	assert!(other_xmp.keywords().is_empty());
+
+	// Only supported for WebP
+	assert!(get_test_metadata()?.write_to_file_with_xmp(Path::new("tests/sample2.jpg"), &xmp).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_preserves_iccp_chunk_png()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_iccp_copy.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_iccp.png", "tests/sample2_iccp_copy.png")?;
+
+	// The iCCP chunk including its length and CRC, followed by the pHYs chunk
+	let original = std::fs::read("tests/sample2_iccp.png")?;
+	let ancillary_chunks = original[33..find_subslice(&original, "IDAT".as_bytes()).unwrap()-4].to_vec();
+
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_iccp_copy.png"))?;
+	get_test_metadata()?.write_to_file(Path::new("tests/sample2_iccp_copy.png"))?;
+
+	let written = std::fs::read("tests/sample2_iccp_copy.png")?;
+	assert!(find_subslice(&written, &ancillary_chunks).is_some());
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_iccp_copy.png"))?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_file_apng()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample_animated_copy.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample_animated.png", "tests/sample_animated_copy.png")?;
+	let path = Path::new("tests/sample_animated_copy.png");
+
+	// Everything after IHDR: acTL, the fcTL and IDAT of the first frame and 
+	// the fcTL and fdAT of the second frame, followed by IEND
+	let original = std::fs::read(path)?;
+	let animation_chunks = original[33..].to_vec();
+
+	get_test_metadata()?.write_to_file(path)?;
+	get_test_metadata()?.write_to_file(path)?;
+
+	// The zTXt chunk is placed between IHDR and acTL, the animation is not
+	// touched at all
+	let written = std::fs::read(path)?;
+	assert_eq!(&written[..33], &original[..33]);
+	assert_eq!(&written[37..41], "zTXt".as_bytes());
+	assert!(written.ends_with(&animation_chunks));
+	assert!(find_subslice(&written, "acTL".as_bytes()) < find_subslice(&written, "IDAT".as_bytes()));
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Metadata::clear_metadata(path)?;
+	assert_eq!(std::fs::read(path)?, original);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_corrupted_crc_png()
+-> Result<(), std::io::Error>
+{
+	// The CRC of the IHDR chunk doesn't match its content
+	let path = Path::new("tests/sample2_corrupted_crc.png");
+
+	// By default, CRCs are not verified
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	let mut options = DecodeOptions::new();
+	options.verify_png_crc = true;
+
+	let result = Metadata::new_from_path_with_options(path, &options);
+	assert!(result.is_err_and(|error| error.to_string().contains("IHDR")));
+
+	// The same applies to the constructors working on bytes
+	let file_buffer = std::fs::read(path)?;
+	assert!(Metadata::new_from_vec_autodetect(&file_buffer)?.get_tag(&ExifTag::ISO(vec![])).is_some());
+	assert!(Metadata::new_from_vec_autodetect_with_options(&file_buffer, &options)
+		.is_err_and(|error| error.to_string().contains("IHDR")));
+	assert!(Metadata::from_header_bytes_with_options(&file_buffer, FileExtension::PNG {as_zTXt_chunk: true}, &options)
+		.is_err_and(|error| error.to_string().contains("IHDR")));
+	assert!(Metadata::new_from_reader_autodetect_with_options(&file_buffer[..], &options)
+		.is_err_and(|error| error.to_string().contains("IHDR")));
+
+	// Intact files are still accepted when verifying
+	let metadata = Metadata::new_from_path_with_options(Path::new("tests/png_parse_test_image.png"), &options)?;
+	assert!(metadata.data().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+tag_classification()
+{
+	let gps_tags = [
+		ExifTag::GPSInfo(vec![0]),
+		ExifTag::GPSDifferential(vec![0]),
+		ExifTag::GPSHPositioningError(vec![]),
+		ExifTag::UnknownINT8U(vec![2, 3, 0, 0], 0x0000, little_exif::exif_tag::ExifTagGroup::GPSIFD),
+	];
+	let date_tags = [
+		ExifTag::ModifyDate(String::new()),
+		ExifTag::DateTimeOriginal(String::new()),
+		ExifTag::CreateDate(String::new()),
+		ExifTag::OffsetTime(String::new()),
+		ExifTag::OffsetTimeOriginal(String::new()),
+		ExifTag::OffsetTimeDigitized(String::new()),
+		ExifTag::SubSecTime(String::new()),
+		ExifTag::SubSecTimeOriginal(String::new()),
+		ExifTag::SubSecTimeDigitized(String::new()),
+	];
+	let camera_identity_tags = [
+		ExifTag::OwnerName(String::new()),
+		ExifTag::SerialNumber(String::new()),
+		ExifTag::LensSerialNumber(String::new()),
+	];
+	let other_tags = [
+		ExifTag::Make(String::new()),
+		ExifTag::Model(String::new()),
+		ExifTag::LensModel(String::new()),
+		ExifTag::ISO(vec![]),
+		ExifTag::ExifOffset(vec![]),
+		ExifTag::UnknownINT8U(vec![], 0x0000, little_exif::exif_tag::ExifTagGroup::IFD0),
+	];
+
+	for tag in &gps_tags
+	{
+		assert!(tag.is_gps_tag() && !tag.is_date_tag() && !tag.is_camera_identity_tag(), "{:?}", tag);
+		assert!(tag.is_potentially_sensitive());
+	}
+	for tag in &date_tags
+	{
+		assert!(!tag.is_gps_tag() && tag.is_date_tag() && !tag.is_camera_identity_tag(), "{:?}", tag);
+		assert!(tag.is_potentially_sensitive());
+	}
+	for tag in &camera_identity_tags
+	{
+		assert!(!tag.is_gps_tag() && !tag.is_date_tag() && tag.is_camera_identity_tag(), "{:?}", tag);
+		assert!(tag.is_potentially_sensitive());
+	}
+	for tag in &other_tags
+	{
+		assert!(!tag.is_potentially_sensitive(), "{:?}", tag);
+	}
+
+	// One-line privacy scrub
+	let mut metadata = get_test_metadata().unwrap();
+	metadata.set_tag(ExifTag::GPSDifferential(vec![1]));
+	metadata.set_tag(ExifTag::SerialNumber("1234".to_string()));
+	metadata.retain(|tag| !tag.is_potentially_sensitive());
+	assert_eq!(metadata.data(), get_test_metadata().unwrap().data());
+}
+
+#[test]
+fn
+write_exif_blob()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_blob_copy.exif")
+	{
+		println!("{}", error);
+	}
+
+	let metadata = get_test_metadata()?;
+	metadata.write_exif_blob(Path::new("tests/sample2_blob_copy.exif"))?;
+
+	// The blob is the TIFF block that is also contained in the JPEG APP1
+	// segment after its marker, length and the EXIF header
+	let blob = std::fs::read("tests/sample2_blob_copy.exif")?;
+	assert_eq!(&blob[0..4], &[0x49, 0x49, 0x2a, 0x00]);
+	assert_eq!(blob, metadata.as_u8_vec(FileExtension::JPEG)[10..].to_vec());
+
+	Ok(())
+}
+
+#[test]
+fn
+ifd_entries_sorted_ascending()
+{
+	// Tags are set in descending order, including ones pointing to SubIFDs
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::GPSDifferential(vec![0]));
+	metadata.set_tag(ExifTag::ISO(vec![100]));
+	metadata.set_tag(ExifTag::Software("little_exif".to_string()));
+	metadata.set_tag(ExifTag::Orientation(vec![1]));
+	metadata.set_tag(ExifTag::ImageDescription("Sorted".to_string()));
+
+	// Little endian TIFF block with IFD0 at offset 8
+	let blob = metadata.as_u8_vec(FileExtension::JPEG)[10..].to_vec();
+	let count_entries = u16::from_le_bytes([blob[8], blob[9]]) as usize;
+	let hex_values: Vec<u16> = (0..count_entries)
+		.map(|i| u16::from_le_bytes([blob[10 + 12*i], blob[11 + 12*i]]))
+		.collect();
+
+	// ImageDescription, Orientation, Software, ExifOffset, GPSInfo
+	assert_eq!(hex_values, vec![0x010e, 0x0112, 0x0131, 0x8769, 0x8825]);
+}
+
+#[test]
+fn
+read_thumbnail_jpg()
+-> Result<(), std::io::Error>
+{
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail.jpg"))?;
+
+	let thumbnail = metadata.get_thumbnail().expect("Expected a thumbnail!");
+	assert_eq!(&thumbnail[0..2], &[0xff, 0xd8]);
+	assert_eq!(&thumbnail[(thumbnail.len()-2)..], &[0xff, 0xd9]);
+	assert_eq!(metadata.get_tag(&ExifTag::ThumbnailLength(vec![])), Some(&ExifTag::ThumbnailLength(vec![thumbnail.len() as u32])));
+
+	// IFD0 and IFD1 tags with the same hex value are kept apart
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])), Some(&ExifTag::Orientation(vec![1])));
+	assert!(metadata.data().contains(
+		&ExifTag::UnknownRATIONAL64U(vec![little_exif::rational::uR64 { nominator: 72, denominator: 1 }], 0x011a, little_exif::exif_tag::ExifTagGroup::IFD1)
+	));
+
+	Ok(())
+}
+
+#[test]
+fn
+read_thumbnail_out_of_bounds_jpg()
+-> Result<(), std::io::Error>
+{
+	// ThumbnailOffset + ThumbnailLength exceed the APP1 segment
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail_out_of_bounds.jpg"))?;
+
+	assert_eq!(metadata.get_thumbnail(), None);
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])), Some(&ExifTag::Orientation(vec![1])));
+	assert_eq!(metadata.decode_warnings().len(), 1);
+	assert_eq!(metadata.decode_warnings()[0].group, little_exif::exif_tag::ExifTagGroup::IFD1);
+	assert_eq!(metadata.decode_warnings()[0].hex_tag, Some(0x0201));
+
+	// An offset and length whose sum overflows 32 bits
+	let mut tiff = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+	tiff.extend([0x01, 0x00, 0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+	tiff.extend([0x1a, 0x00, 0x00, 0x00]);
+	tiff.extend([0x02, 0x00]);
+	tiff.extend([0x01, 0x02, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0xf0, 0xff, 0xff, 0xff]);
+	tiff.extend([0x02, 0x02, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00]);
+	tiff.extend([0x00, 0x00, 0x00, 0x00]);
+
+	let metadata = Metadata::decode(&tiff, None)?;
+	assert_eq!(metadata.get_thumbnail(), None);
+	assert_eq!(metadata.decode_warnings().len(), 1);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_thumbnail_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_thumbnail_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_thumbnail_copy.jpg")?;
+
+	// Take over the metadata including the IFD1 tags and the thumbnail
+	let original = Metadata::new_from_path(Path::new("tests/sample2_thumbnail.jpg"))?;
+	original.write_to_file(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+	assert_eq!(metadata.get_thumbnail(), original.get_thumbnail());
+	assert_eq!(metadata.data(), original.data());
+
+	// Replace the thumbnail
+	let mut metadata = get_test_metadata()?;
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0x01, 0x02, 0x03, 0xff, 0xd9]);
+	metadata.write_to_file(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+	assert_eq!(read_metadata.get_thumbnail(), Some(&vec![0xff, 0xd8, 0x01, 0x02, 0x03, 0xff, 0xd9]));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ThumbnailLength(vec![])), Some(&ExifTag::ThumbnailLength(vec![7])));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	// And remove it again
+	let mut metadata = read_metadata;
+	metadata.clear_thumbnail();
+	metadata.write_to_file(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail_copy.jpg"))?;
+	assert_eq!(read_metadata.get_thumbnail(), None);
+	assert_eq!(read_metadata.data(), get_test_metadata()?.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+edit_preserves_thumbnail_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_thumbnail_edit_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_thumbnail.jpg", "tests/sample2_thumbnail_edit_copy.jpg")?;
+	let path = Path::new("tests/sample2_thumbnail_edit_copy.jpg");
+
+	// Read, edit a tag and write back to the same file
+	let original = Metadata::new_from_path(path)?;
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag(ExifTag::ImageDescription("Edited".to_string()));
+	metadata.write_to_file(path)?;
+
+	// The thumbnail and the other IFD1 tags survive the edit (apart from 
+	// the ThumbnailOffset, which depends on the size of IFD0)
+	let ifd1_tags = |metadata: &Metadata| metadata.data().iter()
+		.filter(|tag| tag.get_group() == little_exif::exif_tag::ExifTagGroup::IFD1 && tag.is_writable())
+		.map(|tag| format!("{:?}", tag))
+		.collect::<Vec<String>>();
+
+	let edited = Metadata::new_from_path(path)?;
+	assert!(edited.get_thumbnail().is_some());
+	assert_eq!(edited.get_thumbnail(), original.get_thumbnail());
+	assert_eq!(ifd1_tags(&edited), ifd1_tags(&original));
+	assert_eq!(ifd1_tags(&edited).len(), 3);
+	assert_eq!(edited.get_tag(&ExifTag::ImageDescription(String::new())), Some(&ExifTag::ImageDescription("Edited".to_string())));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_thumbnail_resolution_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::rational::uR64;
+
+	if let Err(error) = remove_file("tests/sample2_thumbnail_resolution_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_thumbnail_resolution_copy.jpg")?;
+	let path = Path::new("tests/sample2_thumbnail_resolution_copy.jpg");
+
+	// The resolution of the image in IFD0 and the one of the thumbnail in IFD1
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::XResolution(vec![uR64 { nominator: 300, denominator: 1 }]));
+	metadata.set_tag(ExifTag::YResolution(vec![uR64 { nominator: 300, denominator: 1 }]));
+	metadata.set_tag(ExifTag::ResolutionUnit(vec![2]));
+	metadata.set_tag(ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 72, denominator: 1 }], 0x011a, ExifTagGroup::IFD1));
+	metadata.set_tag(ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 72, denominator: 1 }], 0x011b, ExifTagGroup::IFD1));
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![3], 0x0128, ExifTagGroup::IFD1));
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+	metadata.write_to_file(path)?;
+
+	let read_metadata = Metadata::new_from_path(path)?;
+	assert_eq!(read_metadata.tags_in_group(ExifTagGroup::IFD0), vec![
+		&ExifTag::XResolution(vec![uR64 { nominator: 300, denominator: 1 }]),
+		&ExifTag::YResolution(vec![uR64 { nominator: 300, denominator: 1 }]),
+		&ExifTag::ResolutionUnit(vec![2]),
+	]);
+	assert_eq!(read_metadata.tags_in_group(ExifTagGroup::IFD1)[..4], vec![
+		&ExifTag::UnknownINT16U(vec![6], 0x0103, ExifTagGroup::IFD1),
+		&ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 72, denominator: 1 }], 0x011a, ExifTagGroup::IFD1),
+		&ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 72, denominator: 1 }], 0x011b, ExifTagGroup::IFD1),
+		&ExifTag::UnknownINT16U(vec![3], 0x0128, ExifTagGroup::IFD1),
+	]);
+	assert_eq!(read_metadata.get_thumbnail(), Some(&vec![0xff, 0xd8, 0xff, 0xd9]));
+
+	Ok(())
+}
+
+#[test]
+fn
+read_previews_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::preview::Preview;
+	use little_exif::preview::PreviewFormat;
+
+	// A JPEG consisting only of an SOF0 segment for the given dimensions
+	let jpeg = |width: u8, height: u8| vec![
+		0xff, 0xd8, 0xff, 0xc0, 0x00, 0x0b, 0x08, 0x00, height, 0x00, width, 0x01, 0x01, 0x11, 0x00, 0xff, 0xd9
+	];
+	let entry = |hex: u16, format: u16, value: u32| [
+		hex.to_le_bytes().to_vec(), format.to_le_bytes().to_vec(), 1u32.to_le_bytes().to_vec(), value.to_le_bytes().to_vec()
+	].concat();
+
+	// IFD0 with a SubIFD containing an uncompressed preview, IFD1 with the 
+	// thumbnail and IFD2 with a larger JPEG preview, followed by the data
+	let mut tiff = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00];
+	tiff.extend([0x02, 0x00]);
+	tiff.extend(entry(0x0112, 3, 1));
+	tiff.extend(entry(0x014a, 4, 98));
+	tiff.extend(38u32.to_le_bytes());
+	tiff.extend([0x02, 0x00]);
+	tiff.extend(entry(0x0201, 4, 152));
+	tiff.extend(entry(0x0202, 4, 17));
+	tiff.extend(68u32.to_le_bytes());
+	tiff.extend([0x02, 0x00]);
+	tiff.extend(entry(0x0201, 4, 169));
+	tiff.extend(entry(0x0202, 4, 17));
+	tiff.extend(0u32.to_le_bytes());
+	tiff.extend([0x04, 0x00]);
+	tiff.extend(entry(0x0100, 3, 4));
+	tiff.extend(entry(0x0101, 3, 2));
+	tiff.extend(entry(0x0201, 4, 186));
+	tiff.extend(entry(0x0202, 4, 24));
+	tiff.extend(0u32.to_le_bytes());
+	tiff.extend(jpeg(8, 8));
+	tiff.extend(jpeg(160, 120));
+	tiff.extend([0x7f; 24]);
+	assert_eq!(tiff.len(), 210);
+
+	let mut file_buffer = vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 8 + tiff.len() as u8];
+	file_buffer.extend(b"Exif\0\0");
+	file_buffer.extend(tiff);
+	file_buffer.extend([0xff, 0xd9]);
+
+	let metadata = Metadata::new_from_vec_autodetect(&file_buffer)?;
+	assert_eq!(metadata.get_thumbnail(), Some(&jpeg(8, 8)));
+	assert_eq!(metadata.previews(), vec![
+		Preview { width: Some(8),   height: Some(8),   format: PreviewFormat::JPEG,    data: jpeg(8, 8) },
+		Preview { width: Some(160), height: Some(120), format: PreviewFormat::JPEG,    data: jpeg(160, 120) },
+		Preview { width: Some(4),   height: Some(2),   format: PreviewFormat::Unknown, data: vec![0x7f; 24] },
+	]);
+
+	// Without EXIF data, there are no previews
+	assert!(Metadata::new().previews().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_large_thumbnail_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_large_thumbnail_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_large_thumbnail_copy.jpg")?;
+
+	// A thumbnail exceeding the 64 KB of a single APP1 segment
+	let mut thumbnail = vec![0xff, 0xd8];
+	thumbnail.extend((0..150_000u32).map(|i| (i % 251) as u8));
+	thumbnail.extend([0xff, 0xd9]);
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_thumbnail(thumbnail.clone());
+
+	// The EXIF data gets split up into three APP1 segments
+	let encoded = metadata.as_u8_vec(FileExtension::JPEG);
+	let mut segment_count = 0;
+	let mut position = 0;
+	while position < encoded.len()
+	{
+		assert_eq!(&encoded[position..(position+2)], &[0xff, 0xe1]);
+		assert_eq!(&encoded[(position+4)..(position+10)], b"Exif\0\0");
+		position += 2 + u16::from_be_bytes([encoded[position+2], encoded[position+3]]) as usize;
+		segment_count += 1;
+	}
+	assert_eq!(position, encoded.len());
+	assert_eq!(segment_count, 3);
+
+	// Write and reassemble them when reading
+	metadata.write_to_file(Path::new("tests/sample2_large_thumbnail_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_large_thumbnail_copy.jpg"))?;
+	assert_eq!(read_metadata.get_thumbnail(), Some(&thumbnail));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	let file_buffer = std::fs::read("tests/sample2_large_thumbnail_copy.jpg")?;
+	let read_metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+	assert_eq!(read_metadata.get_thumbnail(), Some(&thumbnail));
+
+	// Clearing removes all of the segments
+	Metadata::clear_metadata(Path::new("tests/sample2_large_thumbnail_copy.jpg"))?;
+	let file_buffer = std::fs::read("tests/sample2_large_thumbnail_copy.jpg")?;
+	assert!(find_subslice(&file_buffer, b"Exif\0\0").is_none());
+
+	Ok(())
+}
+
+#[test]
+fn
+new_from_path_with_options_original_raw()
+-> Result<(), std::io::Error>
+{
+	// Disabled by default
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2.jpg"))?;
+	assert_eq!(metadata.original_raw(), None);
+
+	let mut options = DecodeOptions::new();
+	options.keep_original_raw = true;
+	let metadata = Metadata::new_from_path_with_options(Path::new("tests/sample2.jpg"), &options)?;
+
+	// The raw data is the payload of the APP1 segment
+	let file_buffer = std::fs::read("tests/sample2.jpg")?;
+	let raw = metadata.original_raw().expect("Expected raw EXIF data!");
+	assert!(raw.starts_with(&[0x45, 0x78, 0x69, 0x66, 0x00, 0x00]));
+	assert!(file_buffer.windows(raw.len()).any(|window| window == raw));
+	assert_eq!(metadata.data(), Metadata::new_from_path(Path::new("tests/sample2.jpg"))?.data());
+
+	// The EXIF chunk of a WebP file has no EXIF header
+	let metadata = Metadata::new_from_path_with_options(Path::new("tests/read_sample.webp"), &options)?;
+	let raw = metadata.original_raw().expect("Expected raw EXIF data!");
+	assert!(raw.starts_with(b"II") || raw.starts_with(b"MM"));
+	assert!(!metadata.data().is_empty());
+
+	// Nothing to keep if there is no metadata
+	let metadata = Metadata::new_from_path_with_options(Path::new("tests/sample2_simple_lossy.webp"), &options)?;
+	assert_eq!(metadata.original_raw(), None);
+
+	Ok(())
+}
+
+#[test]
+#[cfg(feature = "content_hash")]
+fn
+content_hash()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+
+	// Independent of the order in which the tags were set
+	let mut reordered = Metadata::new();
+	reordered.set_tag(ExifTag::Model("Testcam(1)".to_string()));
+	reordered.set_tag(ExifTag::ISO(vec![2706]));
+	reordered.set_tag(ExifTag::ExposureProgram(vec![1]));
+	reordered.set_tag(ExifTag::ImageDescription("Hello World!".to_string()));
+	assert_eq!(metadata.content_hash(), reordered.content_hash());
+
+	// ...of the file type and the thumbnail...
+	if let Err(error) = remove_file("tests/sample2_hash_copy.png")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.png", "tests/sample2_hash_copy.png")?;
+	let mut with_thumbnail = get_test_metadata()?;
+	with_thumbnail.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+	with_thumbnail.write_to_file(Path::new("tests/sample2_hash_copy.png"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_hash_copy.png"))?;
+	with_thumbnail.set_thumbnail(vec![0xff, 0xd8, 0x00, 0xff, 0xd9]);
+	assert_eq!(read_metadata.content_hash(), with_thumbnail.content_hash());
+
+	// ...but not of the values
+	reordered.set_tag(ExifTag::ISO(vec![2707]));
+	assert_ne!(metadata.content_hash(), reordered.content_hash());
+
+	Ok(())
+}
+
+#[test]
+fn
+reproducible_encoding()
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let tags = || vec![
+		ExifTag::ImageDescription("Hello World!".to_string()),
+		ExifTag::ISO(vec![2706]),
+		ExifTag::GPSSpeedRef("K".to_string()),
+		ExifTag::UnknownINT16U(vec![6], 0x0103, ExifTagGroup::IFD1),
+		ExifTag::UnknownUNDEF(vec![0x01, 0x02], 0xc4a5, ExifTagGroup::IFD0),
+		ExifTag::Model("Test".to_string()),
+	];
+
+	let mut metadata = Metadata::new();
+	tags().into_iter().for_each(|tag| metadata.set_tag(tag));
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+
+	// The same tags, set in reverse order
+	let mut reversed = Metadata::new();
+	reversed.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+	tags().into_iter().rev().for_each(|tag| reversed.set_tag(tag));
+
+	for file_type in [|| FileExtension::JPEG, || FileExtension::PNG { as_zTXt_chunk: true }, || FileExtension::WEBP]
+	{
+		let encoded = metadata.as_u8_vec(file_type());
+		assert_eq!(encoded, metadata.as_u8_vec(file_type()));
+		assert_eq!(encoded, reversed.as_u8_vec(file_type()));
+	}
+}
+
+#[test]
+fn
+tags_in_group()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::rational::uR64;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_thumbnail.jpg"))?;
+
+	assert_eq!(metadata.tags_in_group(ExifTagGroup::IFD0), vec![&ExifTag::Orientation(vec![1])]);
+	assert_eq!(metadata.tags_in_group(ExifTagGroup::IFD1), vec![
+		&ExifTag::UnknownINT16U(vec![6], 0x0103, ExifTagGroup::IFD1),
+		&ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 72, denominator: 1 }], 0x011a, ExifTagGroup::IFD1),
+		&ExifTag::UnknownINT16U(vec![2], 0x0128, ExifTagGroup::IFD1),
+		&ExifTag::ThumbnailOffset(vec![100]),
+		&ExifTag::ThumbnailLength(vec![66]),
+	]);
+	assert!(metadata.tags_in_group(ExifTagGroup::ExifIFD).is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+interpret_enumerated_tags()
+-> Result<(), std::io::Error>
+{
+	// Big endian camera JPEG
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_camera.jpg"))?;
+	assert_eq!(metadata.get_endian(), &little_exif::endian::Endian::Big);
+	assert_eq!(metadata.get_tag(&ExifTag::Model(String::new())), Some(&ExifTag::Model("TestCam X100".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![400])));
+
+	let interpret = |tag: ExifTag| metadata.get_tag(&tag).and_then(|tag| tag.interpret());
+	assert_eq!(interpret(ExifTag::MeteringMode(vec![])),     Some("Multi-segment"));
+	assert_eq!(interpret(ExifTag::ExposureMode(vec![])),     Some("Auto"));
+	assert_eq!(interpret(ExifTag::WhiteBalance(vec![])),     Some("Manual"));
+	assert_eq!(interpret(ExifTag::SceneCaptureType(vec![])), Some("Portrait"));
+	assert_eq!(interpret(ExifTag::ISO(vec![])),              None);
+
+	assert_eq!(ExifTag::MeteringMode(vec![3]).interpret(),   Some("Spot"));
+	assert_eq!(ExifTag::MeteringMode(vec![7]).interpret(),   None);
+	assert_eq!(ExifTag::MeteringMode(vec![]).interpret(),    None);
+
+	// The tags round-trip in the ExifIFD
+	if let Err(error) = remove_file("tests/sample2_camera_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_camera_copy.jpg")?;
+	metadata.write_to_file(Path::new("tests/sample2_camera_copy.jpg"))?;
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_camera_copy.jpg"))?;
+	assert_eq!(read_metadata.data(), metadata.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+components()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag_format::ExifTagFormat;
+	use little_exif::exif_tag_format::TagValue;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_camera.jpg"))?;
+	assert_eq!(metadata.get_tag(&ExifTag::Model(String::new())).map(|tag| tag.components()), Some(TagValue::Ascii("TestCam X100".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])).map(|tag| tag.components()),          Some(TagValue::Short(vec![400])));
+
+	// The type of the value matches the format of the tag, also for unknown tags
+	let unknown = ExifTag::UnknownRATIONAL64S(vec![little_exif::rational::iR64 { nominator: -1, denominator: 3 }], 0x9999, little_exif::exif_tag::ExifTagGroup::ExifIFD);
+	assert_eq!(unknown.components(), TagValue::SRational(vec![little_exif::rational::iR64 { nominator: -1, denominator: 3 }]));
+
+	for tag in metadata.data().iter().chain([unknown].iter())
+	{
+		assert_eq!(tag.components().format(), tag.format());
+	}
+
+	assert_eq!(ExifTag::MakerNote(vec![1, 2]).components(), TagValue::Undefined(vec![1, 2]));
+	assert_eq!(TagValue::Byte(vec![]).format(), ExifTagFormat::INT8U);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_camera()
+-> Result<(), std::io::Error>
+{
+	assert_eq!(
+		little_exif::read_camera(Path::new("tests/sample2_camera.jpg"))?,
+		Some(("TestMaker".to_string(), "TestCam X100".to_string()))
+	);
+
+	// Same result as a full decode
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_camera.jpg"))?;
+	assert_eq!(metadata.get_tag(&ExifTag::Make(String::new())), Some(&ExifTag::Make("TestMaker".to_string())));
+
+	// No Make and Model tags or no EXIF data at all
+	assert_eq!(little_exif::read_camera(Path::new("tests/sample2_thumbnail.jpg"))?, None);
+	assert_eq!(little_exif::read_camera(Path::new("tests/sample2_text_chunks.png"))?, None);
+	assert!(little_exif::read_camera(Path::new("tests/does_not_exist.jpg")).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_color_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::rational::uR64;
+
+	if let Err(error) = remove_file("tests/sample2_color_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_color_copy.jpg")?;
+
+	// D65 white point and the sRGB primaries and coefficients
+	let rational = |nominator: u32, denominator: u32| uR64 { nominator, denominator };
+	let white_point = ExifTag::WhitePoint(vec![rational(3127, 10000), rational(3290, 10000)]);
+	let primary_chromaticities = ExifTag::PrimaryChromaticities(vec![
+		rational(64, 100), rational(33, 100),
+		rational(30, 100), rational(60, 100),
+		rational(15, 100), rational( 6, 100),
+	]);
+	let ycbcr_coefficients = ExifTag::YCbCrCoefficients(vec![rational(299, 1000), rational(587, 1000), rational(114, 1000)]);
+
+	let mut metadata = Metadata::new();
+	for tag in [&white_point, &primary_chromaticities, &ycbcr_coefficients]
+	{
+		assert!(tag.has_valid_number_of_components());
+		assert!(!tag.is_unknown());
+	}
+	metadata.set_tag(white_point);
+	metadata.set_tag(primary_chromaticities);
+	metadata.set_tag(ycbcr_coefficients);
+	metadata.write_to_file(Path::new("tests/sample2_color_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_color_copy.jpg"))?;
+	assert_eq!(read_metadata.data(), metadata.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+get_make_and_model_trimmed()
+{
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_make_trimmed(),  None);
+	assert_eq!(metadata.get_model_trimmed(), None);
+
+	metadata.set_tag(ExifTag::Make("Canon   ".to_string()));
+	metadata.set_tag(ExifTag::Model("NIKON D850\0\0 \0".to_string()));
+	assert_eq!(metadata.get_make_trimmed(),  Some("Canon"));
+	assert_eq!(metadata.get_model_trimmed(), Some("NIKON D850"));
+
+	// The raw values are kept for writing
+	assert_eq!(metadata.get_tag(&ExifTag::Make(String::new())), Some(&ExifTag::Make("Canon   ".to_string())));
+}
+
+#[test]
+fn
+write_and_read_software_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	if let Err(error) = remove_file("tests/sample2_software_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_software_copy.jpg")?;
+
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_software_trimmed(),            None);
+	assert_eq!(metadata.get_processing_software_trimmed(), None);
+
+	// GPSDOP shares its hex value with ProcessingSoftware
+	metadata.set_raw_tag(0x000b, ExifTagFormat::RATIONAL64U, ExifTagGroup::GPSIFD, vec![1, 0, 0, 0, 1, 0, 0, 0])?;
+	metadata.set_tag(ExifTag::ProcessingSoftware("RAW Converter 2.1\0".to_string()));
+	metadata.set_tag(ExifTag::Software("Photo Editor 5 ".to_string()));
+	metadata.write_to_file(Path::new("tests/sample2_software_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_software_copy.jpg"))?;
+	assert_eq!(read_metadata.get_software_trimmed(),            Some("Photo Editor 5"));
+	assert_eq!(read_metadata.get_processing_software_trimmed(), Some("RAW Converter 2.1"));
+	assert_eq!(read_metadata.tags_in_group(ExifTagGroup::GPSIFD).len(), 1);
+
+	Ok(())
+}
+
+#[test]
+fn
+diff()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let original = get_test_metadata()?;
+	assert!(original.diff(&get_test_metadata()?).is_empty());
+
+	let mut edited = get_test_metadata()?;
+	edited.set_tag(ExifTag::ISO(vec![100]));
+	edited.set_tag(ExifTag::Software("little_exif".to_string()));
+	edited.set_tag(ExifTag::UnknownINT16U(vec![1], 0x0112, ExifTagGroup::IFD1));
+	edited.retain(|tag| tag.as_u16() != ExifTag::Model(String::new()).as_u16());
+
+	let diff = original.diff(&edited);
+	assert_eq!(diff.added,   vec![&ExifTag::Software("little_exif".to_string()), &ExifTag::UnknownINT16U(vec![1], 0x0112, ExifTagGroup::IFD1)]);
+	assert_eq!(diff.removed, vec![&ExifTag::Model("Testcam(1)".to_string())]);
+	assert_eq!(diff.changed, vec![(&ExifTag::ISO(vec![2706]), &ExifTag::ISO(vec![100]))]);
+
+	// The other way round
+	let diff = edited.diff(&original);
+	assert_eq!(diff.added.len(),   1);
+	assert_eq!(diff.removed.len(), 2);
+	assert_eq!(diff.changed, vec![(&ExifTag::ISO(vec![100]), &ExifTag::ISO(vec![2706]))]);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_if_changed_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_if_changed_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_if_changed_copy.jpg")?;
+	let path = Path::new("tests/sample2_if_changed_copy.jpg");
+
+	let mut metadata = get_test_metadata()?;
+	assert!(metadata.write_if_changed(path)?);
+
+	// Writing the same metadata again doesn't touch the file
+	let modified = std::fs::metadata(path)?.modified()?;
+	std::thread::sleep(std::time::Duration::from_millis(20));
+	assert!(!metadata.write_if_changed(path)?);
+	assert!(!Metadata::new_from_path(path)?.write_if_changed(path)?);
+	assert_eq!(std::fs::metadata(path)?.modified()?, modified);
+
+	// A changed tag or thumbnail causes a write
+	metadata.set_tag(ExifTag::ISO(vec![100]));
+	assert!(metadata.write_if_changed(path)?);
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+	assert!(metadata.write_if_changed(path)?);
+	assert!(!metadata.write_if_changed(path)?);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_writer()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+
+	for (original, copy_path, file_type) in [
+		("tests/sample2.jpg",                   "tests/sample2_writer_copy.jpg",                  FileExtension::JPEG),
+		("tests/sample2.png",                   "tests/sample2_writer_copy.png",                  FileExtension::PNG { as_zTXt_chunk: true }),
+		("tests/sample2_iccp.png",              "tests/sample2_iccp_writer_copy.png",             FileExtension::PNG { as_zTXt_chunk: true }),
+		("tests/sample2_extended.webp",         "tests/sample2_extended_writer_copy.webp",        FileExtension::WEBP),
+		("tests/sample2_extended_xmp.webp",     "tests/sample2_extended_xmp_writer_copy.webp",    FileExtension::WEBP),
+		("tests/sample2_simple_loseless.webp",  "tests/sample2_simple_loseless_writer_copy.webp", FileExtension::WEBP),
+	]
+	{
+		// Same result as writing to a file
+		if let Err(error) = remove_file(copy_path)
+		{
+			println!("{}", error);
+		}
+		copy(original, copy_path)?;
+		metadata.write_to_file(Path::new(copy_path))?;
+
+		let mut output = Vec::new();
+		metadata.write_to_writer(&std::fs::read(original)?, &mut output, file_type)?;
+		assert_eq!(output, std::fs::read(copy_path)?, "{}", original);
+	}
+
+	// The input has to match the file type
+	let mut output = Vec::new();
+	assert!(metadata.write_to_writer(&std::fs::read("tests/sample2.png")?, &mut output, FileExtension::JPEG).is_err());
+	assert!(output.is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_leading_garbage_jpg()
+-> Result<(), std::io::Error>
+{
+	// A UTF-8 BOM and some other bytes in front of the SOI marker
+	let path = Path::new("tests/sample2_leading_garbage.jpg");
+
+	// Strict by default, resulting in no metadata
+	assert!(Metadata::new_from_path(path)?.data().is_empty());
+
+	let mut options = DecodeOptions::new();
+	options.jpeg_leading_bytes_limit = 4;
+	assert!(Metadata::new_from_path_with_options(path, &options)?.data().is_empty());
+
+	options.jpeg_leading_bytes_limit = 16;
+	let metadata = Metadata::new_from_path_with_options(path, &options)?;
+	assert_eq!(metadata.data(), Metadata::new_from_path(Path::new("tests/sample2_camera.jpg"))?.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_leading_garbage_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_leading_garbage_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_leading_garbage.jpg", "tests/sample2_leading_garbage_copy.jpg")?;
+	let path = Path::new("tests/sample2_leading_garbage_copy.jpg");
+
+	let original = std::fs::read(path)?;
+	let preamble_length = original.windows(2).position(|window| window == [0xff, 0xd8]).unwrap();
+
+	// Strict by default
+	assert!(get_test_metadata()?.write_to_file(path).is_err());
+	assert!(get_test_metadata()?.write_to_file_lenient(path, 4).is_err());
+	assert_eq!(std::fs::read(path)?, original);
+
+	get_test_metadata()?.write_to_file_lenient(path, 16)?;
+
+	// The leading bytes are kept in front of the JPEG data
+	let written = std::fs::read(path)?;
+	assert_eq!(&written[..preamble_length+2], &original[..preamble_length+2]);
+
+	let mut options = DecodeOptions::new();
+	options.jpeg_leading_bytes_limit = 16;
+	let metadata = Metadata::new_from_path_with_options(path, &options)?;
+	assert_eq!(metadata.data(), get_test_metadata()?.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_trailing_junk_after_ifds_jpg()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+
+	// A kilobyte of padding after the data of the last IFD, within the APP1
+	// segment
+	let app1 = metadata.as_u8_vec(FileExtension::JPEG);
+	let mut padded_app1 = app1[4..].to_vec();
+	padded_app1.extend([0xaa; 1024]);
+
+	let mut file_buffer = vec![0xff, 0xd8, 0xff, 0xe1];
+	file_buffer.extend(((padded_app1.len() + 2) as u16).to_be_bytes());
+	file_buffer.extend(padded_app1);
+	file_buffer.extend(&std::fs::read("tests/sample2.jpg")?[2..]);
+
+	let read_metadata = Metadata::new_from_vec_autodetect(&file_buffer)?;
+	assert_eq!(read_metadata.data(), metadata.data());
+	assert!(read_metadata.decode_warnings().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_large_ifd0_offset_jpg()
+-> Result<(), std::io::Error>
+{
+	// IFD0 starts at offset 0x200, with arbitrary data after the TIFF header
+	let path = Path::new("tests/sample2_large_ifd0_offset.jpg");
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_tag(&ExifTag::Make(String::new())), Some(&ExifTag::Make("Canon".to_string())));
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])), Some(&ExifTag::Orientation(vec![6])));
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(little_exif::read_camera(path)?, Some(("Canon".to_string(), "EOS".to_string())));
+
+	// Writing places IFD0 directly after the header again
+	let tiff = metadata.as_u8_vec(FileExtension::JPEG)[10..].to_vec();
+	assert_eq!(tiff[4..8], [0x00, 0x00, 0x00, 0x08]);
+	assert_eq!(Metadata::decode(&tiff, None)?.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	// Offsets beyond the end of the data or into the header are rejected
+	let file_buffer = std::fs::read(path)?;
+	let raw_exif = file_buffer[10..(file_buffer.len()-2)].to_vec();
+	for offset in [0x0000_0004u32, 0x0000_0250, 0x7fff_fff0, 0xffff_fffe]
+	{
+		let mut broken = raw_exif.clone();
+		broken[10..14].copy_from_slice(&offset.to_be_bytes());
+		assert!(Metadata::decode(&broken, None).is_err());
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+write_to_file_stamped_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_stamped_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_stamped_copy.jpg")?;
+	let path = Path::new("tests/sample2_stamped_copy.jpg");
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_software("Old Tool");
+	assert_eq!(metadata.get_software_trimmed(), Some("Old Tool"));
+
+	// Replaces the previous value, also in the struct itself
+	metadata.write_to_file_stamped(path, "little_exif test")?;
+	assert_eq!(metadata.get_software_trimmed(), Some("little_exif test"));
+
+	let written = Metadata::new_from_path(path)?;
+	assert_eq!(written.get_software_trimmed(), Some("little_exif test"));
+	assert_eq!(written.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_empty_metadata_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_empty_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_empty_copy.jpg")?;
+	let path = Path::new("tests/sample2_empty_copy.jpg");
+
+	// The TIFF header is followed by an IFD0 without entries, not linking
+	// to any other IFD
+	let mut expected = vec![0xff, 0xe1, 0x00, 0x16];
+	expected.extend(b"Exif\0\0");
+	expected.extend([0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00]);
+	expected.extend([0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	assert_eq!(Metadata::new().as_u8_vec(FileExtension::JPEG), expected);
+
+	Metadata::new().write_to_file(path)?;
+	assert!(find_subslice(&std::fs::read(path)?, &expected).is_some());
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert!(metadata.data().is_empty());
+	assert!(metadata.decode_warnings().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+as_u8_vec_filtered()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::GPSDifferential(vec![1]));
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0x01, 0x02, 0x03, 0xff, 0xd9]);
+
+	// Wraps the APP1 segment(s) into a minimal JPEG for decoding
+	let decode = |groups: &[ExifTagGroup]| {
+		let mut file_buffer = vec![0xff, 0xd8];
+		file_buffer.extend(metadata.as_u8_vec_filtered(FileExtension::JPEG, groups));
+		file_buffer.extend([0xff, 0xd9]);
+		return Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG);
+	};
+
+	// Requesting all groups is the same as the unfiltered encoding
+	let all = decode(&[
+		ExifTagGroup::IFD0, 
+		ExifTagGroup::ExifIFD, 
+		ExifTagGroup::InteropIFD, 
+		ExifTagGroup::GPSIFD, 
+		ExifTagGroup::IFD1
+	])?;
+	assert_eq!(all.data(), Metadata::from_header_bytes(
+		&[vec![0xff, 0xd8], metadata.as_u8_vec(FileExtension::JPEG), vec![0xff, 0xd9]].concat(), 
+		FileExtension::JPEG
+	)?.data());
+	assert!(all.get_thumbnail().is_some());
+
+	// Without GPS data and thumbnail
+	let read_metadata = decode(&[ExifTagGroup::IFD0, ExifTagGroup::ExifIFD])?;
+	assert_eq!(read_metadata.get_tag(&ExifTag::Model(String::new())), Some(&ExifTag::Model("Testcam(1)".to_string())));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])),          Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(read_metadata.get_tag(&ExifTag::GPSDifferential(vec![])), None);
+	assert_eq!(read_metadata.get_tag(&ExifTag::GPSInfo(vec![])),         None);
+	assert!(read_metadata.tags_in_group(ExifTagGroup::IFD1).is_empty());
+	assert_eq!(read_metadata.get_thumbnail(), None);
+
+	// Only the ExifIFD, still linked from an otherwise empty IFD0
+	let read_metadata = decode(&[ExifTagGroup::ExifIFD])?;
+	assert_eq!(read_metadata.get_tag(&ExifTag::Model(String::new())), None);
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])),          Some(&ExifTag::ISO(vec![2706])));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ExposureProgram(vec![])), Some(&ExifTag::ExposureProgram(vec![1])));
+	assert!(read_metadata.tags_in_group(ExifTagGroup::GPSIFD).is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+as_u8_vec_with_filter()
+-> Result<(), std::io::Error>
+{
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::GPSDifferential(vec![1]));
+	let tag_count = metadata.data().len();
+
+	let mut file_buffer = vec![0xff, 0xd8];
+	file_buffer.extend(metadata.as_u8_vec_with_filter(FileExtension::JPEG, |tag| !tag.is_gps_tag() && *tag != ExifTag::ISO(vec![2706])));
+	file_buffer.extend([0xff, 0xd9]);
+	let read_metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+
+	assert_eq!(read_metadata.get_tag(&ExifTag::Model(String::new())), Some(&ExifTag::Model("Testcam(1)".to_string())));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])),             None);
+	assert_eq!(read_metadata.get_tag(&ExifTag::GPSDifferential(vec![])), None);
+	assert_eq!(read_metadata.get_tag(&ExifTag::GPSInfo(vec![])),         None);
+
+	// Filtering nothing is the same as the unfiltered encoding, the metadata
+	// itself stays unchanged
+	assert_eq!(metadata.as_u8_vec_with_filter(FileExtension::JPEG, |_| true), metadata.as_u8_vec(FileExtension::JPEG));
+	assert_eq!(metadata.data().len(), tag_count);
+
+	Ok(())
+}
+
+#[test]
+fn
+as_minimal_jpeg()
+-> Result<(), std::io::Error>
+{
+	let metadata = get_test_metadata()?;
+	let jpeg = metadata.as_minimal_jpeg();
+
+	assert!(jpeg.starts_with(&[0xff, 0xd8, 0xff, 0xe1]));
+	assert!(jpeg.ends_with(&[0xff, 0xd9]));
+	assert_eq!(jpeg, [vec![0xff, 0xd8], metadata.as_u8_vec(FileExtension::JPEG), vec![0xff, 0xd9]].concat());
+
+	std::fs::write("tests/minimal_copy.jpg", &jpeg)?;
+	let read_metadata = Metadata::new_from_path(Path::new("tests/minimal_copy.jpg"))?;
+	assert_eq!(read_metadata.data(), metadata.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+ifd_bytes()
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::GPSDifferential(vec![1]));
+	metadata.set_tag(ExifTag::GPSMapDatum("WGS-84".to_string()));
+	metadata.set_tag(ExifTag::GPSStatus("A".to_string()));
+
+	let mut expected: Vec<u8> = vec![0x03, 0x00];
+	expected.extend([0x09, 0x00, 0x02, 0x00, 0x02, 0x00, 0x00, 0x00, b'A', 0x00, 0x00, 0x00]);
+	expected.extend([0x12, 0x00, 0x02, 0x00, 0x07, 0x00, 0x00, 0x00, 0x2a, 0x00, 0x00, 0x00]);
+	expected.extend([0x1e, 0x00, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+	expected.extend([0x00, 0x00, 0x00, 0x00]);
+	expected.extend(b"WGS-84\0");
+
+	assert_eq!(metadata.ifd_bytes(ExifTagGroup::GPSIFD), Some(expected));
+	assert_eq!(metadata.ifd_bytes(ExifTagGroup::ExifIFD), None);
+
+	// IFD0 only links to the GPS IFD, which is not included
+	assert_eq!(metadata.ifd_bytes(ExifTagGroup::IFD0), None);
+}
+
+#[test]
+fn
+approximate_memory_usage()
+{
+	assert_eq!(ExifTag::ImageDescription(String::with_capacity(100)).heap_size(), 100);
+	assert_eq!(ExifTag::ISO(Vec::with_capacity(3)).heap_size(), 6);
+
+	let mut metadata = Metadata::new();
+	let empty_usage = metadata.approximate_memory_usage();
+	assert!(empty_usage >= std::mem::size_of::<Metadata>());
+
+	metadata.set_tag(ExifTag::ImageDescription("x".repeat(1000)));
+	let tag_usage = metadata.approximate_memory_usage();
+	assert!(tag_usage >= empty_usage + 1000 + std::mem::size_of::<ExifTag>());
+
+	metadata.set_thumbnail(vec![0xff; 5000]);
+	assert!(metadata.approximate_memory_usage() >= tag_usage + 5000);
+}
+
+#[test]
+fn
+set_raw_tag_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	if let Err(error) = remove_file("tests/sample2_raw_tag_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_raw_tag_copy.jpg")?;
+
+	let mut metadata = Metadata::new();
+	metadata.set_raw_tag(0xc4a5, ExifTagFormat::UNDEF,  ExifTagGroup::IFD0,    vec![0x50, 0x72, 0x69, 0x6e, 0x74])?;
+	metadata.set_raw_tag(0xfedc, ExifTagFormat::INT16U, ExifTagGroup::GPSIFD,  vec![0x02, 0x01, 0x04, 0x03])?;
+	metadata.set_raw_tag(0x8827, ExifTagFormat::INT16U, ExifTagGroup::ExifIFD, vec![0x00, 0x01])?;
+
+	// Incomplete components and format mismatches of known tags
+	assert!(metadata.set_raw_tag(0xfedc, ExifTagFormat::INT32U, ExifTagGroup::GPSIFD,  vec![0x00; 6]).is_err());
+	assert!(metadata.set_raw_tag(0x8827, ExifTagFormat::INT32U, ExifTagGroup::ExifIFD, vec![0x00; 4]).is_err());
+
+	metadata.write_to_file(Path::new("tests/sample2_raw_tag_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_raw_tag_copy.jpg"))?;
+	assert!(read_metadata.data().contains(&ExifTag::UnknownUNDEF(vec![0x50, 0x72, 0x69, 0x6e, 0x74], 0xc4a5, ExifTagGroup::IFD0)));
+	assert!(read_metadata.data().contains(&ExifTag::UnknownINT16U(vec![0x0102, 0x0304], 0xfedc, ExifTagGroup::GPSIFD)));
+	assert_eq!(read_metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![256])));
+
+	Ok(())
+}
+
+#[test]
+fn
+rename_tag()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::UnknownSTRING("Title".to_string(), 0x0320, ExifTagGroup::IFD0));
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![1, 2], 0x1234, ExifTagGroup::ExifIFD));
+
+	// To a known tag of the same IFD
+	metadata.rename_tag(0x0320, 0x010e, ExifTagGroup::IFD0)?;
+	assert_eq!(metadata.get_tag_by_hex(0x0320), None);
+	assert_eq!(metadata.get_tag_by_hex(0x010e), Some(&ExifTag::ImageDescription("Title".to_string())));
+
+	// To an unknown tag, staying in the same IFD
+	metadata.rename_tag(0x1234, 0x1235, ExifTagGroup::ExifIFD)?;
+	assert_eq!(metadata.get_tag_by_hex(0x1234), None);
+	assert_eq!(metadata.get_tag_by_hex(0x1235), Some(&ExifTag::UnknownINT16U(vec![1, 2], 0x1235, ExifTagGroup::ExifIFD)));
+
+	// Incompatible formats (ExposureTime is a rational) and missing tags
+	let before = format!("{:?}", metadata.data());
+	assert_eq!(metadata.rename_tag(0x1235, 0x829a, ExifTagGroup::ExifIFD).map_err(|error| error.kind()), Err(std::io::ErrorKind::InvalidInput));
+	assert_eq!(metadata.rename_tag(0xbeef, 0x010e, ExifTagGroup::IFD0).map_err(|error| error.kind()), Err(std::io::ErrorKind::NotFound));
+	assert_eq!(metadata.rename_tag(0x1235, 0x1236, ExifTagGroup::IFD0).map_err(|error| error.kind()), Err(std::io::ErrorKind::NotFound));
+	assert_eq!(format!("{:?}", metadata.data()), before);
+
+	// The same hex value in two IFDs: InteroperabilityIndex of the InteropIFD
+	// and the (unknown to little_exif) GPSLatitudeRef of the GPSIFD
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()));
+	metadata.set_tag(ExifTag::UnknownSTRING("N".to_string(), 0x0001, ExifTagGroup::GPSIFD));
+	metadata.rename_tag(0x0001, 0x1001, ExifTagGroup::GPSIFD)?;
+	assert_eq!(metadata.get_string(0x0001, ExifTagGroup::InteropIFD), Some("R98".to_string()));
+	assert_eq!(metadata.get_string(0x0001, ExifTagGroup::GPSIFD),     None);
+	assert_eq!(metadata.get_string(0x1001, ExifTagGroup::GPSIFD),     Some("N".to_string()));
+	assert_eq!(metadata.get_string(0x1001, ExifTagGroup::InteropIFD), None);
+
+	Ok(())
+}
+
+#[test]
+fn
+decode_with_assumed_endian()
+-> Result<(), std::io::Error>
+{
+	let tiff = get_test_metadata()?.as_u8_vec(FileExtension::JPEG)[10..].to_vec();
+
+	// With and without the EXIF header, autodetecting the endianness
+	let mut raw_exif = b"Exif\0\0".to_vec();
+	raw_exif.extend(tiff.iter());
+	for data in [&tiff, &raw_exif]
+	{
+		let metadata = Metadata::decode(data, None)?;
+		assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+	}
+
+	// Without valid byte order information, the endianness has to be given
+	let mut broken_tiff = tiff.clone();
+	broken_tiff[0..2].copy_from_slice(b"XX");
+	assert!(Metadata::decode(&broken_tiff, None).is_err());
+
+	let metadata = Metadata::decode(&broken_tiff, Some(little_exif::endian::Endian::Little))?;
+	assert_eq!(metadata.get_endian(), &little_exif::endian::Endian::Little);
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![2706])));
+
+	let Err(error) = Metadata::decode(&broken_tiff[..8], Some(little_exif::endian::Endian::Little))
+	else
+	{
+		panic!("Decoding 8 bytes of TIFF data should fail");
+	};
+	assert!(error.to_string().contains("decoding"));
+
+	Ok(())
+}
+
+#[test]
+fn
+encode_checked()
+-> Result<(), std::io::Error>
+{
+	let mut with_thumbnail = get_test_metadata()?;
+	with_thumbnail.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+
+	for metadata in [Metadata::new_from_path(Path::new("tests/sample2.jpg"))?, get_test_metadata()?, Metadata::new(), with_thumbnail]
+	{
+		assert_eq!(metadata.encode_checked(FileExtension::JPEG)?, metadata.as_u8_vec(FileExtension::JPEG));
+		assert_eq!(metadata.encode_checked(FileExtension::WEBP)?, metadata.as_u8_vec(FileExtension::WEBP));
+		assert_eq!(
+			metadata.encode_checked(FileExtension::PNG { as_zTXt_chunk: true })?, 
+			metadata.as_u8_vec(FileExtension::PNG { as_zTXt_chunk: true })
+		);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+unknown_tags_keep_their_ifd_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	if let Err(error) = remove_file("tests/sample2_unknown_ifd_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_unknown_ifd_copy.jpg")?;
+	let path = Path::new("tests/sample2_unknown_ifd_copy.jpg");
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::MakerNote(vec![0x01, 0x02, 0x03, 0x04, 0x05]));
+	metadata.set_tag(ExifTag::UnknownUNDEF(vec![0x06, 0x07, 0x08, 0x09, 0x0a], 0x927d, ExifTagGroup::ExifIFD));
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![42], 0xc7ff, ExifTagGroup::IFD0));
+	metadata.write_to_file(path)?;
+
+	// Decoding and writing again must not move the unknown tags to another IFD
+	Metadata::new_from_path(path)?.write_to_file(path)?;
+
+	let read_metadata = Metadata::new_from_path(path)?;
+	assert_eq!(read_metadata.tags_in_group(ExifTagGroup::ExifIFD), vec![
+		&ExifTag::MakerNote(vec![0x01, 0x02, 0x03, 0x04, 0x05]),
+		&ExifTag::UnknownUNDEF(vec![0x06, 0x07, 0x08, 0x09, 0x0a], 0x927d, ExifTagGroup::ExifIFD),
+	]);
+	assert!(read_metadata.data().contains(&ExifTag::UnknownINT16U(vec![42], 0xc7ff, ExifTagGroup::IFD0)));
+
+	Ok(())
+}
+
+#[test]
+fn
+offset_tags_are_structural_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_offset_tags_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_offset_tags_copy.jpg")?;
+	let path = Path::new("tests/sample2_offset_tags_copy.jpg");
+
+	// The file links to an ExifIFD, but the ExifOffset tag is not surfaced
+	let metadata = Metadata::new_from_path(path)?;
+	assert!(metadata.data().iter().any(|tag| tag.get_group() == little_exif::exif_tag::ExifTagGroup::ExifIFD));
+	assert!(metadata.data().iter().all(|tag| tag.is_offset_tag().is_none()));
+
+	// Offset tags set by the user are replaced by the computed ones
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::ExifOffset(vec![0xdead]));
+	metadata.set_tag(ExifTag::GPSInfo(vec![0xbeef]));
+	assert_eq!(metadata.write_plan().iter().filter(|(_, hex)| *hex == 0x8769).count(), 1);
+	assert_eq!(metadata.write_plan().iter().filter(|(_, hex)| *hex == 0x8825).count(), 0);
+	metadata.write_to_file(path)?;
+
+	let read_metadata = Metadata::new_from_path(path)?;
+	assert_eq!(read_metadata.data(), get_test_metadata()?.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_plan()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ISO(vec![100]));
+	metadata.set_tag(ExifTag::Artist("Jane Doe".to_string()));
+	metadata.set_tag(ExifTag::InteroperabilityIndex("R98".to_string()));
+	metadata.set_tag(ExifTag::ImageDescription("Plan".to_string()));
+	metadata.set_tag(ExifTag::GPSMapDatum("WGS-84".to_string()));
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0xff, 0xd9]);
+
+	assert_eq!(metadata.write_plan(), vec![
+		(ExifTagGroup::IFD0,       0x010e),
+		(ExifTagGroup::IFD0,       0x013b),
+		(ExifTagGroup::IFD0,       0x8769),                                     // ExifOffset
+		(ExifTagGroup::IFD0,       0x8825),                                     // GPSInfo
+		(ExifTagGroup::ExifIFD,    0x8827),
+		(ExifTagGroup::ExifIFD,    0xa005),                                     // InteropOffset
+		(ExifTagGroup::InteropIFD, 0x0001),
+		(ExifTagGroup::GPSIFD,     0x0012),
+		(ExifTagGroup::IFD1,       0x0103),                                     // Compression for the JPEG thumbnail
+		(ExifTagGroup::IFD1,       0x0201),                                     // ThumbnailOffset
+		(ExifTagGroup::IFD1,       0x0202),                                     // ThumbnailLength
+	]);
+
+	// Same number of entries as actually written
+	let encoded = metadata.as_minimal_jpeg();
+	let decoded = Metadata::new_from_vec_autodetect(&encoded)?;
+	assert_eq!(decoded.raw_entry_count(), metadata.write_plan().len());
+
+	assert!(Metadata::new().write_plan().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+size_delta()
+-> Result<(), std::io::Error>
+{
+	for (source, copy_path) in [
+		("tests/sample2.jpg",                  "tests/sample2_size_delta_copy.jpg"),
+		("tests/sample2.png",                  "tests/sample2_size_delta_copy.png"),
+		("tests/sample2_simple_loseless.webp", "tests/sample2_size_delta_copy.webp"),
+	]
+	{
+		if let Err(error) = remove_file(copy_path)
+		{
+			println!("{}", error);
+		}
+		copy(source, copy_path)?;
+		let path = Path::new(copy_path);
+
+		let mut metadata = get_test_metadata()?;
+		metadata.set_tag(ExifTag::Artist("A".repeat(1000)));
+
+		let original_size = std::fs::metadata(path)?.len() as i64;
+		let delta = metadata.size_delta(path)?;
+		assert_eq!(std::fs::metadata(path)?.len() as i64, original_size);
+
+		metadata.write_to_file(path)?;
+		assert_eq!(std::fs::metadata(path)?.len() as i64, original_size + delta);
+
+		// Writing the same metadata again doesn't change the size, writing 
+		// less shrinks the file
+		assert_eq!(metadata.size_delta(path)?, 0);
+		assert!(get_test_metadata()?.size_delta(path)? < 0);
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+normalize()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	let mut metadata = Metadata::new();
+	metadata.set_raw_tag(0x8827, ExifTagFormat::INT32U, ExifTagGroup::IFD0,    vec![0x90, 0x01, 0x00, 0x00])?;
+	metadata.set_raw_tag(0x0112, ExifTagFormat::INT16U, ExifTagGroup::ExifIFD, vec![0x06, 0x00])?;
+	metadata.set_raw_tag(0x010f, ExifTagFormat::UNDEF,  ExifTagGroup::ExifIFD, vec![0x41])?;
+	metadata.set_raw_tag(0xc4a5, ExifTagFormat::UNDEF,  ExifTagGroup::IFD0,    vec![0x50])?;
+	metadata.set_tag(ExifTag::Orientation(vec![1]));
+
+	metadata.normalize();
+
+	// The ISO tag gets moved to the ExifIFD and converted to INT16U, the 
+	// misplaced Orientation gets dropped in favor of the existing one
+	assert_eq!(metadata.data(), &vec![
+		ExifTag::Orientation(vec![1]),
+		ExifTag::UnknownUNDEF(vec![0x50], 0xc4a5, ExifTagGroup::IFD0),
+		ExifTag::UnknownUNDEF(vec![0x41], 0x010f, ExifTagGroup::ExifIFD),
+		ExifTag::ISO(vec![400]),
+	]);
+
+	Ok(())
+}
+
+#[test]
+fn
+set_tag_sorts_by_hex_value()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	// Unknown tags are sorted in between the known ones of their IFD
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::Model("Model".to_string()));
+	metadata.set_raw_tag(0x0105, ExifTagFormat::INT16U, ExifTagGroup::IFD0, vec![0x01, 0x00])?;
+	metadata.set_tag(ExifTag::ISO(vec![400]));
+	metadata.set_tag(ExifTag::ImageWidth(vec![100]));
+
+	assert_eq!(metadata.data(), &vec![
+		ExifTag::ImageWidth(vec![100]),
+		ExifTag::UnknownINT16U(vec![1], 0x0105, ExifTagGroup::IFD0),
+		ExifTag::Model("Model".to_string()),
+		ExifTag::ISO(vec![400]),
+	]);
+
+	// The entries of the written IFD0 (little endian by default) are in
+	// strictly ascending order
+	let ifd0 = metadata.ifd_bytes(ExifTagGroup::IFD0).unwrap();
+	let entry_count = u16::from_le_bytes([ifd0[0], ifd0[1]]) as usize;
+	let hex_values = (0..entry_count)
+		.map(|index| u16::from_le_bytes([ifd0[2 + 12*index], ifd0[3 + 12*index]]))
+		.collect::<Vec<u16>>();
+	assert!(hex_values.windows(2).all(|pair| pair[0] < pair[1]));
+	assert!(hex_values.contains(&0x0105));
+
+	Ok(())
+}
+
+#[test]
+fn
+read_and_write_padding_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	// Padding tags with 1590 bytes each in IFD0 and the ExifIFD
+	let mut padding = vec![0x1c, 0xea, 0x00, 0x00, 0x00, 0x08];
+	padding.extend(vec![0x00; 1584]);
+
+	// Little endian EXIF data with these tags, as written by Microsoft tools
+	std::fs::write("tests/sample2_padding_copy.jpg", minimal_jpg(&[
+		0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x01, 0x00,
+		0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00,
+	]))?;
+	let mut metadata = Metadata::new_from_path(Path::new("tests/sample2_padding_copy.jpg"))?;
+	metadata.set_tag(ExifTag::Padding(padding.clone()));
+	metadata.set_tag(ExifTag::UnknownUNDEF(padding.clone(), 0xea1c, ExifTagGroup::ExifIFD));
+	metadata.set_tag(ExifTag::ISO(vec![100]));
+	metadata.write_to_file(Path::new("tests/sample2_padding_copy.jpg"))?;
+
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_padding_copy.jpg"))?;
+	assert_eq!(metadata.get_endian(), &little_exif::endian::Endian::Little);
+	assert!(metadata.data().contains(&ExifTag::Padding(padding.clone())));
+	assert!(metadata.data().contains(&ExifTag::UnknownUNDEF(padding.clone(), 0xea1c, ExifTagGroup::ExifIFD)));
+	assert_eq!(metadata.data().iter().filter(|tag| tag.is_padding_tag()).count(), 2);
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])), Some(&ExifTag::ISO(vec![100])));
+
+	// Round trip keeps the reserved space
+	let file_size = std::fs::metadata("tests/sample2_padding_copy.jpg")?.len();
+	metadata.write_to_file(Path::new("tests/sample2_padding_copy.jpg"))?;
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_padding_copy.jpg"))?;
+	assert!(metadata.diff(&read_metadata).is_empty());
+	assert_eq!(std::fs::metadata("tests/sample2_padding_copy.jpg")?.len(), file_size);
+
+	// Stripping it
+	let mut stripped = read_metadata;
+	stripped.retain(|tag| !tag.is_padding_tag());
+	stripped.write_to_file(Path::new("tests/sample2_padding_copy.jpg"))?;
+	assert_eq!(std::fs::metadata("tests/sample2_padding_copy.jpg")?.len(), file_size - 2 * (12 + 1590));
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_sensitivity_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	if let Err(error) = remove_file("tests/sample2_sensitivity_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_sensitivity_copy.jpg")?;
+
+	// SensitivityType 2: Recommended exposure index, as recorded by cameras
+	// next to the legacy ISO tag
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::ISO(vec![800]));
+	metadata.set_tag(ExifTag::SensitivityType(vec![2]));
+	metadata.set_tag(ExifTag::StandardOutputSensitivity(vec![800]));
+	metadata.set_tag(ExifTag::RecommendedExposureIndex(vec![640]));
+	metadata.set_tag(ExifTag::ISOSpeed(vec![800]));
+	for tag in metadata.data()
+	{
+		assert!(!tag.is_unknown());
+		assert_eq!(tag.get_group(), ExifTagGroup::ExifIFD);
+	}
+	metadata.write_to_file(Path::new("tests/sample2_sensitivity_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_sensitivity_copy.jpg"))?;
+	assert_eq!(read_metadata.data(), metadata.data());
+	assert_eq!(
+		read_metadata.get_tag(&ExifTag::RecommendedExposureIndex(vec![])),
+		Some(&ExifTag::RecommendedExposureIndex(vec![640]))
+	);
+
+	Ok(())
+}
+
+/// A simple xorshift pseudo random number generator for the fuzz-like tests,
+/// giving reproducible results for a given seed
+fn
+pseudo_random_generator
+(
+	seed: u64
+)
+-> impl FnMut() -> u64
+{
+	let mut state = seed;
+	return move || {
+		state ^= state << 13;
+		state ^= state >> 7;
+		state ^= state << 17;
+		return state;
+	};
+}
+
+#[test]
+fn
+decode_mutated_data()
+{
+	// Decoding randomly altered or truncated files must never panic
+	let mut next = pseudo_random_generator(0x2545f4914f6cdd1d);
+	for path in [
+		"tests/sample2_thumbnail.jpg",
+		"tests/sample2_camera.jpg",
+		"tests/sample2.png",
+		"tests/sample2_extended.webp",
+	]
+	{
+		let file_type = || match Path::new(path).extension().unwrap().to_str()
+		{
+			Some("jpg") => FileExtension::JPEG,
+			Some("png") => FileExtension::PNG { as_zTXt_chunk: true },
+			_           => FileExtension::WEBP,
+		};
+		let original = std::fs::read(path).unwrap();
+		let mutable_length = original.len().min(4096);
+
+		for _ in 0..1000
+		{
+			let mut file_buffer = original.clone();
+			for _ in 0..(next() % 6 + 1)
+			{
+				file_buffer[next() as usize % mutable_length] = next() as u8;
+			}
+			if next() % 5 == 0
+			{
+				file_buffer.truncate(next() as usize % original.len());
+			}
+
+			let _ = Metadata::from_header_bytes(&file_buffer, file_type());
+		}
+	}
+}
+
+#[test]
+fn
+round_trip_random_metadata()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	let writable_tags: Vec<u16> = (0..=0xffff)
+		.filter(|hex| ExifTag::from_u16(*hex)
+			.map(|tag| tag.is_writable() && tag.get_group() != ExifTagGroup::NO_GROUP)
+			.unwrap_or(false)
+		)
+		.collect();
+
+	let mut next = pseudo_random_generator(0x9e3779b97f4a7c15);
+	for _ in 0..1000
+	{
+		// Random known tags with a valid number of components and values 
+		// that survive a round trip unchanged (ASCII strings, no NaN)
+		let mut metadata = Metadata::new();
+		for _ in 0..(next() % 12)
+		{
+			let hex    = writable_tags[next() as usize % writable_tags.len()];
+			let empty  = ExifTag::from_u16(hex).unwrap();
+			let format = empty.format();
+
+			let number_of_components = if empty.has_valid_number_of_components()
+			{
+				(next() % 8) as usize + (format != ExifTagFormat::STRING) as usize
+			}
+			else
+			{
+				empty.number_of_components() as usize - empty.is_string() as usize
+			};
+
+			let raw_data: Vec<u8> = (0..(number_of_components * format.bytes_per_component() as usize))
+				.map(|_| match format
+				{
+					ExifTagFormat::STRING => (next() % 95 + 0x20) as u8,
+					ExifTagFormat::FLOAT  |
+					ExifTagFormat::DOUBLE => (next() % 0x40) as u8,
+					_                     => next() as u8,
+				})
+				.collect();
+
+			metadata.set_tag(ExifTag::from_u16_with_data(hex, &format, &raw_data, &Endian::Little, &empty.get_group()).unwrap());
+		}
+		if next() % 4 == 0
+		{
+			metadata.set_thumbnail(vec![0xff, 0xd8, next() as u8, 0xff, 0xd9]);
+		}
+
+		let file_buffer = [vec![0xff, 0xd8], metadata.as_u8_vec(FileExtension::JPEG), vec![0xff, 0xd9]].concat();
+		let decoded = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+
+		assert!(metadata.diff(&decoded).is_empty(), "{:?}", metadata.data());
+		assert_eq!(metadata.get_thumbnail(), decoded.get_thumbnail());
+	}
+
+	Ok(())
+}
+
+#[test]
+fn
+write_thumbnail_compression_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::TagValue;
+
+	if let Err(error) = remove_file("tests/sample2_compression_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_compression_copy.jpg")?;
+
+	let compression = |metadata: &Metadata| metadata
+		.tags_in_group(ExifTagGroup::IFD1)
+		.into_iter()
+		.filter(|tag| tag.as_u16() == 0x0103)
+		.map(|tag| tag.components())
+		.collect::<Vec<_>>();
+
+	// Setting a JPEG thumbnail sets the Compression of IFD1
+	let mut metadata = get_test_metadata()?;
+	metadata.set_thumbnail(vec![0xff, 0xd8, 0x01, 0x02, 0x03, 0xff, 0xd9]);
+	assert_eq!(compression(&metadata), vec![TagValue::Short(vec![6])]);
+	metadata.write_to_file(Path::new("tests/sample2_compression_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_compression_copy.jpg"))?;
+	assert_eq!(compression(&read_metadata), vec![TagValue::Short(vec![6])]);
+	assert_eq!(ExifTag::UnknownINT16U(vec![6], 0x0103, ExifTagGroup::IFD1).interpret(), Some("JPEG (old-style)"));
+	assert!(read_metadata.diff(&metadata).is_empty());
+
+	// An inconsistent value gets corrected when writing
+	metadata.set_tag(ExifTag::UnknownINT16U(vec![1], 0x0103, ExifTagGroup::IFD1));
+	metadata.write_to_file(Path::new("tests/sample2_compression_copy.jpg"))?;
+
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_compression_copy.jpg"))?;
+	assert_eq!(compression(&read_metadata), vec![TagValue::Short(vec![6])]);
+
+	// Removing the thumbnail also removes its Compression
+	metadata.clear_thumbnail();
+	assert!(compression(&metadata).is_empty());
+
+	assert_eq!(ExifTag::Compression(vec![1]).interpret(), Some("Uncompressed"));
+
+	Ok(())
+}
+
+#[test]
+fn
+preserve_source_order()
+-> Result<(), std::io::Error>
+{
+	// Big endian IFD0 with Model before Make (i.e. not sorted ascending), 
+	// both values fitting into their entries
+	let tiff_data: Vec<u8> = [
+		vec![0x4d, 0x4d, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08],                  // TIFF header
+		vec![0x00, 0x02],                                                      // Number of entries
+		vec![0x01, 0x10, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, b'X', b'Y', 0x00, 0x00], // Model
+		vec![0x01, 0x0f, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, b'A', b'B', 0x00, 0x00], // Make
+		vec![0x00, 0x00, 0x00, 0x00],                                          // No next IFD
+	].concat();
+	let file_buffer = [
+		vec![0xff, 0xd8, 0xff, 0xe1, 0x00, (2 + 6 + tiff_data.len()) as u8],
+		b"Exif\0\0".to_vec(),
+		tiff_data,
+		vec![0xff, 0xd9]
+	].concat();
+
+	let mut metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+	metadata.set_tag(ExifTag::Model("XZ".to_string()));
+
+	// By default, the entries get sorted
+	let mut output = Vec::new();
+	metadata.write_to_writer(&file_buffer, &mut output, FileExtension::JPEG)?;
+	assert_eq!(output.len(), file_buffer.len());
+	assert_ne!(output[20..44], file_buffer[20..44]);
+
+	// Otherwise only the changed value differs
+	metadata.set_preserve_source_order(true);
+	let mut output = Vec::new();
+	metadata.write_to_writer(&file_buffer, &mut output, FileExtension::JPEG)?;
+
+	let mut expected = file_buffer.clone();
+	expected[31] = b'Z';
+	assert_eq!(output, expected);
+
+	Ok(())
+}
+
+#[test]
+fn
+preserve_source_formats()
+-> Result<(), std::io::Error>
+{
+	// Big endian IFD0 with the ImageWidth stored as INT16U instead of INT32U
+	let tiff_data: Vec<u8> = [
+		vec![0x4d, 0x4d, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08],                  // TIFF header
+		vec![0x00, 0x02],                                                      // Number of entries
+		vec![0x01, 0x00, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x02, 0x80, 0x00, 0x00], // ImageWidth
+		vec![0x01, 0x0f, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, b'A', b'B', 0x00, 0x00], // Make
+		vec![0x00, 0x00, 0x00, 0x00],                                          // No next IFD
+	].concat();
+	let file_buffer = minimal_jpg(&tiff_data);
+
+	// The value gets converted to the specified format
+	let mut metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+	assert_eq!(metadata.get_tag(&ExifTag::ImageWidth(vec![])), Some(&ExifTag::ImageWidth(vec![640])));
+	assert!(metadata.decode_warnings().is_empty());
+
+	// By default, it is written using that format...
+	let mut output = Vec::new();
+	metadata.write_to_writer(&file_buffer, &mut output, FileExtension::JPEG)?;
+	assert_eq!(output[24..26], [0x00, 0x04]);
+
+	// ...otherwise using the one of the source, as long as the value fits
+	metadata.set_preserve_source_order(true);
+	let mut output = Vec::new();
+	metadata.write_to_writer(&file_buffer, &mut output, FileExtension::JPEG)?;
+	assert_eq!(output, file_buffer);
+
+	metadata.set_tag(ExifTag::ImageWidth(vec![70000]));
+	let mut output = Vec::new();
+	metadata.write_to_writer(&file_buffer, &mut output, FileExtension::JPEG)?;
+	assert_eq!(output[24..26], [0x00, 0x04]);
+	assert_eq!(Metadata::from_header_bytes(&output, FileExtension::JPEG)?.get_tag(&ExifTag::ImageWidth(vec![])), Some(&ExifTag::ImageWidth(vec![70000])));
+
+	Ok(())
+}
+
+#[test]
+fn
+best_capture_time()
+{
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.best_capture_time(), None);
+
+	// Falls back to the ModifyDate of IFD0...
+	metadata.set_tag(ExifTag::ModifyDate("2024:03:01 12:00:00".to_string()));
+	assert_eq!(metadata.get_modify_date(),   Some("2024:03:01 12:00:00"));
+	assert_eq!(metadata.best_capture_time(), Some("2024:03:01 12:00:00"));
+
+	// ...prefers the CreateDate (digitized), unless it is not set...
+	metadata.set_tag(ExifTag::CreateDate("    :  :     :  :  ".to_string()));
+	assert_eq!(metadata.get_create_date(),   None);
+	assert_eq!(metadata.best_capture_time(), Some("2024:03:01 12:00:00"));
+	metadata.set_tag(ExifTag::CreateDate("2024:02:01 12:00:00".to_string()));
+	assert_eq!(metadata.best_capture_time(), Some("2024:02:01 12:00:00"));
+
+	// ...and the DateTimeOriginal even more
+	metadata.set_tag(ExifTag::DateTimeOriginal("0000:00:00 00:00:00".to_string()));
+	assert_eq!(metadata.best_capture_time(), Some("2024:02:01 12:00:00"));
+	metadata.set_tag(ExifTag::DateTimeOriginal("2024:01:01 12:00:00\0".to_string()));
+	assert_eq!(metadata.get_date_time_original(), Some("2024:01:01 12:00:00"));
+	assert_eq!(metadata.best_capture_time(),      Some("2024:01:01 12:00:00"));
+}
+
+#[test]
+fn
+clear_duplicate_metadata_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_duplicate_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_duplicate_copy.jpg")?;
+	let path = Path::new("tests/sample2_duplicate_copy.jpg");
+
+	// The primary EXIF data is split across several APP1 segments due to 
+	// its large thumbnail
+	let mut primary = get_test_metadata()?;
+	primary.set_thumbnail([vec![0xff, 0xd8], vec![0x42; 150_000], vec![0xff, 0xd9]].concat());
+	primary.write_to_file(path)?;
+	let expected = std::fs::read(path)?;
+	let primary_length = primary.as_u8_vec(FileExtension::JPEG).len();
+
+	// Followed by an XMP and another EXIF APP1 segment
+	let xmp = b"http://ns.adobe.com/xap/1.0/\0<x:xmpmeta/>";
+	let mut xmp_segment = vec![0xff, 0xe1, 0x00, (2 + xmp.len()) as u8];
+	xmp_segment.extend(xmp);
+
+	let mut duplicate = Metadata::new();
+	duplicate.set_tag(ExifTag::Artist("Someone else".to_string()));
+
+	let split = 2 + primary_length;
+	let file_buffer = [
+		expected[..split].to_vec(),
+		xmp_segment.clone(),
+		duplicate.as_u8_vec(FileExtension::JPEG),
+		expected[split..].to_vec()
+	].concat();
+	std::fs::write(path, &file_buffer)?;
+
+	Metadata::clear_duplicate_metadata(path)?;
+	assert_eq!(std::fs::read(path)?, [expected[..split].to_vec(), xmp_segment, expected[split..].to_vec()].concat());
+
+	let read_metadata = Metadata::new_from_path(path)?;
+	assert_eq!(read_metadata.get_thumbnail(), primary.get_thumbnail());
+	assert_eq!(read_metadata.get_tag(&ExifTag::Artist(String::new())), None);
+
+	// No duplicates for other file types
+	assert!(Metadata::clear_duplicate_metadata(Path::new("tests/sample2.png")).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+jpg_segment_iter()
+-> Result<(), std::io::Error>
+{
+	use std::io::Cursor;
+	use little_exif::jpg::SegmentIter;
+
+	let file = std::io::BufReader::new(std::fs::File::open("tests/sample2.jpg")?);
+	let mut segments = SegmentIter::new(file)?;
+	let mut markers = Vec::new();
+	while let Some(segment) = segments.next()
+	{
+		let segment = segment?;
+		if segment.marker() == 0xe1
+		{
+			assert!(segments.read_data(&segment)?.starts_with(b"Exif\0\0"));
+		}
+		markers.push(segment.marker());
+	}
+	assert!(markers.contains(&0xe1));
+	assert!(markers.contains(&0xda));
+	assert_eq!(markers.last(), Some(&0xd9));
+
+	// Two scans, with stuffed bytes, restart and fill markers within the
+	// entropy coded data and another segment between the scans
+	let mut file_buffer: Vec<u8> = vec![0xff, 0xd8];
+	file_buffer.extend([0xff, 0xe0, 0x00, 0x04, 0x01, 0x02]);
+	file_buffer.extend([0xff, 0xda, 0x00, 0x03, 0x00]);
+	file_buffer.extend([0x12, 0xff, 0x00, 0x34, 0xff, 0xd0, 0x56, 0xff, 0xff, 0x00]);
+	file_buffer.extend([0xff, 0xff, 0xfe, 0x00, 0x05, b'a', b'b', b'c']);
+	file_buffer.extend([0xff, 0xda, 0x00, 0x02]);
+	file_buffer.extend([0x78, 0xff, 0x00]);
+	file_buffer.extend([0xff, 0xd9]);
+
+	let mut segments = SegmentIter::new(Cursor::new(file_buffer))?;
+	let mut found = Vec::new();
+	while let Some(segment) = segments.next()
+	{
+		let segment = segment?;
+		found.push((segment.marker(), segment.offset(), segments.read_data(&segment)?));
+	}
+	assert_eq!(found, vec![
+		(0xe0,  2, vec![0x01, 0x02]),
+		(0xda,  8, vec![0x00]),
+		(0xfe, 24, b"abc".to_vec()),
+		(0xda, 31, vec![]),
+		(0xd9, 38, vec![]),
+	]);
+
+	// Not a JPEG or truncated
+	assert!(SegmentIter::new(Cursor::new(vec![0x89, 0x50])).is_err());
+	let mut truncated = SegmentIter::new(Cursor::new(vec![0xff, 0xd8, 0xff, 0xe1, 0x00]))?;
+	assert_eq!(required_length_of(&truncated.next().unwrap().unwrap_err()), 6);
+	assert!(truncated.next().is_none());
+
+	// The byte based readers and writers walk the segments the same way: A
+	// segment with an invalid length stops the walk, so the APP1 segment 
+	// after it is neither read nor removed
+	let file_buffer = [
+		vec![0xff, 0xd8, 0xff, 0xff, 0xe0, 0x00, 0x01],
+		get_test_metadata()?.as_minimal_jpeg()[2..].to_vec(),
+	].concat();
+	assert!(Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?.data().is_empty());
+	assert_eq!(Metadata::clear_metadata_from_bytes(&file_buffer, FileExtension::JPEG)?, file_buffer);
+
+	// With a valid length, both find the APP1 segment behind the fill byte
+	let mut file_buffer = file_buffer;
+	file_buffer[6] = 0x02;
+	assert!(!Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?.data().is_empty());
+	assert_eq!(Metadata::clear_metadata_from_bytes(&file_buffer, FileExtension::JPEG)?, vec![0xff, 0xd8, 0xff, 0xff, 0xe0, 0x00, 0x02, 0xff, 0xd9]);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_adobe_transform_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::jpg::read_adobe_transform;
+
+	// APP14 segment with the YCbCr transform
+	assert_eq!(read_adobe_transform(Path::new("tests/sample2_adobe.jpg"))?, Some(1));
+	assert_eq!(read_adobe_transform(Path::new("tests/sample2.jpg"))?,       None);
+	assert!(read_adobe_transform(Path::new("tests/sample2.png")).is_err());
+	assert_eq!(
+		read_adobe_transform(Path::new("tests/does_not_exist.jpg")).unwrap_err().kind(),
+		std::io::ErrorKind::NotFound
+	);
+
+	// The EXIF data is not affected
+	assert_eq!(
+		Metadata::new_from_path(Path::new("tests/sample2_adobe.jpg"))?.data(),
+		Metadata::new_from_path(Path::new("tests/sample2.jpg"))?.data()
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_clear_keeps_adobe_segment_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::jpg::read_adobe_transform;
+
+	// APP14 segment with the YCCK transform, as used by CMYK JPEGs
+	if let Err(error) = remove_file("tests/sample2_adobe_ycck_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2_adobe_ycck.jpg", "tests/sample2_adobe_ycck_copy.jpg")?;
+	let path = Path::new("tests/sample2_adobe_ycck_copy.jpg");
+
+	let adobe_segment = std::fs::read(path)?[2..18].to_vec();
+	assert_eq!(read_adobe_transform(path)?, Some(2));
+
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag(ExifTag::ImageDescription("CMYK".to_string()));
+	metadata.write_to_file(path)?;
+	assert_eq!(read_adobe_transform(path)?, Some(2));
+	assert!(std::fs::read(path)?.windows(adobe_segment.len()).any(|window| window == adobe_segment));
+
+	Metadata::clear_metadata(path)?;
+	assert_eq!(read_adobe_transform(path)?, Some(2));
+	assert!(std::fs::read(path)?.windows(adobe_segment.len()).any(|window| window == adobe_segment));
+	assert!(Metadata::new_from_path(path)?.data().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+file_extension_capabilities()
+{
+	use std::str::FromStr;
+
+	for file_type in [FileExtension::JPEG, FileExtension::PNG { as_zTXt_chunk: true }, FileExtension::WEBP]
+	{
+		assert!(file_type.supports_read());
+		assert!(file_type.supports_write());
+		assert_eq!(FileExtension::from_str(&file_type.to_string()), Ok(file_type));
+	}
+	assert_eq!(FileExtension::JPEG.to_string(), "jpg");
+}
+
+#[test]
+fn
+read_ifd_cycle_jpg()
+-> Result<(), std::io::Error>
+{
+	// The GPSInfo tag of IFD0 points back to IFD0 itself
+	let tiff_data = [
+		0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x02, 0x00,
+		0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x25, 0x88, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x00, 0x00, 0x00, 0x00,
+	];
+	let metadata = Metadata::new_from_vec_autodetect(&minimal_jpg(&tiff_data))?;
+	assert!(metadata.data().is_empty());
+
+	// Limiting the depth, e.g. to just IFD0 and IFD1
+	let path = Path::new("tests/sample2_camera.jpg");
+	assert!(!Metadata::new_from_path(path)?.data().is_empty());
+
+	let mut options = DecodeOptions::new();
+	assert_eq!(options.max_ifd_depth, 8);
+	options.max_ifd_depth = 1;
+	assert!(Metadata::new_from_path_with_options(path, &options)?.data().is_empty());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_ifd1_cycle_jpg()
+-> Result<(), std::io::Error>
+{
+	// The link of IFD0 to IFD1 points back to IFD0 itself
+	let tiff_data = [
+		0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00,
+		0x01, 0x00,
+		0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+		0x08, 0x00, 0x00, 0x00,
+	];
+	let metadata = Metadata::new_from_vec_autodetect(&minimal_jpg(&tiff_data))?;
+	assert!(!metadata.data().is_empty());
+	assert!(metadata.tags_in_group(little_exif::exif_tag::ExifTagGroup::IFD1).is_empty());
+
+	assert_eq!(metadata.decode_warnings().len(), 1);
+	assert_eq!(metadata.decode_warnings()[0].group, little_exif::exif_tag::ExifTagGroup::IFD1);
+	assert!(metadata.decode_warnings()[0].message.contains("links to itself"));
+
+	Ok(())
+}
+
+#[test]
+fn
+from_u16_with_data_invalid_data()
+{
+	use little_exif::endian::Endian;
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	// Orientation is an INT16U tag, so 3 bytes are no whole component
+	assert!(ExifTag::from_u16_with_data(
+		0x0112, &ExifTagFormat::INT16U, &vec![0, 1, 0], &Endian::Little, &ExifTagGroup::IFD0
+	).is_err());
+
+	// The format of known tags has to match
+	assert!(ExifTag::from_u16_with_data(
+		0x0112, &ExifTagFormat::INT8U, &vec![1], &Endian::Little, &ExifTagGroup::IFD0
+	).is_err());
+
+	// The same applies to unknown tags, apart from the format check
+	assert!(ExifTag::from_u16_with_data(
+		0xfff0, &ExifTagFormat::RATIONAL64U, &vec![0; 12], &Endian::Little, &ExifTagGroup::IFD0
+	).is_err());
+	assert!(ExifTag::from_u16_with_data(
+		0xfff0, &ExifTagFormat::INT32U, &vec![0; 12], &Endian::Little, &ExifTagGroup::IFD0
+	).is_ok());
+
+	assert_eq!(
+		ExifTag::from_u16_with_data(
+			0x0112, &ExifTagFormat::INT16U, &vec![0, 6], &Endian::Big, &ExifTagGroup::IFD0
+		),
+		Ok(ExifTag::Orientation(vec![6]))
+	);
+}
+
+#[test]
+fn
+try_from_u16_for_format_and_group()
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	for hex_code in 0x0001..=0x000c
+	{
+		assert_eq!(ExifTagFormat::try_from(hex_code).unwrap().as_u16(), hex_code);
+	}
+	assert!(ExifTagFormat::try_from(0x0000).is_err());
+	assert!(ExifTagFormat::try_from(0x000d).is_err());
+
+	for group in [
+		ExifTagGroup::NO_GROUP,
+		ExifTagGroup::IFD0,
+		ExifTagGroup::ExifIFD,
+		ExifTagGroup::InteropIFD,
+		ExifTagGroup::MakerNotesIFD,
+		ExifTagGroup::GPSIFD,
+		ExifTagGroup::IFD1,
+	]
+	{
+		assert_eq!(ExifTagGroup::try_from(group.as_u16()).unwrap(), group);
+	}
+	assert!(ExifTagGroup::IFD0.as_u16() < ExifTagGroup::IFD1.as_u16());
+	assert!(ExifTagGroup::try_from(7).is_err());
+}
+
+#[test]
+fn
+exiftool_type_names()
+{
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	for hex_code in 0x0001..=0x000c
+	{
+		let format = ExifTagFormat::from_u16(hex_code).unwrap();
+		assert_eq!(ExifTagFormat::from_type_name(format.type_name()), Some(format));
+	}
+	assert_eq!(ExifTagFormat::STRING.type_name(),            "string");
+	assert_eq!(ExifTagFormat::from_type_name("UNDEF"),       Some(ExifTagFormat::UNDEF));
+	assert_eq!(ExifTagFormat::from_type_name("rational64s"), Some(ExifTagFormat::RATIONAL64S));
+	assert_eq!(ExifTagFormat::from_type_name("int64u"),      None);
+	assert_eq!(ExifTagFormat::from_type_name(""),            None);
+}
+
+#[test]
+fn
+all_known_tags()
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::exif_tag_format::ExifTagFormat;
+
+	let known_tags = little_exif::all_known_tags();
+	assert!(known_tags.contains(&(0x010e, "ImageDescription", ExifTagFormat::STRING, ExifTagGroup::IFD0)));
+	assert!(known_tags.contains(&(0x8827, "ISO", ExifTagFormat::INT16U, ExifTagGroup::ExifIFD)));
+	assert!(known_tags.contains(&(0x000d, "GPSSpeed", ExifTagFormat::RATIONAL64U, ExifTagGroup::GPSIFD)));
+
+	// Names are unique, the hex values only within a group
+	let mut names = known_tags.iter().map(|(_, name, _, _)| *name).collect::<Vec<&str>>();
+	names.sort();
+	names.dedup();
+	assert_eq!(names.len(), known_tags.len());
+
+	for (hex_value, name, format, group) in &known_tags
+	{
+		assert_eq!(known_tags.iter().filter(|(other_hex, _, _, other_group)| other_hex == hex_value && other_group == group).count(), 1, "{}", name);
+
+		// Consistent with the tags themselves, except for hex values that
+		// are shared with tags of other groups
+		let tag = ExifTag::from_u16(*hex_value).unwrap();
+		if tag.get_group() == *group
+		{
+			assert_eq!(tag.format(), *format);
+			assert_eq!(format!("{:?}", tag).split('(').next(), Some(*name));
+		}
+	}
+}
+
+#[test]
+fn
+typed_array_accessors()
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	assert_eq!(ExifTag::BitsPerSample(vec![8, 8, 8]).as_u16_vec(),                            Some(vec![8, 8, 8]));
+	assert_eq!(ExifTag::BitsPerSample(vec![8, 8, 8]).as_u32_vec(),                            Some(vec![8, 8, 8]));
+	assert_eq!(ExifTag::ImageWidth(vec![70000]).as_u16_vec(),                                 None);
+	assert_eq!(ExifTag::ImageWidth(vec![70000]).as_u32_vec(),                                 Some(vec![70000]));
+	assert_eq!(ExifTag::UnknownINT8U(vec![1, 2], 0xc000, ExifTagGroup::IFD0).as_u16_vec(),    Some(vec![1, 2]));
+	assert_eq!(ExifTag::UnknownINT16S(vec![-1], 0xc000, ExifTagGroup::IFD0).as_u32_vec(),     None);
+	assert_eq!(ExifTag::Artist("Jane Doe".to_string()).as_u32_vec(),                          None);
+}
+
+#[test]
+fn
+typed_getters()
+-> Result<(), std::io::Error>
+{
+	use little_exif::rational::uR64;
+	use little_exif::exif_tag::ExifTagGroup;
+
+	let mut metadata = get_test_metadata()?;
+	metadata.set_tag(ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 250 }]));
+	metadata.set_tag(ExifTag::ExifImageWidth(vec![4000]));
+
+	assert_eq!(metadata.get_u16(0x8827, ExifTagGroup::ExifIFD),      Some(2706));
+	assert_eq!(metadata.get_u32(0x8827, ExifTagGroup::ExifIFD),      Some(2706));
+	assert_eq!(metadata.get_u32(0xa002, ExifTagGroup::ExifIFD),      Some(4000));
+	assert_eq!(metadata.get_string(0x010e, ExifTagGroup::IFD0),      Some("Hello World!".to_string()));
+	assert_eq!(metadata.get_rational(0x829a, ExifTagGroup::ExifIFD), Some(vec![uR64 { nominator: 1, denominator: 250 }]));
+
+	// Wrong format, wrong group or not present
+	assert_eq!(metadata.get_u16(0xa002, ExifTagGroup::ExifIFD),      None);
+	assert_eq!(metadata.get_string(0x8827, ExifTagGroup::ExifIFD),   None);
+	assert_eq!(metadata.get_rational(0x010e, ExifTagGroup::IFD0),    None);
+	assert_eq!(metadata.get_u16(0x8827, ExifTagGroup::IFD0),         None);
+	assert_eq!(metadata.get_u16(0x9999, ExifTagGroup::ExifIFD),      None);
+
+	// The same hex value in two IFDs: ProcessingSoftware of IFD0 and the
+	// (unknown to little_exif) GPSDOP of the GPSIFD
+	metadata.set_tag(ExifTag::UnknownRATIONAL64U(vec![uR64 { nominator: 5, denominator: 2 }], 0x000b, ExifTagGroup::GPSIFD));
+	metadata.set_tag(ExifTag::ProcessingSoftware("little_exif".to_string()));
+	assert_eq!(metadata.get_string(0x000b, ExifTagGroup::IFD0),      Some("little_exif".to_string()));
+	assert_eq!(metadata.get_rational(0x000b, ExifTagGroup::GPSIFD),  Some(vec![uR64 { nominator: 5, denominator: 2 }]));
+	assert_eq!(metadata.get_string(0x000b, ExifTagGroup::GPSIFD),    None);
+	assert_eq!(metadata.get_rational(0x000b, ExifTagGroup::IFD0),    None);
+
+	Ok(())
+}
+
+#[test]
+fn
+apex_conversion()
+{
+	use little_exif::rational::{iR64, uR64};
+
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_shutter_speed_seconds(), None);
+	assert_eq!(metadata.get_aperture_fnumber(),      None);
+
+	// Tv = 7.97 and Av = 2.97 as written by cameras for 1/250s at f/2.8
+	metadata.set_tag(ExifTag::ShutterSpeedValue(vec![iR64 { nominator: 797, denominator: 100 }]));
+	metadata.set_tag(ExifTag::ApertureValue(vec![uR64 { nominator: 297, denominator: 100 }]));
+	assert_eq!(metadata.get_shutter_speed_seconds(), Some(1.0 / 250.0));
+	assert_eq!(metadata.get_aperture_fnumber(),      Some(2.8));
+
+	// Long exposures have a negative Tv
+	metadata.set_tag(ExifTag::ShutterSpeedValue(vec![iR64 { nominator: -3, denominator: 1 }]));
+	metadata.set_tag(ExifTag::ApertureValue(vec![uR64 { nominator: 6, denominator: 1 }]));
+	assert_eq!(metadata.get_shutter_speed_seconds(), Some(8.0));
+	assert_eq!(metadata.get_aperture_fnumber(),      Some(8.0));
+
+	// Not close to a standard value
+	metadata.set_tag(ExifTag::ShutterSpeedValue(vec![iR64 { nominator: 35, denominator: 10 }]));
+	assert_eq!(metadata.get_shutter_speed_seconds(), Some(1.0 / 11.0));
+
+	// Invalid values
+	metadata.set_tag(ExifTag::ShutterSpeedValue(vec![iR64 { nominator: 1, denominator: 0 }]));
+	metadata.set_tag(ExifTag::ApertureValue(vec![]));
+	assert_eq!(metadata.get_shutter_speed_seconds(), None);
+	assert_eq!(metadata.get_aperture_fnumber(),      None);
+}
+
+#[test]
+fn
+read_gps_speed_and_directions_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::NorthReference;
+	use little_exif::rational::uR64;
+
+	// Drone footage with speed, track and (magnetic) image direction
+	let mut drone_metadata = Metadata::new();
+	drone_metadata.set_tag(ExifTag::GPSSpeedRef("K".to_string()));
+	drone_metadata.set_tag(ExifTag::GPSSpeed(vec![uR64 { nominator: 54, denominator: 1 }]));
+	drone_metadata.set_tag(ExifTag::GPSTrackRef("T".to_string()));
+	drone_metadata.set_tag(ExifTag::GPSTrack(vec![uR64 { nominator: 270, denominator: 1 }]));
+	drone_metadata.set_tag(ExifTag::GPSImgDirectionRef("M".to_string()));
+	drone_metadata.set_tag(ExifTag::GPSImgDirection(vec![uR64 { nominator: 12345, denominator: 100 }]));
+
+	let metadata = Metadata::new_from_vec_autodetect(&drone_metadata.as_minimal_jpeg())?;
+	assert_eq!(metadata.get_gps_speed_kmh(),     Some(54.0));
+	assert_eq!(metadata.get_gps_track(),         Some((270.0,  NorthReference::True)));
+	assert_eq!(metadata.get_gps_img_direction(), Some((123.45, NorthReference::Magnetic)));
+	assert!(metadata.data().iter().all(|tag| !tag.is_unknown()));
+
+	// Other units and missing references
+	let mut metadata = Metadata::new();
+	assert_eq!(metadata.get_gps_speed_kmh(), None);
+
+	metadata.set_tag(ExifTag::GPSSpeed(vec![uR64 { nominator: 10, denominator: 1 }]));
+	metadata.set_tag(ExifTag::GPSImgDirection(vec![uR64 { nominator: 90, denominator: 1 }]));
+	assert_eq!(metadata.get_gps_speed_kmh(),     Some(10.0));
+	assert_eq!(metadata.get_gps_img_direction(), Some((90.0, NorthReference::True)));
+
+	metadata.set_tag(ExifTag::GPSSpeedRef("N".to_string()));
+	assert!((metadata.get_gps_speed_kmh().unwrap() - 18.52).abs() < 1e-9);
+	metadata.set_tag(ExifTag::GPSSpeedRef("M".to_string()));
+	assert!((metadata.get_gps_speed_kmh().unwrap() - 16.09344).abs() < 1e-9);
+	metadata.set_tag(ExifTag::GPSSpeedRef("X".to_string()));
+	assert_eq!(metadata.get_gps_speed_kmh(), None);
+
+	metadata.set_tag(ExifTag::GPSImgDirectionRef("X".to_string()));
+	assert_eq!(metadata.get_gps_img_direction(), None);
+	assert_eq!(metadata.get_gps_track(),         None);
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_subject_area_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::metadata::SubjectArea;
+
+	if let Err(error) = remove_file("tests/sample2_subject_area_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_subject_area_copy.jpg")?;
+	let path = Path::new("tests/sample2_subject_area_copy.jpg");
+
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag_checked(ExifTag::SubjectArea(vec![500, 400, 120]))?;
+	metadata.set_tag_checked(ExifTag::SubjectLocation(vec![500, 400]))?;
+	metadata.write_to_file(path)?;
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_subject_area(), Some(SubjectArea::Circle { x: 500, y: 400, diameter: 120 }));
+	assert_eq!(metadata.get_tag(&ExifTag::SubjectLocation(vec![])), Some(&ExifTag::SubjectLocation(vec![500, 400])));
+
+	// The number of values tells the shape
+	let mut metadata = Metadata::new();
+	metadata.set_tag(ExifTag::SubjectArea(vec![10, 20]));
+	assert_eq!(metadata.get_subject_area(), Some(SubjectArea::Point { x: 10, y: 20 }));
+	metadata.set_tag(ExifTag::SubjectArea(vec![10, 20, 30, 40]));
+	assert_eq!(metadata.get_subject_area(), Some(SubjectArea::Rectangle { x: 10, y: 20, width: 30, height: 40 }));
+	metadata.set_tag(ExifTag::SubjectArea(vec![10]));
+	assert_eq!(metadata.get_subject_area(), None);
+
+	// The location always consists of two values
+	assert!(metadata.set_tag_checked(ExifTag::SubjectLocation(vec![10])).is_err());
+
+	Ok(())
+}
+
+#[test]
+fn
+write_and_read_subfile_type_jpg()
+-> Result<(), std::io::Error>
+{
+	if let Err(error) = remove_file("tests/sample2_subfile_type_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_subfile_type_copy.jpg")?;
+	let path = Path::new("tests/sample2_subfile_type_copy.jpg");
+
+	// A full-resolution image, using the new and the deprecated tag
+	let mut metadata = Metadata::new_from_path(path)?;
+	metadata.set_tag_checked(ExifTag::NewSubfileType(vec![0]))?;
+	metadata.set_tag_checked(ExifTag::SubfileType(vec![1]))?;
+	metadata.write_to_file(path)?;
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.get_tag(&ExifTag::NewSubfileType(vec![])), Some(&ExifTag::NewSubfileType(vec![0])));
+	assert_eq!(metadata.get_tag(&ExifTag::SubfileType(vec![])), Some(&ExifTag::SubfileType(vec![1])));
+	assert_eq!(ExifTag::NewSubfileType(vec![]).as_u16(), 0x00fe);
+	assert_eq!(ExifTag::SubfileType(vec![]).as_u16(), 0x00ff);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_camera_identity_tags_jpg()
+-> Result<(), std::io::Error>
+{
+	let metadata = Metadata::new_from_path(Path::new("tests/sample2_camera_identity.jpg"))?;
+
+	let identity_tags = [
+		ExifTag::ImageUniqueID("0123456789abcdef0123456789abcdef".to_string()),
+		ExifTag::OwnerName("Jane Doe".to_string()),
+		ExifTag::SerialNumber("3012345".to_string()),
+		ExifTag::LensSerialNumber("20098765".to_string()),
+	];
+	for tag in &identity_tags
+	{
+		assert_eq!(metadata.get_tag(tag), Some(tag));
+		assert!(tag.is_camera_identity_tag());
+	}
+	assert!(!ExifTag::Model(String::new()).is_camera_identity_tag());
+
+	// Round trip
+	if let Err(error) = remove_file("tests/sample2_camera_identity_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	copy("tests/sample2.jpg", "tests/sample2_camera_identity_copy.jpg")?;
+	metadata.write_to_file(Path::new("tests/sample2_camera_identity_copy.jpg"))?;
+	let read_metadata = Metadata::new_from_path(Path::new("tests/sample2_camera_identity_copy.jpg"))?;
+	assert_eq!(read_metadata.data(), metadata.data());
+
+	// And privacy stripping
+	let mut stripped = read_metadata;
+	stripped.retain(|tag| !tag.is_camera_identity_tag());
+	assert_eq!(stripped.data().len(), 2);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_big_endian_subifd()
+-> Result<(), std::io::Error>
+{
+	use little_exif::endian::Endian;
+
+	// Big endian TIFF data with an ExifIFD containing multi-byte values
+	let mut tiff: Vec<u8> = vec![b'M', b'M', 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08];
+	tiff.extend([0x00, 0x01]);
+	tiff.extend([0x87, 0x69, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x1a]);    // ExifOffset
+	tiff.extend([0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0x00, 0x03]);
+	tiff.extend([0x82, 0x9a, 0x00, 0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x44]);    // ExposureTime
+	tiff.extend([0x88, 0x27, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x01, 0x02, 0x00, 0x00]);    // ISO
+	tiff.extend([0xa0, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x02, 0x03]);    // ExifImageWidth
+	tiff.extend([0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0xfa]);
+
+	let mut app1: Vec<u8> = vec![0xff, 0xe1];
+	app1.extend(((2 + 6 + tiff.len()) as u16).to_be_bytes());
+	app1.extend(b"Exif\0\0");
+	app1.extend(&tiff);
+
+	let file_buffer = [vec![0xff, 0xd8], app1.clone(), vec![0xff, 0xd9]].concat();
+	let metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+
+	assert_eq!(metadata.get_endian(), &Endian::Big);
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ExposureTime(vec![])),
+		Some(&ExifTag::ExposureTime(vec![little_exif::rational::uR64 { nominator: 1, denominator: 250 }]))
+	);
+	assert_eq!(metadata.get_tag(&ExifTag::ISO(vec![])),            Some(&ExifTag::ISO(vec![0x0102])));
+	assert_eq!(metadata.get_tag(&ExifTag::ExifImageWidth(vec![])), Some(&ExifTag::ExifImageWidth(vec![0x00010203])));
+
+	// Encoding again keeps the byte order of the SubIFD values
+	assert_eq!(metadata.as_u8_vec(FileExtension::JPEG), app1);
+
+	Ok(())
+}
+
+#[test]
+fn
+decode_warnings_for_skipped_entries_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::metadata::DecodeWarning;
+
+	// IFD0 with a valid Make, an ImageDescription whose data is beyond the
+	// end of the EXIF data and an Orientation with an illegal format 
+	let mut tiff = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00, 0x03, 0x00];
+	tiff.extend([0x0f, 0x01, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x54, 0x65, 0x73, 0x00]);
+	tiff.extend([0x0e, 0x01, 0x02, 0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x00]);
+	tiff.extend([0x12, 0x01, 0x20, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]);
+	tiff.extend([0x00, 0x00, 0x00, 0x00]);
+
+	let mut file_buffer = vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 8 + tiff.len() as u8];
+	file_buffer.extend(b"Exif\0\0");
+	file_buffer.extend(tiff);
+	file_buffer.extend([0xff, 0xd9]);
+	std::fs::write("tests/sample_decode_warnings_copy.jpg", file_buffer)?;
+	let path = Path::new("tests/sample_decode_warnings_copy.jpg");
+
+	// By default, the invalid entries prevent decoding IFD0
+	let strict_metadata = Metadata::new_from_path(path)?;
+	assert!(strict_metadata.data().is_empty());
+	assert!(strict_metadata.decode_warnings().is_empty());
+
+	let mut options = DecodeOptions::new();
+	options.skip_invalid_entries = true;
+
+	let metadata = Metadata::new_from_path_with_options(path, &options)?;
+	assert_eq!(metadata.get_make_trimmed(), Some("Tes"));
+	assert_eq!(metadata.data().len(), 1);
+	assert_eq!(metadata.raw_entry_count(), 3);
+	assert_eq!(metadata.decode_warnings(), &vec![
+		DecodeWarning { 
+			group:   ExifTagGroup::IFD0, 
+			offset:  0x0e, 
+			hex_tag: Some(0x010e), 
+			message: "Data of tag 0x010e exceeds the end of the EXIF data!".to_string() 
+		},
+		DecodeWarning { 
+			group:   ExifTagGroup::IFD0, 
+			offset:  0x1a, 
+			hex_tag: Some(0x0112), 
+			message: "Illegal format value!".to_string() 
+		},
+	]);
+	assert_eq!(
+		metadata.decode_warnings()[0].to_string(), 
+		"Skipped entry 0x010e at IFD0+0xe: Data of tag 0x010e exceeds the end of the EXIF data!"
+	);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_and_write_zero_component_entries_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+
+	// IFD0 with a valid Make and entries without any components: 
+	// ImageDescription, Orientation, XResolution and an unknown tag
+	let mut tiff = vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00, 0x05, 0x00];
+	tiff.extend([0x0e, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0x0f, 0x01, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, 0x54, 0x65, 0x73, 0x00]);
+	tiff.extend([0x12, 0x01, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0x1a, 0x01, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0xa5, 0xc4, 0x07, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	tiff.extend([0x00, 0x00, 0x00, 0x00]);
+
+	let mut file_buffer = vec![0xff, 0xd8, 0xff, 0xe1, 0x00, 8 + tiff.len() as u8];
+	file_buffer.extend(b"Exif\0\0");
+	file_buffer.extend(tiff.iter());
+	file_buffer.extend([0xff, 0xd9]);
+	std::fs::write("tests/sample_zero_components_copy.jpg", file_buffer)?;
+	let path = Path::new("tests/sample_zero_components_copy.jpg");
+
+	let expected = vec![
+		ExifTag::ImageDescription(String::new()),
+		ExifTag::Make("Tes".to_string()),
+		ExifTag::Orientation(vec![]),
+		ExifTag::XResolution(vec![]),
+		ExifTag::UnknownUNDEF(vec![], 0xc4a5, ExifTagGroup::IFD0),
+	];
+
+	let metadata = Metadata::new_from_path(path)?;
+	assert_eq!(metadata.data(), &expected);
+	assert_eq!(metadata.get_tag(&ExifTag::Orientation(vec![])).map(|tag| tag.number_of_components()), Some(0));
+
+	// Written back as entries without components, apart from the string,
+	// which gets its NUL terminator
+	metadata.write_to_file(path)?;
+	let written = std::fs::read(path)?;
+	let written_tiff = &written[12..(written.len()-2)];
+	assert_eq!(&written_tiff[10..22], &[0x0e, 0x01, 0x02, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+	assert_eq!(&written_tiff[22..], &tiff[22..]);
+
+	assert_eq!(Metadata::new_from_path(path)?.data(), &expected);
+
+	Ok(())
+}
+
+#[test]
+fn
+read_component_count_mismatch_jpg()
+-> Result<(), std::io::Error>
+{
+	use little_exif::exif_tag::ExifTagGroup;
+	use little_exif::metadata::DecodeWarning;
+
+	// Little endian IFD0 with a valid Make, an Orientation with three instead
+	// of a single component and an XResolution declaring more components 
+	// than there is data available
+	let tiff_data: Vec<u8> = [
+		vec![0x49, 0x49, 0x2a, 0x00, 0x08, 0x00, 0x00, 0x00],                  // TIFF header
+		vec![0x03, 0x00],                                                      // Number of entries
+		vec![0x0f, 0x01, 0x02, 0x00, 0x04, 0x00, 0x00, 0x00, b'T', b'e', b's', 0x00], // Make
+		vec![0x12, 0x01, 0x03, 0x00, 0x03, 0x00, 0x00, 0x00, 0x32, 0x00, 0x00, 0x00], // Orientation
+		vec![0x1a, 0x01, 0x05, 0x00, 0x04, 0x00, 0x00, 0x00, 0x38, 0x00, 0x00, 0x00], // XResolution
+		vec![0x00, 0x00, 0x00, 0x00],                                          // No next IFD
+		vec![0x01, 0x00, 0x01, 0x00, 0x01, 0x00],                              // Orientation values
+		vec![0x48, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00],                  // The only XResolution value
+	].concat();
+
+	if let Err(error) = remove_file("tests/sample2_component_count_mismatch_copy.jpg")
+	{
+		println!("{}", error);
+	}
+	std::fs::write("tests/sample2_component_count_mismatch_copy.jpg", minimal_jpg(&tiff_data))?;
+	let path = Path::new("tests/sample2_component_count_mismatch_copy.jpg");
+
+	let strict_metadata = Metadata::new_from_path(path)?;
+	assert!(strict_metadata.data().is_empty());
+
+	let mut options = DecodeOptions::new();
+	options.skip_invalid_entries = true;
+
+	// The Orientation keeps its three components
+	let metadata = Metadata::new_from_path_with_options(path, &options)?;
+	assert_eq!(metadata.data(), &vec![
+		ExifTag::Make("Tes".to_string()),
+		ExifTag::Orientation(vec![1, 1, 1]),
+	]);
+	assert_eq!(metadata.decode_warnings(), &vec![
+		DecodeWarning { 
+			group:   ExifTagGroup::IFD0, 
+			offset:  0x1a, 
+			hex_tag: Some(0x011a), 
+			message: "Data of tag 0x011a exceeds the end of the EXIF data!".to_string() 
+		},
+	]);
+
+	// The remaining tags can be written back with their actual counts
+	let written = Metadata::from_header_bytes(
+		&[vec![0xff, 0xd8], metadata.as_u8_vec(FileExtension::JPEG), vec![0xff, 0xd9]].concat(), 
+		FileExtension::JPEG
+	)?;
+	assert_eq!(written.data(), metadata.data());
+
+	Ok(())
+}
+
+#[test]
+fn
+read_grayscale_bits_per_sample_jpg()
+-> Result<(), std::io::Error>
+{
+	// Big endian IFD0 of a grayscale image, whose BitsPerSample has a single
+	// component instead of the three predefined for RGB images
+	let tiff_data: Vec<u8> = [
+		vec![0x4d, 0x4d, 0x00, 0x2a, 0x00, 0x00, 0x00, 0x08],                  // TIFF header
+		vec![0x00, 0x02],                                                      // Number of entries
+		vec![0x01, 0x02, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x08, 0x00, 0x00], // BitsPerSample
+		vec![0x01, 0x0f, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, b'A', b'B', 0x00, 0x00], // Make
+		vec![0x00, 0x00, 0x00, 0x00],                                          // No next IFD
+	].concat();
+	let file_buffer = minimal_jpg(&tiff_data);
+
+	// Decoded with the default options and without any warnings
+	let metadata = Metadata::from_header_bytes(&file_buffer, FileExtension::JPEG)?;
+	assert_eq!(metadata.data(), &vec![
+		ExifTag::BitsPerSample(vec![8]),
+		ExifTag::Make("AB".to_string()),
+	]);
+	assert!(metadata.decode_warnings().is_empty());
+
+	let bits_per_sample = &metadata.data()[0];
+	assert_eq!(bits_per_sample.number_of_components(),            1);
+	assert_eq!(bits_per_sample.predefined_number_of_components(), Some(3));
+	assert!(!bits_per_sample.has_valid_number_of_components());
+
+	// Written back unchanged, i.e. still with a single component
+	assert_eq!(metadata.as_u8_vec(FileExtension::JPEG), file_buffer[2..(file_buffer.len() - 2)]);
+
+	Ok(())
+}
+
+#[test]
+fn
+prelude()
+-> Result<(), std::io::Error>
+{
+	use little_exif::prelude::*;
+
+	let mut metadata = Metadata::new_from_path_with_options(Path::new("tests/sample2_camera.jpg"), &DecodeOptions::new())?;
+	assert_eq!(metadata.get_endian(), &Endian::Big);
+
+	metadata.set_tag(ExifTag::ExposureTime(vec![uR64 { nominator: 1, denominator: 250 }]));
+	metadata.set_tag(ExifTag::UnknownINT32S(vec![-1], 0x9999, ExifTagGroup::ExifIFD));
+	assert_eq!(
+		metadata.get_tag(&ExifTag::ExposureTime(vec![])).map(|tag| tag.components()), 
+		Some(TagValue::Rational(vec![uR64 { nominator: 1, denominator: 250 }]))
+	);
+	assert!(!metadata.as_u8_vec(FileExtension::WEBP).is_empty());
+
+	let _ = iR64 { nominator: -1, denominator: 3 };
+
+	Ok(())
+}
+
+#[test]
+fn
+transcode_metadata()
+-> Result<(), std::io::Error>
+{
+	for (source, destination, copy_path) in [
+		("tests/sample2_camera.jpg",         "tests/sample2.png",           "tests/sample2_transcode_copy.png"),
+		("tests/sample2_thumbnail.jpg",      "tests/sample2_extended.webp", "tests/sample2_transcode_copy.webp"),
+		("tests/sample2_transcode_copy.png", "tests/sample2.jpg",           "tests/sample2_transcode_copy.jpg"),
+	]
+	{
+		if let Err(error) = remove_file(copy_path)
+		{
+			println!("{}", error);
+		}
+		copy(destination, copy_path)?;
+
+		little_exif::transcode_metadata(Path::new(source), Path::new(copy_path))?;
+
+		let original    = Metadata::new_from_path(Path::new(source))?;
+		let transcoded  = Metadata::new_from_path(Path::new(copy_path))?;
+		assert!(original.diff(&transcoded).is_empty());
+		assert_eq!(original.get_thumbnail(), transcoded.get_thumbnail());
+		assert_eq!(original.get_endian(),    transcoded.get_endian());
+	}
+
+	// Sources without EXIF data leave the destination untouched
+	let before = std::fs::read("tests/sample2_transcode_copy.jpg")?;
+	assert!(little_exif::transcode_metadata(
+		Path::new("tests/read_sample_no_exif.webp"), 
+		Path::new("tests/sample2_transcode_copy.jpg")
+	).is_err());
+	assert_eq!(std::fs::read("tests/sample2_transcode_copy.jpg")?, before);
+
+	Ok(())
+}